@@ -528,6 +528,125 @@ fn build_search(rows: &[LogRow]) -> Vec<SearchEntry> {
     entries
 }
 
+/// Gateway access counts for one runtime, used to rank stub entries when
+/// `[build] section_order = "usage"` per [[RFC-0001:C-SECTIONS]].
+///
+/// Only successful accesses count. Section names are matched
+/// case-insensitively, as `skc show` does.
+#[derive(Debug, Default)]
+pub struct AccessCounts {
+    /// `show` hits keyed by (file, lowercased section); file is `None` when not logged
+    sections: HashMap<(Option<String>, String), i64>,
+    /// `open` hits and `show` hits, keyed by file
+    files: HashMap<String, i64>,
+    /// Search queries as lowercased terms
+    queries: HashMap<Vec<String>, i64>,
+}
+
+impl AccessCounts {
+    /// Load counts from the access log in `runtime_dir`. A missing log counts nothing.
+    pub fn load(runtime_dir: &Path) -> Result<Self> {
+        let db_path = runtime_dir.join(".skillc-meta").join("logs.db");
+        if !db_path.exists() {
+            return Ok(Self::default());
+        }
+        let conn = Connection::open(&db_path)?;
+        let mut stmt = conn.prepare("SELECT command, args FROM access_log WHERE error IS NULL")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut counts = Self::default();
+        for (command, args) in rows {
+            counts.record(&command, &args);
+        }
+        Ok(counts)
+    }
+
+    /// Count one successful gateway access.
+    pub(crate) fn record(&mut self, command: &str, args: &str) {
+        match command {
+            "show" => {
+                if let Some((section, file)) = parse_show_args(args) {
+                    let file = file.map(|f| normalize_log_path(&f));
+                    if let Some(file) = &file {
+                        *self.files.entry(file.clone()).or_insert(0) += 1;
+                    }
+                    let section = normalize_section(&section);
+                    *self.sections.entry((file, section)).or_insert(0) += 1;
+                }
+            }
+            "open" => {
+                if let Some(path) = parse_open_args(args) {
+                    *self.files.entry(normalize_log_path(&path)).or_insert(0) += 1;
+                }
+            }
+            "search" => {
+                if let Some(query) = parse_search_args(args) {
+                    let terms: Vec<String> =
+                        query.split_whitespace().map(str::to_lowercase).collect();
+                    if !terms.is_empty() {
+                        *self.queries.entry(terms).or_insert(0) += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// True if nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty() && self.files.is_empty() && self.queries.is_empty()
+    }
+
+    /// Hits for a heading: `show` requests for it, plus searches whose terms
+    /// all appear in its text.
+    pub fn section_hits(&self, file: &Path, heading: &str) -> i64 {
+        let file = normalize_log_path(&file.to_string_lossy());
+        let heading = normalize_section(heading);
+        let shown = self
+            .sections
+            .get(&(Some(file), heading.clone()))
+            .copied()
+            .unwrap_or(0)
+            + self
+                .sections
+                .get(&(None, heading.clone()))
+                .copied()
+                .unwrap_or(0);
+        let searched: i64 = self
+            .queries
+            .iter()
+            .filter(|(terms, _)| terms.iter().all(|t| heading.contains(t.as_str())))
+            .map(|(_, count)| count)
+            .sum();
+        shown + searched
+    }
+
+    /// Hits for a file: `open` requests and `show` requests that matched it.
+    pub fn file_hits(&self, file: &Path) -> i64 {
+        let file = normalize_log_path(&file.to_string_lossy());
+        self.files.get(&file).copied().unwrap_or(0)
+    }
+}
+
+/// Logged paths use the platform separator; compare them with `/`.
+fn normalize_log_path(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+/// Lowercase a section name, dropping an em-dash description suffix as `skc show` does.
+fn normalize_section(section: &str) -> String {
+    section
+        .split(" — ")
+        .next()
+        .unwrap_or(section)
+        .trim()
+        .to_lowercase()
+}
+
 fn parse_search_args(args: &str) -> Option<String> {
     let parsed: serde_json::Value = serde_json::from_str(args).ok()?;
     parsed
@@ -747,6 +866,30 @@ mod tests {
         assert_eq!(sections[0].section, "A");
     }
 
+    #[test]
+    fn test_access_counts() {
+        let mut counts = AccessCounts::default();
+        assert!(counts.is_empty());
+        counts.record(
+            "show",
+            r#"{"section":"Setup — install steps","file":"SKILL.md"}"#,
+        );
+        counts.record("show", r#"{"section":"setup"}"#);
+        counts.record("open", r#"{"path":"./refs/api.md"}"#);
+        counts.record("search", r#"{"query":"Error Handling","result_count":2}"#);
+        counts.record("outline", r#"{}"#);
+
+        assert_eq!(counts.section_hits(Path::new("SKILL.md"), "Setup"), 2);
+        assert_eq!(counts.section_hits(Path::new("refs/api.md"), "Setup"), 1);
+        assert_eq!(
+            counts.section_hits(Path::new("SKILL.md"), "Error handling and retries"),
+            1
+        );
+        assert_eq!(counts.section_hits(Path::new("SKILL.md"), "Errors"), 0);
+        assert_eq!(counts.file_hits(Path::new("refs/api.md")), 1);
+        assert_eq!(counts.file_hits(Path::new("SKILL.md")), 1);
+    }
+
     #[test]
     fn test_build_files_sorted() {
         let rows = vec![
@@ -0,0 +1,219 @@
+//! Batch build of every skill in the source stores (`skc build --all`).
+//!
+//! Discovery reuses [`crate::list::list`]; each skill is compiled to its
+//! SSOT and deployed exactly as a single `skc build` would.
+
+use crate::compiler::{BuildReport, CompileOptions, CompileStatus, compile_with_report};
+use crate::config::{TargetSpec, find_project_root, global_runtime_store};
+use crate::deploy;
+use crate::error::{Result, SkillcError};
+use crate::list::{ListOptions, SkillScope, list};
+use crate::util::project_skill_runtime_dir;
+use comfy_table::{Cell, Color, ContentArrangement, Table};
+use rayon::prelude::*;
+use std::path::Path;
+
+/// Options for [`build_all`].
+#[derive(Debug, Default)]
+pub struct BatchOptions {
+    /// Restrict to one source store, or None for both
+    pub scope: Option<SkillScope>,
+    /// Filter by skill name (glob)
+    pub pattern: Option<String>,
+    /// Targets to deploy each skill to
+    pub targets: Vec<TargetSpec>,
+    /// Force copy instead of symlink/junction
+    pub copy: bool,
+    /// Rebuild and redeploy even if up to date
+    pub force: bool,
+    /// Also render and deploy each target's native instruction file
+    pub native: bool,
+}
+
+/// Outcome for a single skill in a batch build.
+#[derive(Debug)]
+pub enum BatchStatus {
+    Built,
+    UpToDate,
+    Failed(String),
+}
+
+/// Per-skill result of a batch build.
+#[derive(Debug)]
+pub struct BatchEntry {
+    pub name: String,
+    pub scope: SkillScope,
+    pub status: BatchStatus,
+    /// Build report with its deployments, unless the skill failed
+    pub report: Option<BuildReport>,
+}
+
+/// Result of a batch build.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchResult {
+    /// Number of skills that failed to build or deploy.
+    pub fn failed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, BatchStatus::Failed(_)))
+            .count()
+    }
+}
+
+/// Build every discovered skill in parallel.
+///
+/// Individual failures are recorded in the result rather than aborting the batch.
+pub fn build_all(options: &BatchOptions) -> Result<BatchResult> {
+    let listed = list(&ListOptions {
+        scope: options.scope,
+        pattern: options.pattern.clone(),
+        ..Default::default()
+    })?;
+
+    let project_root = find_project_root();
+    let global_runtime = global_runtime_store()?;
+
+    let entries = listed
+        .skills
+        .par_iter()
+        .map(|skill| {
+            let (ssot, deploy_root) = match (skill.scope, project_root.as_deref()) {
+                (SkillScope::Project, Some(root)) => {
+                    (project_skill_runtime_dir(root, &skill.name), Some(root))
+                }
+                _ => (global_runtime.join(&skill.name), None),
+            };
+
+            let (status, report) =
+                match build_one(&skill.source_path, &ssot, &skill.name, deploy_root, options) {
+                    Ok(report) => {
+                        let status = match report.status {
+                            CompileStatus::Built => BatchStatus::Built,
+                            CompileStatus::UpToDate => BatchStatus::UpToDate,
+                        };
+                        (status, Some(report))
+                    }
+                    Err(e) => (BatchStatus::Failed(e.to_string()), None),
+                };
+
+            BatchEntry {
+                name: skill.name.clone(),
+                scope: skill.scope,
+                status,
+                report,
+            }
+        })
+        .collect();
+
+    Ok(BatchResult { entries })
+}
+
+/// Compile one skill and deploy it to every target, natively too where asked.
+fn build_one(
+    source: &Path,
+    ssot: &Path,
+    skill_name: &str,
+    deploy_root: Option<&Path>,
+    options: &BatchOptions,
+) -> Result<BuildReport> {
+    let native = deploy::native_targets(ssot, &options.targets, options.native);
+    let mut report = compile_with_report(
+        source,
+        ssot,
+        &CompileOptions {
+            force: options.force,
+            native: native.clone(),
+            ..Default::default()
+        },
+    )?;
+
+    report.deployments = deploy::deploy_targets(
+        ssot,
+        &options.targets,
+        skill_name,
+        options.copy,
+        deploy_root,
+        options.force,
+    )?;
+    report.deployments.extend(deploy::deploy_native(
+        ssot,
+        &native,
+        skill_name,
+        deploy_root,
+    )?);
+
+    Ok(report)
+}
+
+/// Format a batch result as a per-skill table.
+pub fn format_batch(result: &BatchResult) -> String {
+    if result.entries.is_empty() {
+        return "No skills found.".to_string();
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(comfy_table::presets::NOTHING)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    // Ensure a minimum width for headless environments (CI, pre-commit)
+    if table.width().unwrap_or(0) < 80 {
+        table.set_width(120);
+    }
+
+    table.set_header(vec!["SKILL", "SCOPE", "RESULT", "DETAIL"]);
+
+    for entry in &result.entries {
+        let deployed = entry.report.as_ref().map_or(0, |r| r.deployments.len());
+        let (result_cell, detail) = match &entry.status {
+            BatchStatus::Built => (
+                Cell::new("built").fg(Color::Green),
+                format!("{} deployment(s)", deployed),
+            ),
+            BatchStatus::UpToDate => (
+                Cell::new("up-to-date").fg(Color::DarkGrey),
+                format!("{} deployment(s)", deployed),
+            ),
+            BatchStatus::Failed(msg) => (Cell::new("failed").fg(Color::Red), msg.clone()),
+        };
+        table.add_row(vec![
+            Cell::new(&entry.name),
+            Cell::new(entry.scope.to_string()),
+            result_cell,
+            Cell::new(detail),
+        ]);
+    }
+
+    let failed = result.failed_count();
+    format!(
+        "{}\n\n{} skill(s), {} failed",
+        table,
+        result.entries.len(),
+        failed
+    )
+}
+
+/// Format a batch result as a JSON array with one build report per skill.
+///
+/// A failed skill is reported as `{"skill", "status": "failed", "error"}`.
+pub fn format_batch_json(result: &BatchResult) -> Result<String> {
+    let reports = result
+        .entries
+        .iter()
+        .map(|entry| match &entry.status {
+            BatchStatus::Failed(msg) => Ok(serde_json::json!({
+                "skill": entry.name,
+                "status": "failed",
+                "error": msg,
+            })),
+            BatchStatus::Built | BatchStatus::UpToDate => serde_json::to_value(&entry.report),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| SkillcError::Internal(format!("failed to serialize report: {}", e)))?;
+    serde_json::to_string_pretty(&reports)
+        .map_err(|e| SkillcError::Internal(format!("failed to serialize report: {}", e)))
+}
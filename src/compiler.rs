@@ -1,37 +1,502 @@
 //! Skill compiler per [[RFC-0001]] and RFC-0004
 
 use crate::Heading;
-use crate::config::ensure_dir;
-use crate::error::{Result, SkillcError};
+use crate::analytics::AccessCounts;
+use crate::conditional;
+use crate::config::{self, BudgetMode, SectionOrder, Target, TargetSpec, ensure_dir};
+use crate::deploy::Deployment;
+use crate::emit;
+use crate::error::{Result, SkillcError, SkillcWarning};
 use crate::frontmatter::{self, Frontmatter};
+use crate::history;
+use crate::hooks::{self, HookPhase};
+use crate::include;
+use crate::index::{self, IndexStats};
 use crate::markdown;
 use crate::search;
+use crate::skillignore::SkillIgnore;
+use crate::tokens::{self, SectionTokens};
 use crate::verbose;
-use chrono::Utc;
-use serde::Serialize;
+use crate::verify::{FileChange, diff_inventories};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use walkdir::WalkDir;
 
 /// Build manifest per [[RFC-0001:C-MANIFEST]]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Manifest {
     pub skill: String,
     pub version: u32,
     pub built_at: String,
     pub source_hash: String,
+    /// Estimated token counts (absent in v1 manifests)
+    #[serde(default)]
+    pub tokens: TokenStats,
+    /// Per-file source inventory, sorted by path (absent before v3)
+    #[serde(default)]
+    pub files: Vec<FileHash>,
+    /// SHA-256 of the generated stub (absent before v3)
+    #[serde(default)]
+    pub stub_sha256: String,
+    /// Files outside the skill root pulled in by include directives (absent before v4)
+    #[serde(default)]
+    pub includes: Vec<FileHash>,
+    /// Targets with their own runtime variant, on the shared runtime (absent before v5)
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Target a variant runtime was built for; `None` for the shared runtime
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+/// A source file and its content hash, as recorded in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHash {
+    /// Path relative to the skill root
+    pub path: String,
+    /// Hex-encoded SHA-256 of the file content
+    pub sha256: String,
+}
+
+/// Estimated token counts recorded in the manifest.
+///
+/// Estimates come from [`crate::tokens::estimate_tokens`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenStats {
+    /// Compiled stub, paid in every agent session
+    pub stub: usize,
+    /// Per markdown file, keyed by relative path
+    pub files: BTreeMap<String, usize>,
+    /// Per heading-delimited section
+    pub sections: Vec<SectionTokens>,
+}
+
+/// Current manifest format version per [[RFC-0001:C-MANIFEST]]
+/// v2: Added token estimates.
+/// v3: Added per-file source inventory.
+/// v4: Added include dependencies.
+/// v5: Added per-target runtime variants.
+const MANIFEST_VERSION: u32 = 5;
+
+/// Options for [`compile_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct CompileOptions {
+    /// Rebuild even if the runtime is already up to date
+    pub force: bool,
+    /// Build timestamp (RFC 3339); defaults to [`build_timestamp`]
+    pub built_at: Option<String>,
+    /// Run no `[build]` pre/post hooks
+    pub skip_hooks: bool,
+    /// Targets to render native instruction files for (see [`crate::emit`])
+    pub native: Vec<TargetSpec>,
+}
+
+/// Outcome of a compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompileStatus {
+    /// Stub, manifest, and index were (re)written
+    Built,
+    /// Runtime already matched the source; nothing was written
+    UpToDate,
+}
+
+/// Structured result of a build, for `skc build -o json` and the MCP `skc_build` tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildReport {
+    pub skill: String,
+    pub status: CompileStatus,
+    pub source: PathBuf,
+    pub runtime: PathBuf,
+    pub source_hash: String,
+    /// Headings across all markdown files
+    pub headings: usize,
+    /// Source files covered by the source hash
+    pub files: usize,
+    pub index: IndexStats,
+    /// Lines in the shared runtime's stub
+    pub stub_lines: usize,
+    /// SKILL.md sections left out of the stub listing
+    pub sections_omitted: usize,
+    /// Reference files left out of the stub listing
+    pub references_omitted: usize,
+    /// Agent directory entries; filled in by the caller after deploying
+    pub deployments: Vec<Deployment>,
+    /// Native instruction files rendered into the runtime by `--native`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub native: Vec<PathBuf>,
+}
+
+/// Everything derived from the source before anything is written.
+struct PreparedBuild {
+    name: String,
+    source_hash: String,
+    files: Vec<FileHash>,
+    /// Files outside the skill root pulled in by include directives
+    dependencies: Vec<FileHash>,
+    /// Shared runtime, used by every target without its own variant
+    base: PreparedVariant,
+    /// Runtimes for the targets named by `skc:if` blocks
+    variants: Vec<(Target, PreparedVariant)>,
+}
+
+impl PreparedBuild {
+    /// Summarize the build of the shared runtime.
+    fn report(
+        &self,
+        source_dir: &Path,
+        runtime_dir: &Path,
+        native: &[TargetSpec],
+        status: CompileStatus,
+    ) -> Result<BuildReport> {
+        let mut native_files: Vec<PathBuf> = Vec::new();
+        for path in native
+            .iter()
+            .filter_map(|target| emit::native_output(runtime_dir, target, &self.name))
+        {
+            if !native_files.contains(&path) {
+                native_files.push(path);
+            }
+        }
+        let (sections_omitted, references_omitted) = self.base.omitted;
+        Ok(BuildReport {
+            skill: self.name.clone(),
+            status,
+            source: source_dir.to_path_buf(),
+            runtime: runtime_dir.to_path_buf(),
+            source_hash: self.source_hash.clone(),
+            headings: self.base.headings.len(),
+            files: self.files.len(),
+            index: index::index_stats(runtime_dir, source_dir, &self.name)?,
+            stub_lines: self.base.stub.lines().count(),
+            sections_omitted,
+            references_omitted,
+            deployments: Vec::new(),
+            native: native_files,
+        })
+    }
+
+    /// Estimated tokens of the largest stub across all runtimes.
+    fn stub_tokens(&self) -> usize {
+        self.variants
+            .iter()
+            .map(|(_, variant)| variant.tokens.stub)
+            .fold(self.base.tokens.stub, usize::max)
+    }
+
+    /// Names of the targets with their own variant, as recorded in the manifest.
+    fn target_names(&self) -> Vec<String> {
+        self.variants.iter().map(|(t, _)| t.to_string()).collect()
+    }
+
+    /// Manifest for the shared runtime (`target` is `None`) or one variant.
+    fn manifest(
+        &self,
+        variant: &PreparedVariant,
+        target: Option<Target>,
+        built_at: &str,
+    ) -> Manifest {
+        Manifest {
+            skill: self.name.clone(),
+            version: MANIFEST_VERSION,
+            built_at: built_at.to_string(),
+            source_hash: self.source_hash.clone(),
+            tokens: variant.tokens.clone(),
+            files: self.files.clone(),
+            stub_sha256: sha256_hex(variant.stub.as_bytes()),
+            includes: self.dependencies.clone(),
+            targets: match target {
+                Some(_) => Vec::new(),
+                None => self.target_names(),
+            },
+            target: target.map(|t| t.to_string()),
+        }
+    }
+}
+
+/// Stub, token estimates, and content of one runtime.
+struct PreparedVariant {
+    stub: String,
+    tokens: TokenStats,
+    /// Headings across all markdown files, SKILL.md first
+    headings: Vec<Heading>,
+    /// Stub entries dropped by truncation (SKILL.md sections, references)
+    omitted: (usize, usize),
+    /// Markdown files whose content differs from disk after resolving
+    /// includes and conditionals, keyed by path relative to the skill root
+    content: BTreeMap<PathBuf, String>,
 }
 
 /// Compile a skill from source to runtime directory
-pub fn compile(source_dir: &Path, runtime_dir: &Path) -> Result<()> {
+pub fn compile(source_dir: &Path, runtime_dir: &Path) -> Result<CompileStatus> {
+    compile_with_options(source_dir, runtime_dir, &CompileOptions::default())
+}
+
+/// Compile a skill, skipping the write when the runtime is already up to date.
+///
+/// A build is a no-op when the existing manifest records the same source hash,
+/// the stub on disk matches the one that would be generated, the search
+/// index is current, and each native file in `options.native` matches its
+/// render. `options.force` rebuilds regardless.
+pub fn compile_with_options(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    options: &CompileOptions,
+) -> Result<CompileStatus> {
+    Ok(compile_with_report(source_dir, runtime_dir, options)?.status)
+}
+
+/// Compile a skill like [`compile_with_options`], summarizing the result.
+pub fn compile_with_report(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    options: &CompileOptions,
+) -> Result<BuildReport> {
     let start = Instant::now();
 
     verbose!("build: source_dir={}", source_dir.display());
     verbose!("build: runtime_dir={}", runtime_dir.display());
 
+    let (pre_hooks, post_hooks) = if options.skip_hooks {
+        (Vec::new(), Vec::new())
+    } else {
+        config::get_build_hooks()
+    };
+    let hook_name = hook_skill_name(source_dir);
+
+    // A build that is already up to date runs no hooks. Failing to prepare
+    // here is not an error yet: a pre-hook may be about to generate sources.
+    let mut prepared = if options.force {
+        None
+    } else {
+        prepare(source_dir, runtime_dir).ok()
+    };
+    if let Some(prepared) = &prepared
+        && is_runtime_current(source_dir, runtime_dir, prepared)
+        && emit::native_current(source_dir, runtime_dir, &options.native)
+    {
+        verbose!("build: skipping (up to date)");
+        check_stub_budget(prepared.stub_tokens(), config::get_stub_budget())?;
+        return prepared.report(
+            source_dir,
+            runtime_dir,
+            &options.native,
+            CompileStatus::UpToDate,
+        );
+    }
+
+    // Pre-hooks may change the sources, so they are read again afterwards
+    if !pre_hooks.is_empty() {
+        hooks::run_hooks(
+            HookPhase::Pre,
+            &pre_hooks,
+            &hook_name,
+            source_dir,
+            runtime_dir,
+        )?;
+        prepared = None;
+    }
+    let prepared = match prepared {
+        Some(prepared) => prepared,
+        None => prepare(source_dir, runtime_dir)?,
+    };
+    // Checked here rather than in `prepare`, so each build warns at most once
+    check_stub_budget(prepared.stub_tokens(), config::get_stub_budget())?;
+
+    let built_at = match &options.built_at {
+        Some(built_at) => built_at.clone(),
+        None => build_timestamp()?,
+    };
+
+    // Write the whole runtime into a staging directory, let post-hooks vet
+    // it there, then swap it in; a failing post-hook leaves the live runtime
+    // untouched
+    let staging = history::stage(runtime_dir)?;
+    verbose!("build: staging in {}", staging.display());
+    let staged = write_staged(source_dir, &staging, &prepared, &built_at, &options.native)
+        .and_then(|_| {
+            hooks::run_hooks(
+                HookPhase::Post,
+                &post_hooks,
+                &hook_name,
+                source_dir,
+                &staging,
+            )
+        })
+        .and_then(|_| history::install(&staging, runtime_dir, config::get_keep_builds()));
+    if let Err(e) = staged {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    verbose!("build: completed in {:?}", start.elapsed());
+
+    prepared.report(
+        source_dir,
+        runtime_dir,
+        &options.native,
+        CompileStatus::Built,
+    )
+}
+
+/// Skill name passed to hooks: the frontmatter `name`, or the source
+/// directory's name when SKILL.md is missing or unparseable (a pre-hook may
+/// be about to generate it).
+fn hook_skill_name(source_dir: &Path) -> String {
+    fs::read_to_string(source_dir.join("SKILL.md"))
+        .ok()
+        .and_then(|content| frontmatter::parse(&content).ok())
+        .map(|frontmatter| frontmatter.name)
+        .unwrap_or_else(|| {
+            source_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+}
+
+/// Write the shared runtime, every target variant, and the native renders into `staging`.
+fn write_staged(
+    source_dir: &Path,
+    staging: &Path,
+    prepared: &PreparedBuild,
+    built_at: &str,
+    native: &[TargetSpec],
+) -> Result<()> {
+    let manifest = prepared.manifest(&prepared.base, None, built_at);
+    write_runtime(source_dir, staging, &manifest, &prepared.base)?;
+
+    for (target, variant) in &prepared.variants {
+        let variant_dir = crate::util::target_variant_dir(staging, *target);
+        let manifest = prepared.manifest(variant, Some(*target), built_at);
+        write_runtime(source_dir, &variant_dir, &manifest, variant)?;
+        verbose!("build: wrote {} variant", target);
+    }
+
+    emit::emit_native(source_dir, staging, native)?;
+    Ok(())
+}
+
+/// Timestamp recorded in the manifest and search index.
+///
+/// Honors `SOURCE_DATE_EPOCH` (seconds since the Unix epoch) per the
+/// reproducible-builds convention, so identical sources produce a
+/// byte-identical runtime. Falls back to the current time.
+pub fn build_timestamp() -> Result<String> {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(value) => value
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+            .map(|time| time.to_rfc3339())
+            .ok_or_else(|| {
+                SkillcError::InvalidOption(format!("invalid SOURCE_DATE_EPOCH '{}'", value))
+            }),
+        Err(_) => Ok(Utc::now().to_rfc3339()),
+    }
+}
+
+/// Build a skill twice into scratch runtimes and compare the results.
+///
+/// Both builds share one timestamp (see [`build_timestamp`]), so any
+/// difference means the output depends on something other than the source.
+/// The scratch builds run no hooks: the check compares the source as it is
+/// on disk. Returns the files that differ between the two runtimes.
+pub fn check_reproducible(source_dir: &Path) -> Result<Vec<FileChange>> {
+    let scratch = std::env::temp_dir().join(format!(
+        "skillc-repro-{}-{:016x}",
+        std::process::id(),
+        rand::random::<u64>()
+    ));
+    verbose!("build: reproducible check in {}", scratch.display());
+
+    let result = (|| {
+        let options = CompileOptions {
+            force: true,
+            built_at: Some(build_timestamp()?),
+            skip_hooks: true,
+            ..Default::default()
+        };
+        let first = scratch.join("first");
+        let second = scratch.join("second");
+        compile_with_options(source_dir, &first, &options)?;
+        compile_with_options(source_dir, &second, &options)?;
+        Ok(diff_inventories(
+            &runtime_inventory(&first)?,
+            &runtime_inventory(&second)?,
+        ))
+    })();
+
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Every file in a runtime, including `.skillc-meta`.
+fn runtime_inventory(runtime_dir: &Path) -> Result<Vec<FileHash>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(runtime_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let relative = entry
+            .path()
+            .strip_prefix(runtime_dir)
+            .unwrap_or(entry.path());
+        files.push(FileHash {
+            path: relative.to_string_lossy().to_string(),
+            sha256: sha256_hex(&fs::read(entry.path())?),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Check whether the runtime needs a rebuild without writing anything.
+pub fn needs_rebuild(source_dir: &Path, runtime_dir: &Path) -> Result<bool> {
+    let prepared = prepare(source_dir, runtime_dir)?;
+    Ok(!is_runtime_current(source_dir, runtime_dir, &prepared))
+}
+
+/// The shared runtime as the current source would build it.
+#[derive(Debug)]
+pub struct RenderedRuntime {
+    pub stub: String,
+    /// Headings as indexed: includes expanded, conditional blocks dropped
+    pub headings: Vec<Heading>,
+}
+
+/// Regenerate the shared runtime's stub and headings from the current
+/// source without writing anything.
+///
+/// Runs the same pipeline as a build, so the result compares like-for-like
+/// with the shared runtime's stub and search index.
+pub fn render_runtime(source_dir: &Path, runtime_dir: &Path) -> Result<RenderedRuntime> {
+    let base = prepare(source_dir, runtime_dir)?.base;
+    Ok(RenderedRuntime {
+        stub: base.stub,
+        headings: base.headings,
+    })
+}
+
+/// Read the manifest from a runtime directory, if present and parseable.
+pub fn read_manifest(runtime_dir: &Path) -> Option<Manifest> {
+    let path = runtime_dir.join(".skillc-meta").join("manifest.json");
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Validate the source and derive the stub and source hash.
+///
+/// `runtime_dir` is only read, for the access log when stub entries are
+/// ranked by usage.
+fn prepare(source_dir: &Path, runtime_dir: &Path) -> Result<PreparedBuild> {
     // Validate source directory (E001/E010 per [[RFC-0005:C-CODES]])
     crate::util::validate_skill_path(source_dir)?;
     let skill_md_path = source_dir.join("SKILL.md");
@@ -44,54 +509,314 @@ pub fn compile(source_dir: &Path, runtime_dir: &Path) -> Result<()> {
     let frontmatter = frontmatter::parse(&skill_md_content)?;
     verbose!("build: skill name=\"{}\"", frontmatter.name);
 
-    // Extract headings from all .md files
-    let headings = extract_headings(source_dir)?;
-    verbose!("build: extracted {} headings", headings.len());
-
     // Get list of .md files
     let md_files = list_md_files(source_dir)?;
     verbose!("build: found {} markdown files", md_files.len());
 
-    // Extract reference descriptions per [[RFC-0008:C-REFERENCE-FRONTMATTER]]
-    let descriptions = extract_reference_descriptions(source_dir, &md_files);
-    verbose!("build: found {} reference descriptions", descriptions.len());
-
-    // Compute source hash
-    let source_hash = compute_source_hash(source_dir)?;
+    // Resolve include directives; everything below sees expanded content
+    let expansion = include::expand_includes(source_dir, &md_files)?;
+    verbose!(
+        "build: expanded includes in {} files ({} external)",
+        expansion.files.len(),
+        expansion.dependencies.len()
+    );
+
+    // Validate conditionals and collect the targets that get their own variant
+    let mut targets = BTreeSet::new();
+    let mut conditional_files = BTreeMap::new();
+    for file in &md_files {
+        let content = match expansion.files.get(file) {
+            Some(content) => content.clone(),
+            None => fs::read_to_string(source_dir.join(file))?,
+        };
+        if !conditional::has_conditionals(&content) {
+            continue;
+        }
+        if let Some(problem) = conditional::check(&content).first() {
+            return Err(SkillcError::InvalidDirective(format!(
+                "{}:{}: {}",
+                file.display(),
+                problem.line,
+                problem.message
+            )));
+        }
+        targets.extend(conditional::referenced_targets(&content));
+        conditional_files.insert(file.clone(), content);
+    }
+    verbose!(
+        "build: conditionals in {} files, {} target variants",
+        conditional_files.len(),
+        targets.len()
+    );
+
+    // Compute per-file and aggregate source hashes, covering included files
+    let files = compute_file_hashes(source_dir)?;
+    let hashed: Vec<FileHash> = files
+        .iter()
+        .chain(&expansion.dependencies)
+        .cloned()
+        .collect();
+    let source_hash = combine_file_hashes(&hashed);
     verbose!("build: source_hash={}", &source_hash[..16]);
 
-    // Generate stub
-    let stub = generate_stub(&frontmatter, &headings, &descriptions);
+    // Content as one target sees it; `None` drops every conditional block
+    let content_for = |target: Option<Target>| {
+        let mut content = expansion.files.clone();
+        for (file, raw) in &conditional_files {
+            content.insert(file.clone(), conditional::filter(raw, target));
+        }
+        content
+    };
 
-    // Generate manifest
-    let manifest = Manifest {
-        skill: frontmatter.name.clone(),
-        version: 1,
-        built_at: Utc::now().to_rfc3339(),
+    let template = load_stub_template(source_dir)?;
+    let usage = load_usage(runtime_dir);
+    let base = prepare_variant(
+        source_dir,
+        &frontmatter,
+        &frontmatter.name,
+        &md_files,
+        content_for(None),
+        template.as_deref(),
+        usage.as_ref(),
+    )?;
+    let variants = targets
+        .into_iter()
+        .map(|target| {
+            let gateway_name = format!("{}@{}", frontmatter.name, target);
+            let variant = prepare_variant(
+                source_dir,
+                &frontmatter,
+                &gateway_name,
+                &md_files,
+                content_for(Some(target)),
+                template.as_deref(),
+                usage.as_ref(),
+            )?;
+            Ok((target, variant))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(PreparedBuild {
+        name: frontmatter.name,
         source_hash,
+        files,
+        dependencies: expansion.dependencies,
+        base,
+        variants,
+    })
+}
+
+/// Derive the stub and token estimates for one runtime's content.
+///
+/// `gateway_name` is what the stub tells agents to pass to gateway commands:
+/// the skill name, or `name@target` for a target variant.
+fn prepare_variant(
+    source_dir: &Path,
+    frontmatter: &Frontmatter,
+    gateway_name: &str,
+    md_files: &[PathBuf],
+    content: BTreeMap<PathBuf, String>,
+    template: Option<&str>,
+    usage: Option<&AccessCounts>,
+) -> Result<PreparedVariant> {
+    let view = SourceView {
+        root: source_dir,
+        content: &content,
     };
 
+    // Extract headings from all .md files
+    let headings = extract_headings(&view, md_files)?;
+    verbose!("build: extracted {} headings", headings.len());
+
+    // Extract reference descriptions per [[RFC-0008:C-REFERENCE-FRONTMATTER]]
+    let descriptions = extract_reference_descriptions(&view, md_files);
+    verbose!("build: found {} reference descriptions", descriptions.len());
+
+    // Generate stub, through a user template if one applies
+    let stub = generate_stub(
+        frontmatter,
+        gateway_name,
+        &headings,
+        &descriptions,
+        template,
+        usage,
+    )?;
+
+    // Estimate token costs
+    let tokens = compute_token_stats(&view, md_files, &stub)?;
+    verbose!("build: stub tokens=~{}", tokens.stub);
+
+    let sections = collect_stub_sections(&headings, &descriptions, usage);
+
+    Ok(PreparedVariant {
+        stub,
+        tokens,
+        headings,
+        omitted: (sections.skill_omitted, sections.references_omitted),
+        content,
+    })
+}
+
+/// Markdown content as one runtime sees it: on disk, with includes and
+/// conditionals resolved.
+struct SourceView<'a> {
+    root: &'a Path,
+    content: &'a BTreeMap<PathBuf, String>,
+}
+
+impl SourceView<'_> {
+    /// Read a file by path relative to the skill root.
+    fn read(&self, relative: &Path) -> Result<String> {
+        match self.content.get(relative) {
+            Some(content) => Ok(content.clone()),
+            None => Ok(fs::read_to_string(self.root.join(relative))?),
+        }
+    }
+}
+
+/// Write the stub, manifest, content, and search index of one runtime.
+fn write_runtime(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    manifest: &Manifest,
+    variant: &PreparedVariant,
+) -> Result<()> {
     // Ensure runtime directory exists
     ensure_dir(runtime_dir)?;
 
     // Write stub
     let stub_path = runtime_dir.join("SKILL.md");
-    fs::write(&stub_path, &stub)?;
-    verbose!("build: wrote stub ({} bytes)", stub.len());
+    fs::write(&stub_path, &variant.stub)?;
+    verbose!("build: wrote stub ({} bytes)", variant.stub.len());
 
     // Write manifest
     let manifest_dir = runtime_dir.join(".skillc-meta");
     ensure_dir(&manifest_dir)?;
     let manifest_path = manifest_dir.join("manifest.json");
-    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    let manifest_json = serde_json::to_string_pretty(manifest)?;
     fs::write(&manifest_path, &manifest_json)?;
 
+    // Materialize resolved content, then index what agents will read
+    let content_dir = materialize_expanded(source_dir, runtime_dir, &variant.content)?;
+
     // Build search index per [[RFC-0004:C-INDEX]]
-    search::build_index(source_dir, runtime_dir, &manifest.source_hash)?;
+    search::build_index(
+        source_dir,
+        &content_dir,
+        runtime_dir,
+        &manifest.source_hash,
+        &manifest.built_at,
+    )
+}
 
-    verbose!("build: completed in {:?}", start.elapsed());
+/// Write the resolved copy of the source, or remove a stale one.
+///
+/// Returns the directory that the index and gateway read content from.
+fn materialize_expanded(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    content: &BTreeMap<PathBuf, String>,
+) -> Result<PathBuf> {
+    let dir = crate::util::expanded_source_dir(runtime_dir);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    if content.is_empty() {
+        return Ok(source_dir.to_path_buf());
+    }
 
-    Ok(())
+    crate::util::copy_dir_filtered(source_dir, &dir)?;
+    for (relative, content) in content {
+        fs::write(dir.join(relative), content)?;
+    }
+    verbose!("build: materialized expanded source at {}", dir.display());
+
+    Ok(dir)
+}
+
+/// Estimate tokens for the stub and for every markdown file and section.
+fn compute_token_stats(view: &SourceView, md_files: &[PathBuf], stub: &str) -> Result<TokenStats> {
+    let mut stats = TokenStats {
+        stub: tokens::estimate_tokens(stub),
+        ..Default::default()
+    };
+
+    for file in md_files {
+        let content = view.read(file)?;
+        let relative = file.to_string_lossy().to_string();
+        stats
+            .sections
+            .extend(tokens::section_tokens(&relative, &content));
+        stats
+            .files
+            .insert(relative, tokens::estimate_tokens(&content));
+    }
+
+    Ok(stats)
+}
+
+/// Enforce the configured stub token budget.
+///
+/// Over budget is W020 in warn mode and E016 in error mode.
+fn check_stub_budget(stub_tokens: usize, budget: Option<(usize, BudgetMode)>) -> Result<()> {
+    match budget {
+        Some((budget, mode)) if stub_tokens > budget => match mode {
+            BudgetMode::Warn => {
+                SkillcWarning::StubOverBudget(stub_tokens, budget).emit();
+                Ok(())
+            }
+            BudgetMode::Error => Err(SkillcError::StubOverBudget(stub_tokens, budget)),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Check manifest, stub, and index of every runtime against a prepared build.
+fn is_runtime_current(source_dir: &Path, runtime_dir: &Path, prepared: &PreparedBuild) -> bool {
+    let Some(manifest) = read_manifest(runtime_dir) else {
+        return false;
+    };
+    if manifest.targets != prepared.target_names() {
+        return false;
+    }
+
+    is_variant_current(source_dir, runtime_dir, &manifest, prepared, &prepared.base)
+        && prepared.variants.iter().all(|(target, variant)| {
+            let dir = crate::util::target_variant_dir(runtime_dir, *target);
+            read_manifest(&dir).is_some_and(|manifest| {
+                is_variant_current(source_dir, &dir, &manifest, prepared, variant)
+            })
+        })
+}
+
+/// Check one runtime's manifest, stub, content, and index.
+fn is_variant_current(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    manifest: &Manifest,
+    prepared: &PreparedBuild,
+    variant: &PreparedVariant,
+) -> bool {
+    if manifest.version != MANIFEST_VERSION
+        || manifest.skill != prepared.name
+        || manifest.source_hash != prepared.source_hash
+    {
+        return false;
+    }
+
+    let stub_matches = fs::read_to_string(runtime_dir.join("SKILL.md"))
+        .map(|existing| existing == variant.stub)
+        .unwrap_or(false);
+    if !stub_matches {
+        return false;
+    }
+
+    let expanded_exists = crate::util::expanded_source_dir(runtime_dir).exists();
+    if expanded_exists == variant.content.is_empty() {
+        return false;
+    }
+
+    search::index_is_current(source_dir, runtime_dir, &prepared.source_hash)
 }
 
 /// Extract headings from all .md files in the source directory.
@@ -105,14 +830,8 @@ pub fn compile(source_dir: &Path, runtime_dir: &Path) -> Result<()> {
 ///
 /// This ensures the compiled stub's "Top Sections" reflect the main skill
 /// content rather than arbitrary reference files.
-fn extract_headings(source_dir: &Path) -> Result<Vec<Heading>> {
-    // Collect all .md files first
-    let mut md_files: Vec<PathBuf> = WalkDir::new(source_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
-        .map(|e| e.path().to_path_buf())
-        .collect();
+fn extract_headings(view: &SourceView, md_files: &[PathBuf]) -> Result<Vec<Heading>> {
+    let mut md_files = md_files.to_vec();
 
     // Sort: SKILL.md first, then alphabetically by path
     md_files.sort_by(|a, b| {
@@ -127,12 +846,8 @@ fn extract_headings(source_dir: &Path) -> Result<Vec<Heading>> {
 
     let mut headings = Vec::new();
 
-    for path in md_files {
-        let content = fs::read_to_string(&path)?;
-        let relative_path = path
-            .strip_prefix(source_dir)
-            .map_err(|_| SkillcError::Internal("path does not start with source_dir".into()))?
-            .to_path_buf();
+    for relative_path in md_files {
+        let content = view.read(&relative_path)?;
 
         // Use AST-based heading extraction
         for extracted in markdown::extract_headings(&content) {
@@ -148,12 +863,14 @@ fn extract_headings(source_dir: &Path) -> Result<Vec<Heading>> {
     Ok(headings)
 }
 
-/// List all .md files in the source directory
-fn list_md_files(source_dir: &Path) -> Result<Vec<PathBuf>> {
+/// List all .md files in the source directory, honoring `.skillcignore`
+pub(crate) fn list_md_files(source_dir: &Path) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
+    let ignore = SkillIgnore::load(source_dir);
 
     for entry in WalkDir::new(source_dir)
         .into_iter()
+        .filter_entry(|e| ignore.allows(e))
         .filter_map(|e| e.ok())
         .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
     {
@@ -176,42 +893,78 @@ fn list_md_files(source_dir: &Path) -> Result<Vec<PathBuf>> {
 }
 
 /// Compute SHA-256 hash of source files per [[RFC-0001:C-MANIFEST]]
-fn compute_source_hash(source_dir: &Path) -> Result<String> {
-    let mut file_hashes: Vec<(String, String)> = Vec::new();
+pub fn compute_source_hash(source_dir: &Path) -> Result<String> {
+    Ok(combine_file_hashes(&compute_file_hashes(source_dir)?))
+}
 
-    for entry in WalkDir::new(source_dir)
+/// Recompute the source hash of a built skill, re-reading the include
+/// dependencies recorded in its manifest (a missing one changes the hash).
+pub fn current_source_hash(source_dir: &Path, includes: &[FileHash]) -> Result<String> {
+    let mut hashed = compute_file_hashes(source_dir)?;
+    for dependency in includes {
+        hashed.push(FileHash {
+            path: dependency.path.clone(),
+            sha256: fs::read(source_dir.join(&dependency.path))
+                .map(|content| sha256_hex(&content))
+                .unwrap_or_default(),
+        });
+    }
+    Ok(combine_file_hashes(&hashed))
+}
+
+/// Hash every file under `dir`, returning `(relative_path, sha256)` pairs sorted by path.
+///
+/// Hidden directories (VCS, IDE settings, caches, `.skillc-meta`) and paths
+/// matched by `.skillcignore` are skipped.
+pub fn compute_file_hashes(dir: &Path) -> Result<Vec<FileHash>> {
+    let mut file_hashes: Vec<FileHash> = Vec::new();
+    let ignore = SkillIgnore::load(dir);
+
+    for entry in WalkDir::new(dir)
         .into_iter()
         .filter_entry(|e| {
             // Exclude hidden directories (VCS, IDE settings, caches, etc.)
-            !e.file_type().is_dir() || !e.file_name().to_string_lossy().starts_with('.')
+            (!e.file_type().is_dir() || !e.file_name().to_string_lossy().starts_with('.'))
+                && ignore.allows(e)
         })
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
     {
         let relative_path = entry
             .path()
-            .strip_prefix(source_dir)
+            .strip_prefix(dir)
             .map_err(|_| SkillcError::Internal("path does not start with source_dir".into()))?
             .to_string_lossy()
             .to_string();
         let content = fs::read(entry.path())?;
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        let file_hash = format!("{:x}", hasher.finalize());
-        file_hashes.push((relative_path, file_hash));
+        file_hashes.push(FileHash {
+            path: relative_path,
+            sha256: sha256_hex(&content),
+        });
     }
 
     // Sort by path for deterministic hash
-    file_hashes.sort_by(|a, b| a.0.cmp(&b.0));
+    file_hashes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(file_hashes)
+}
+
+/// Hex-encoded SHA-256 of a byte slice.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
 
-    // Hash the combined (path, hash) pairs
+/// Combine sorted per-file hashes into the aggregate source hash.
+pub(crate) fn combine_file_hashes(file_hashes: &[FileHash]) -> String {
     let mut hasher = Sha256::new();
-    for (path, hash) in &file_hashes {
-        hasher.update(path.as_bytes());
-        hasher.update(hash.as_bytes());
+    for file in file_hashes {
+        hasher.update(file.path.as_bytes());
+        hasher.update(file.sha256.as_bytes());
     }
 
-    Ok(format!("{:x}", hasher.finalize()))
+    format!("{:x}", hasher.finalize())
 }
 
 /// Check for symlinks that escape the skill root per [[RFC-0001:C-CONSTRAINTS]].
@@ -260,10 +1013,14 @@ fn check_symlink_safety(source_dir: &Path) -> Result<()> {
 }
 
 /// Maximum stub size per [[RFC-0001:C-CONSTRAINTS]]
-/// Currently unused but retained for future enforcement.
-#[allow(dead_code)]
 const MAX_STUB_LINES: usize = 100;
 
+/// Skill-level stub template, relative to the skill source root
+const SKILL_STUB_TEMPLATE: &str = ".skillc-stub.tmpl";
+
+/// Store-level stub template, inside a `.skillc/` directory
+const STORE_STUB_TEMPLATE: &str = "stub.tmpl";
+
 /// Maximum entries for SKILL.md sections per [[RFC-0001:C-SECTIONS]]
 const MAX_SKILL_SECTION_ENTRIES: usize = 15;
 
@@ -286,7 +1043,7 @@ struct SectionEntry {
 ///
 /// Returns a map of relative file paths to their descriptions.
 fn extract_reference_descriptions(
-    source_dir: &Path,
+    view: &SourceView,
     files: &[PathBuf],
 ) -> HashMap<PathBuf, String> {
     let mut descriptions = HashMap::new();
@@ -297,8 +1054,7 @@ fn extract_reference_descriptions(
             continue;
         }
 
-        let full_path = source_dir.join(file);
-        if let Ok(content) = fs::read_to_string(&full_path)
+        if let Ok(content) = view.read(file)
             && let Some(desc) = extract_description_from_frontmatter(&content)
         {
             descriptions.insert(file.clone(), desc);
@@ -355,6 +1111,19 @@ fn truncate_description(desc: &str, max_len: usize) -> String {
     }
 }
 
+/// Section entries for the stub, split by origin and already truncated.
+#[derive(Debug)]
+struct StubSections {
+    /// SKILL.md headings (H1 at indent 0, H2 at indent 1)
+    skill: Vec<SectionEntry>,
+    /// One entry per reference file (indent 1)
+    references: Vec<SectionEntry>,
+    /// SKILL.md entries dropped by the limit
+    skill_omitted: usize,
+    /// Reference entries dropped by the limit
+    references_omitted: usize,
+}
+
 /// Build section entries per [[RFC-0001:C-SECTIONS]].
 ///
 /// - SKILL.md: H1 at indent 0, H2 at indent 1, skip H3+ (max 15 entries)
@@ -365,10 +1134,60 @@ fn truncate_description(desc: &str, max_len: usize) -> String {
 fn build_section_entries(
     headings: &[Heading],
     descriptions: &HashMap<PathBuf, String>,
+    usage: Option<&AccessCounts>,
 ) -> Vec<SectionEntry> {
+    let StubSections {
+        skill: skill_entries,
+        references: reference_entries,
+        skill_omitted,
+        references_omitted: refs_omitted,
+    } = collect_stub_sections(headings, descriptions, usage);
+
+    // Build final entries
+    let mut entries = skill_entries;
+
+    // Add ellipsis with count for truncated SKILL.md sections
+    if skill_omitted > 0 {
+        entries.push(SectionEntry {
+            text: format!("... ({} more)", skill_omitted),
+            indent: 1,
+        });
+    }
+
+    // Add "References" section only if there are reference entries
+    // Include hint per [[RFC-0001:C-SECTIONS]] to clarify query format
+    if !reference_entries.is_empty() {
+        entries.push(SectionEntry {
+            text: "References (query by title only)".to_string(),
+            indent: 0,
+        });
+        entries.extend(reference_entries);
+
+        // Add ellipsis with count for truncated references
+        if refs_omitted > 0 {
+            entries.push(SectionEntry {
+                text: format!("... ({} more)", refs_omitted),
+                indent: 1,
+            });
+        }
+    }
+
+    entries
+}
+
+/// Collect and truncate SKILL.md and reference entries per [[RFC-0001:C-SECTIONS]].
+///
+/// With `usage`, truncation keeps the most-accessed entries instead of the
+/// first ones; kept entries stay in document order.
+fn collect_stub_sections(
+    headings: &[Heading],
+    descriptions: &HashMap<PathBuf, String>,
+    usage: Option<&AccessCounts>,
+) -> StubSections {
     let mut skill_entries = Vec::new();
     let mut seen_files: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
     let mut reference_entries = Vec::new();
+    let mut reference_files = Vec::new();
 
     // Process SKILL.md headings
     for heading in headings {
@@ -376,15 +1195,22 @@ fn build_section_entries(
 
         if is_skill_md {
             // SKILL.md: H1 at indent 0, H2 at indent 1, skip H3+
+            let score = usage.map_or(0, |u| u.section_hits(&heading.file, &heading.text));
             match heading.level {
-                1 => skill_entries.push(SectionEntry {
-                    text: heading.text.clone(),
-                    indent: 0,
-                }),
-                2 => skill_entries.push(SectionEntry {
-                    text: heading.text.clone(),
-                    indent: 1,
-                }),
+                1 => skill_entries.push((
+                    SectionEntry {
+                        text: heading.text.clone(),
+                        indent: 0,
+                    },
+                    score,
+                )),
+                2 => skill_entries.push((
+                    SectionEntry {
+                        text: heading.text.clone(),
+                        indent: 1,
+                    },
+                    score,
+                )),
                 _ => {} // Skip H3+
             }
         }
@@ -412,6 +1238,7 @@ fn build_section_entries(
                 };
 
                 reference_entries.push(SectionEntry { text, indent: 1 });
+                reference_files.push(heading.file.clone());
             }
         }
     }
@@ -433,61 +1260,116 @@ fn build_section_entries(
                 file.display().to_string()
             };
             reference_entries.push(SectionEntry { text, indent: 1 });
+            reference_files.push(file.clone());
         }
     }
 
-    // Calculate omitted counts before truncating
-    let skill_omitted = skill_entries
-        .len()
-        .saturating_sub(MAX_SKILL_SECTION_ENTRIES);
-    let refs_omitted = reference_entries
-        .len()
-        .saturating_sub(MAX_REFERENCE_ENTRIES);
-
-    // Apply limits
-    skill_entries.truncate(MAX_SKILL_SECTION_ENTRIES);
-    reference_entries.truncate(MAX_REFERENCE_ENTRIES);
+    // A reference scores its file's accesses plus those of its headings
+    let reference_entries: Vec<(SectionEntry, i64)> = reference_entries
+        .into_iter()
+        .zip(&reference_files)
+        .map(|(entry, file)| {
+            let score = usage.map_or(0, |u| {
+                u.file_hits(file)
+                    + headings
+                        .iter()
+                        .filter(|h| &h.file == file)
+                        .map(|h| u.section_hits(file, &h.text))
+                        .sum::<i64>()
+            });
+            (entry, score)
+        })
+        .collect();
 
-    // Build final entries
-    let mut entries = skill_entries;
+    // Apply limits, counting what was left out
+    let skill_total = skill_entries.len();
+    let references_total = reference_entries.len();
+    let skill = select_entries(skill_entries, MAX_SKILL_SECTION_ENTRIES);
+    let references = select_entries(reference_entries, MAX_REFERENCE_ENTRIES);
+    StubSections {
+        skill_omitted: skill_total - skill.len(),
+        references_omitted: references_total - references.len(),
+        skill,
+        references,
+    }
+}
 
-    // Add ellipsis with count for truncated SKILL.md sections
-    if skill_omitted > 0 {
-        entries.push(SectionEntry {
-            text: format!("... ({} more)", skill_omitted),
-            indent: 1,
-        });
+/// Keep up to `limit` of the highest-scoring entries, in their original order.
+///
+/// Ties go to the earlier entry, so uniform scores keep the first `limit`.
+/// An entry is only kept together with its ancestors (the nearest preceding
+/// entry at each lower indent), so a subsection never appears without its
+/// parent heading; an entry whose ancestors no longer fit is skipped.
+fn select_entries(entries: Vec<(SectionEntry, i64)>, limit: usize) -> Vec<SectionEntry> {
+    if entries.len() <= limit {
+        return entries.into_iter().map(|(entry, _)| entry).collect();
     }
 
-    // Add "References" section only if there are reference entries
-    // Include hint per [[RFC-0001:C-SECTIONS]] to clarify query format
-    if !reference_entries.is_empty() {
-        entries.push(SectionEntry {
-            text: "References (query by title only)".to_string(),
-            indent: 0,
-        });
-        entries.extend(reference_entries);
+    let mut ranked: Vec<usize> = (0..entries.len()).collect();
+    ranked.sort_by(|&a, &b| entries[b].1.cmp(&entries[a].1).then(a.cmp(&b)));
 
-        // Add ellipsis with count for truncated references
-        if refs_omitted > 0 {
-            entries.push(SectionEntry {
-                text: format!("... ({} more)", refs_omitted),
-                indent: 1,
-            });
+    let mut kept = std::collections::HashSet::new();
+    for i in ranked {
+        if kept.len() == limit {
+            break;
+        }
+        let mut group = vec![i];
+        let mut indent = entries[i].0.indent;
+        for j in (0..i).rev() {
+            if indent == 0 {
+                break;
+            }
+            if entries[j].0.indent < indent {
+                indent = entries[j].0.indent;
+                group.push(j);
+            }
+        }
+        let missing: Vec<usize> = group.into_iter().filter(|j| !kept.contains(j)).collect();
+        if kept.len() + missing.len() <= limit {
+            kept.extend(missing);
         }
     }
 
     entries
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| kept.contains(i))
+        .map(|(_, (entry, _))| entry)
+        .collect()
+}
+
+/// Load access counts when stub entries are ranked by usage.
+///
+/// An unreadable log ranks nothing rather than failing the build.
+fn load_usage(runtime_dir: &Path) -> Option<AccessCounts> {
+    if config::get_section_order() != SectionOrder::Usage {
+        return None;
+    }
+    match AccessCounts::load(runtime_dir) {
+        Ok(counts) => {
+            verbose!("build: ranking stub entries by usage");
+            Some(counts)
+        }
+        Err(e) => {
+            verbose!("build: could not read access log: {}", e);
+            None
+        }
+    }
 }
 
 /// Generate the compiled stub per [[RFC-0001:C-STUB]]
 ///
-/// Enforces the 100-line limit per [[RFC-0001:C-CONSTRAINTS]].
+/// When `template` is given, the body after the frontmatter is rendered from
+/// it (see [`render_stub_template`]). Enforces the 100-line limit per
+/// [[RFC-0001:C-CONSTRAINTS]] on the final result.
 fn generate_stub(
     frontmatter: &Frontmatter,
+    gateway_name: &str,
     headings: &[Heading],
     descriptions: &HashMap<PathBuf, String>,
-) -> String {
+    template: Option<&str>,
+    usage: Option<&AccessCounts>,
+) -> Result<String> {
     let mut stub = String::new();
 
     // Frontmatter
@@ -496,6 +1378,48 @@ fn generate_stub(
     stub.push_str(&format!("description: \"{}\"\n", frontmatter.description));
     stub.push_str("---\n\n");
 
+    match template {
+        Some(template) => {
+            let body = render_stub_template(
+                template,
+                frontmatter,
+                gateway_name,
+                headings,
+                descriptions,
+                usage,
+            )?;
+            stub.push_str(&body);
+            if !stub.ends_with('\n') {
+                stub.push('\n');
+            }
+        }
+        None => push_default_body(
+            &mut stub,
+            frontmatter,
+            gateway_name,
+            headings,
+            descriptions,
+            usage,
+        ),
+    }
+
+    let lines = stub.lines().count();
+    if lines > MAX_STUB_LINES {
+        return Err(SkillcError::StubTooLong(lines));
+    }
+
+    Ok(stub)
+}
+
+/// Append the built-in stub body per [[RFC-0001:C-STUB]].
+fn push_default_body(
+    stub: &mut String,
+    frontmatter: &Frontmatter,
+    gateway_name: &str,
+    headings: &[Heading],
+    descriptions: &HashMap<PathBuf, String>,
+    usage: Option<&AccessCounts>,
+) {
     // Title
     stub.push_str(&format!("# {} (compiled)\n\n", frontmatter.name));
 
@@ -507,39 +1431,128 @@ fn generate_stub(
     stub.push_str("## Usage\n\n");
     stub.push_str("**Prefer MCP if available:** Use skillc MCP tools (`skc_outline`, `skc_show`, `skc_search`, etc.) for better performance and structured output.\n\n");
     stub.push_str("**CLI fallback:**\n");
-    stub.push_str(&format!(
-        "- `skc outline {}` — list sections\n",
-        frontmatter.name
-    ));
-    stub.push_str(&format!(
+    stub.push_str(&cli_fallback_lines(gateway_name));
+    stub.push('\n');
+
+    // Build section entries per [[RFC-0001:C-SECTIONS]]
+    let entries = build_section_entries(headings, descriptions, usage);
+
+    // Top sections
+    stub.push_str("## Top Sections\n\n");
+    stub.push_str(&render_entries(&entries, 0));
+}
+
+/// Gateway command examples used as the CLI fallback list.
+fn cli_fallback_lines(name: &str) -> String {
+    let mut lines = String::new();
+    lines.push_str(&format!("- `skc outline {}` — list sections\n", name));
+    lines.push_str(&format!(
         "- `skc show {} --section \"<Heading>\"` — view section content\n",
-        frontmatter.name
+        name
     ));
-    stub.push_str(&format!(
+    lines.push_str(&format!(
         "- `skc open {} <relative-path>` — open file\n",
-        frontmatter.name
+        name
     ));
-    stub.push_str(&format!(
-        "- `skc sources {}` — list source files\n",
-        frontmatter.name
-    ));
-    stub.push_str(&format!(
-        "- `skc search {} <query>` — search content\n\n",
-        frontmatter.name
+    lines.push_str(&format!("- `skc sources {}` — list source files\n", name));
+    lines.push_str(&format!(
+        "- `skc search {} <query>` — search content\n",
+        name
     ));
+    lines
+}
 
-    // Build section entries per [[RFC-0001:C-SECTIONS]]
-    let entries = build_section_entries(headings, descriptions);
+/// Render entries as a markdown list, shifting indentation left by `dedent` levels.
+fn render_entries(entries: &[SectionEntry], dedent: usize) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        let indent = "  ".repeat(entry.indent.saturating_sub(dedent));
+        out.push_str(&format!("{}- {}\n", indent, entry.text));
+    }
+    out
+}
 
-    // Top sections
-    stub.push_str("## Top Sections\n\n");
+/// Find the stub template for a skill, if any.
+///
+/// Lookup order:
+/// 1. `<skill>/.skillc-stub.tmpl` (skill-level)
+/// 2. `stub.tmpl` in the nearest enclosing `.skillc/` directory, i.e. the
+///    project (`<project>/.skillc/`) or global (`~/.skillc/`) store
+fn load_stub_template(source_dir: &Path) -> Result<Option<String>> {
+    let skill_template = source_dir.join(SKILL_STUB_TEMPLATE);
+    if skill_template.is_file() {
+        verbose!("build: stub template={}", skill_template.display());
+        return Ok(Some(fs::read_to_string(&skill_template)?));
+    }
+
+    for ancestor in source_dir.ancestors().skip(1) {
+        if ancestor.file_name().is_some_and(|n| n == ".skillc") {
+            let store_template = ancestor.join(STORE_STUB_TEMPLATE);
+            if store_template.is_file() {
+                verbose!("build: stub template={}", store_template.display());
+                return Ok(Some(fs::read_to_string(&store_template)?));
+            }
+            break;
+        }
+    }
 
-    for entry in &entries {
-        let indent = "  ".repeat(entry.indent);
-        stub.push_str(&format!("{}- {}\n", indent, entry.text));
+    Ok(None)
+}
+
+/// Render a user stub template.
+///
+/// Placeholders use `{{name}}` syntax (whitespace inside the braces is ignored):
+/// - `name`, `description` — from SKILL.md frontmatter
+/// - `usage` — the CLI fallback command list
+/// - `sections` — SKILL.md headings as a list (truncated)
+/// - `references` — reference titles with descriptions as a list (truncated)
+/// - `sections_omitted`, `references_omitted` — counts dropped by truncation
+/// - `top_sections` — the built-in combined listing, including "... (N more)" lines
+///
+/// Unknown or unterminated placeholders are rejected with E014.
+fn render_stub_template(
+    template: &str,
+    frontmatter: &Frontmatter,
+    gateway_name: &str,
+    headings: &[Heading],
+    descriptions: &HashMap<PathBuf, String>,
+    usage: Option<&AccessCounts>,
+) -> Result<String> {
+    let sections = collect_stub_sections(headings, descriptions, usage);
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let close = after.find("}}").ok_or_else(|| {
+            SkillcError::InvalidStubTemplate("unterminated placeholder '{{'".to_string())
+        })?;
+        let key = after[..close].trim();
+        let value = match key {
+            "name" => frontmatter.name.clone(),
+            "description" => frontmatter.description.clone(),
+            "usage" => cli_fallback_lines(gateway_name),
+            "sections" => render_entries(&sections.skill, 0),
+            "references" => render_entries(&sections.references, 1),
+            "sections_omitted" => sections.skill_omitted.to_string(),
+            "references_omitted" => sections.references_omitted.to_string(),
+            "top_sections" => {
+                render_entries(&build_section_entries(headings, descriptions, usage), 0)
+            }
+            other => {
+                return Err(SkillcError::InvalidStubTemplate(format!(
+                    "unknown placeholder '{{{{{}}}}}'",
+                    other
+                )));
+            }
+        };
+        out.push_str(&value);
+        rest = &after[close + 2..];
     }
+    out.push_str(rest);
 
-    stub
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -583,7 +1596,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         assert_eq!(entries.len(), 3);
         assert_eq!(entries[0].text, "My Skill");
@@ -623,7 +1636,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // Only H1 and H2 should be included
         assert_eq!(entries.len(), 2);
@@ -654,7 +1667,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // My Skill + References header + 2 reference entries
         assert_eq!(entries.len(), 4);
@@ -677,7 +1690,7 @@ description: A test skill
             line_number: 1,
         }];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // No "References" section when there are no other files
         assert_eq!(entries.len(), 1);
@@ -713,7 +1726,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // Only first H1 from docs/multi.md should be included
         assert_eq!(entries.len(), 3);
@@ -744,7 +1757,7 @@ description: A test skill
             });
         }
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // Check for SKILL.md truncation indicator (20 - 15 = 5 more)
         let skill_ellipsis = entries
@@ -771,6 +1784,60 @@ description: A test skill
         );
     }
 
+    #[test]
+    fn test_build_section_entries_ranked_by_usage() {
+        let headings: Vec<Heading> = (0..20)
+            .map(|i| Heading {
+                level: if i == 0 { 1 } else { 2 },
+                text: format!("Section {}", i),
+                file: PathBuf::from("SKILL.md"),
+                line_number: i + 1,
+            })
+            .collect();
+
+        let mut usage = AccessCounts::default();
+        usage.record("show", r#"{"section":"Section 19","file":"SKILL.md"}"#);
+        usage.record("search", r#"{"query":"section 17"}"#);
+
+        let entries = build_section_entries(&headings, &HashMap::new(), Some(&usage));
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+
+        // The accessed sections displace the last unaccessed ones, in document order
+        assert_eq!(texts.len(), 16);
+        assert_eq!(&texts[..3], ["Section 0", "Section 1", "Section 2"]);
+        assert_eq!(
+            &texts[12..],
+            ["Section 12", "Section 17", "Section 19", "... (5 more)"]
+        );
+        assert!(!texts.contains(&"Section 13"));
+    }
+
+    #[test]
+    fn test_build_section_entries_keep_parent_of_ranked_child() {
+        // Section 16 is an unaccessed H1 whose H2 child is the only hot entry
+        let headings: Vec<Heading> = (0..20)
+            .map(|i| Heading {
+                level: if i == 0 || i == 16 { 1 } else { 2 },
+                text: format!("Section {}", i),
+                file: PathBuf::from("SKILL.md"),
+                line_number: i + 1,
+            })
+            .collect();
+
+        let mut usage = AccessCounts::default();
+        usage.record("show", r#"{"section":"Section 17","file":"SKILL.md"}"#);
+
+        let entries = build_section_entries(&headings, &HashMap::new(), Some(&usage));
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+
+        assert_eq!(texts.len(), 16);
+        assert_eq!(
+            &texts[12..],
+            ["Section 12", "Section 16", "Section 17", "... (5 more)"]
+        );
+        assert_eq!(entries[13].indent, 0);
+    }
+
     #[test]
     fn test_build_section_entries_multiple_h1_in_skill_md() {
         let headings = vec![
@@ -800,7 +1867,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // All H1s and H2s from SKILL.md should be included
         assert_eq!(entries.len(), 4);
@@ -832,7 +1899,7 @@ description: A test skill
             },
         ];
 
-        let entries = build_section_entries(&headings, &HashMap::new());
+        let entries = build_section_entries(&headings, &HashMap::new(), None);
 
         // Should fallback to filename for docs/no-h1.md
         assert_eq!(entries.len(), 3);
@@ -933,7 +2000,7 @@ description: Unquoted value
         );
         // refs/errors.md has no description
 
-        let entries = build_section_entries(&headings, &descriptions);
+        let entries = build_section_entries(&headings, &descriptions, None);
 
         assert_eq!(entries.len(), 4); // My Skill + References + 2 refs
         assert_eq!(entries[0].text, "My Skill");
@@ -941,4 +2008,291 @@ description: Unquoted value
         assert_eq!(entries[2].text, "Clap Patterns — Advanced argument parsing");
         assert_eq!(entries[3].text, "Error Handling"); // No description
     }
+
+    fn write_test_skill(dir: &Path) {
+        fs::create_dir_all(dir).expect("create skill dir");
+        fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n",
+        )
+        .expect("write SKILL.md");
+    }
+
+    #[test]
+    fn test_compile_skips_up_to_date_runtime() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        let runtime = temp.path().join("runtime");
+        write_test_skill(&source);
+
+        let status = compile(&source, &runtime).expect("first compile");
+        assert_eq!(status, CompileStatus::Built);
+        let manifest = read_manifest(&runtime).expect("manifest written");
+
+        let status = compile(&source, &runtime).expect("second compile");
+        assert_eq!(status, CompileStatus::UpToDate);
+        let unchanged = read_manifest(&runtime).expect("manifest still present");
+        assert_eq!(unchanged.built_at, manifest.built_at);
+        assert!(!needs_rebuild(&source, &runtime).expect("check"));
+    }
+
+    #[test]
+    fn test_compile_rebuilds_on_change_or_force() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        let runtime = temp.path().join("runtime");
+        write_test_skill(&source);
+        compile(&source, &runtime).expect("first compile");
+
+        // Forced rebuild ignores up-to-date state
+        let status = compile_with_options(
+            &source,
+            &runtime,
+            &CompileOptions {
+                force: true,
+                ..Default::default()
+            },
+        )
+        .expect("forced compile");
+        assert_eq!(status, CompileStatus::Built);
+
+        // A tampered stub needs a rebuild even if the source is unchanged
+        fs::write(runtime.join("SKILL.md"), "edited").expect("edit stub");
+        assert!(needs_rebuild(&source, &runtime).expect("check"));
+        assert_eq!(
+            compile(&source, &runtime).expect("compile"),
+            CompileStatus::Built
+        );
+
+        // A source change needs a rebuild
+        fs::write(source.join("notes.md"), "# Notes\n").expect("add file");
+        assert!(needs_rebuild(&source, &runtime).expect("check"));
+    }
+
+    fn test_frontmatter() -> Frontmatter {
+        frontmatter::parse("---\nname: my-skill\ndescription: Does things\n---\n")
+            .expect("parse frontmatter")
+    }
+
+    fn test_headings() -> Vec<Heading> {
+        vec![
+            Heading {
+                level: 1,
+                text: "My Skill".to_string(),
+                file: PathBuf::from("SKILL.md"),
+                line_number: 1,
+            },
+            Heading {
+                level: 2,
+                text: "Setup".to_string(),
+                file: PathBuf::from("SKILL.md"),
+                line_number: 3,
+            },
+            Heading {
+                level: 1,
+                text: "Clap Patterns".to_string(),
+                file: PathBuf::from("refs/clap.md"),
+                line_number: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_stub_template_placeholders() {
+        let mut descriptions = HashMap::new();
+        descriptions.insert(PathBuf::from("refs/clap.md"), "Arg parsing".to_string());
+
+        let template = "# {{ name }}\n{{description}}\n\n{{sections}}\nRefs:\n{{references}}omitted: {{sections_omitted}}/{{references_omitted}}\n";
+        let rendered = render_stub_template(
+            template,
+            &test_frontmatter(),
+            "my-skill",
+            &test_headings(),
+            &descriptions,
+            None,
+        )
+        .expect("render template");
+
+        assert_eq!(
+            rendered,
+            "# my-skill\nDoes things\n\n- My Skill\n  - Setup\n\nRefs:\n- Clap Patterns — Arg parsing\nomitted: 0/0\n"
+        );
+    }
+
+    #[test]
+    fn test_render_stub_template_rejects_bad_placeholders() {
+        let descriptions = HashMap::new();
+
+        let err = render_stub_template(
+            "{{bogus}}",
+            &test_frontmatter(),
+            "my-skill",
+            &[],
+            &descriptions,
+            None,
+        )
+        .expect_err("unknown placeholder");
+        assert!(err.to_string().contains("unknown placeholder '{{bogus}}'"));
+
+        let err = render_stub_template(
+            "{{name",
+            &test_frontmatter(),
+            "my-skill",
+            &[],
+            &descriptions,
+            None,
+        )
+        .expect_err("unterminated placeholder");
+        assert!(err.to_string().contains("error[E014]"));
+    }
+
+    #[test]
+    fn test_generate_stub_enforces_line_limit() {
+        let descriptions = HashMap::new();
+        let template = "line\n".repeat(MAX_STUB_LINES);
+
+        let err = generate_stub(
+            &test_frontmatter(),
+            "my-skill",
+            &[],
+            &descriptions,
+            Some(&template),
+            None,
+        )
+        .expect_err("stub over limit");
+        assert!(err.to_string().contains("error[E015]"));
+
+        // Default layout stays within the limit
+        let stub = generate_stub(
+            &test_frontmatter(),
+            "my-skill",
+            &test_headings(),
+            &descriptions,
+            None,
+            None,
+        )
+        .expect("default stub");
+        assert!(stub.contains("## Top Sections"));
+    }
+
+    #[test]
+    fn test_load_stub_template_lookup_order() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let store = temp.path().join("project").join(".skillc");
+        let skill = store.join("skills").join("my-skill");
+        fs::create_dir_all(&skill).expect("create skill dir");
+
+        assert!(load_stub_template(&skill).expect("load").is_none());
+
+        fs::write(store.join(STORE_STUB_TEMPLATE), "store").expect("write template");
+        assert_eq!(
+            load_stub_template(&skill).expect("load").as_deref(),
+            Some("store")
+        );
+
+        fs::write(skill.join(SKILL_STUB_TEMPLATE), "skill").expect("write template");
+        assert_eq!(
+            load_stub_template(&skill).expect("load").as_deref(),
+            Some("skill")
+        );
+    }
+
+    #[test]
+    fn test_check_stub_budget() {
+        assert!(check_stub_budget(500, None).is_ok());
+        assert!(check_stub_budget(500, Some((800, BudgetMode::Error))).is_ok());
+        // Warn mode never fails
+        assert!(check_stub_budget(900, Some((800, BudgetMode::Warn))).is_ok());
+
+        let err = check_stub_budget(900, Some((800, BudgetMode::Error))).expect_err("over budget");
+        assert!(err.to_string().contains("error[E016]"));
+    }
+
+    #[test]
+    fn test_compile_records_token_stats() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        let runtime = temp.path().join("runtime");
+        write_test_skill(&source);
+        fs::write(source.join("guide.md"), "# Guide\n\nSome guidance text.\n").expect("write");
+
+        compile(&source, &runtime).expect("compile");
+        let manifest = read_manifest(&runtime).expect("manifest written");
+
+        assert_eq!(manifest.version, MANIFEST_VERSION);
+        let stub = fs::read_to_string(runtime.join("SKILL.md")).expect("read stub");
+        assert_eq!(manifest.tokens.stub, tokens::estimate_tokens(&stub));
+        assert_eq!(
+            manifest.tokens.files.keys().collect::<Vec<_>>(),
+            vec!["SKILL.md", "guide.md"]
+        );
+        assert!(
+            manifest
+                .tokens
+                .sections
+                .iter()
+                .any(|s| s.file == "guide.md" && s.heading == "Guide" && s.tokens > 0)
+        );
+    }
+
+    #[test]
+    fn test_compile_records_file_inventory() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        let runtime = temp.path().join("runtime");
+        write_test_skill(&source);
+        fs::write(source.join("guide.md"), "# Guide\n").expect("write");
+
+        compile(&source, &runtime).expect("compile");
+        let manifest = read_manifest(&runtime).expect("manifest written");
+
+        let paths: Vec<_> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "guide.md"]);
+        assert_eq!(manifest.files[1].sha256, sha256_hex(b"# Guide\n"));
+        assert_eq!(
+            manifest.source_hash,
+            compute_source_hash(&source).expect("hash")
+        );
+        let stub = fs::read(runtime.join("SKILL.md")).expect("read stub");
+        assert_eq!(manifest.stub_sha256, sha256_hex(&stub));
+    }
+
+    #[test]
+    fn test_build_timestamp_honors_source_date_epoch() {
+        temp_env::with_var("SOURCE_DATE_EPOCH", Some("1700000000"), || {
+            assert_eq!(
+                build_timestamp().expect("timestamp"),
+                "2023-11-14T22:13:20+00:00"
+            );
+        });
+        temp_env::with_var("SOURCE_DATE_EPOCH", Some("soon"), || {
+            let err = build_timestamp().expect_err("invalid epoch");
+            assert!(err.to_string().contains("error[E100]"));
+        });
+    }
+
+    #[test]
+    fn test_builds_are_reproducible() {
+        let temp = tempfile::TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        write_test_skill(&source);
+        fs::write(source.join("guide.md"), "# Guide\n\n## Usage\n").expect("write");
+        fs::write(source.join("notes.md"), "# Notes\n").expect("write");
+
+        let options = CompileOptions {
+            force: true,
+            built_at: Some("2024-01-01T00:00:00+00:00".to_string()),
+            ..Default::default()
+        };
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+        compile_with_options(&source, &first, &options).expect("first compile");
+        compile_with_options(&source, &second, &options).expect("second compile");
+
+        let first = runtime_inventory(&first).expect("inventory");
+        assert!(first.iter().any(|f| f.path.ends_with("manifest.json")));
+        assert!(first.iter().any(|f| f.path.ends_with(".db")));
+        assert_eq!(first, runtime_inventory(&second).expect("inventory"));
+        assert!(check_reproducible(&source).expect("check").is_empty());
+    }
 }
@@ -0,0 +1,218 @@
+//! Target-conditional blocks per [[RFC-0001:C-INPUT]].
+//!
+//! Lines between `<!-- skc:if target=claude,codex -->` and
+//! `<!-- skc:endif -->` are kept only in the runtime variant built for one of
+//! the listed targets, and dropped everywhere else. Blocks may nest; a line
+//! is kept when every enclosing condition holds. Directive lines themselves
+//! never reach the output, and directives inside fenced code blocks are
+//! left as-is.
+
+use crate::config::Target;
+use crate::markdown;
+use lazy_regex::{Lazy, Regex, lazy_regex};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// Regex for an opening conditional occupying a whole line.
+static IF_RE: Lazy<Regex> = lazy_regex!(r"^\s*<!--\s*skc:if\s+(.*?)\s*-->\s*$");
+
+/// Regex for a closing conditional occupying a whole line.
+static ENDIF_RE: Lazy<Regex> = lazy_regex!(r"^\s*<!--\s*skc:endif\s*-->\s*$");
+
+/// Regex for the condition of an opening directive.
+static CONDITION_RE: Lazy<Regex> = lazy_regex!(r"^target\s*=\s*(\S+)$");
+
+/// A malformed or unbalanced conditional directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectiveProblem {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+enum Directive {
+    If(Result<Vec<Target>, String>),
+    Endif,
+}
+
+/// Parse a line as a conditional directive.
+fn parse_directive(line: &str) -> Option<Directive> {
+    if ENDIF_RE.is_match(line) {
+        return Some(Directive::Endif);
+    }
+    let condition = IF_RE.captures(line)?.get(1)?.as_str();
+    let Some(caps) = CONDITION_RE.captures(condition) else {
+        return Some(Directive::If(Err(format!(
+            "unsupported condition '{}'; expected 'target=<name>[,<name>...]'",
+            condition
+        ))));
+    };
+    let targets = caps[1]
+        .split(',')
+        .map(|name| {
+            Target::from_str(name.trim()).map_err(|_| format!("unknown target '{}'", name.trim()))
+        })
+        .collect();
+    Some(Directive::If(targets))
+}
+
+/// Directives outside code fences, with 1-based line numbers.
+fn directives(content: &str) -> impl Iterator<Item = (usize, Directive)> + '_ {
+    let mut in_fence = false;
+    content.lines().enumerate().filter_map(move |(idx, line)| {
+        if markdown::is_code_fence(line) {
+            in_fence = !in_fence;
+            return None;
+        }
+        if in_fence {
+            return None;
+        }
+        parse_directive(line).map(|d| (idx + 1, d))
+    })
+}
+
+/// True if `content` has any conditional directive outside code fences.
+pub fn has_conditionals(content: &str) -> bool {
+    content.contains("skc:") && directives(content).next().is_some()
+}
+
+/// Validate conditional directives: known targets and balanced blocks.
+pub fn check(content: &str) -> Vec<DirectiveProblem> {
+    let mut problems = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+
+    for (line, directive) in directives(content) {
+        match directive {
+            Directive::If(targets) => {
+                if let Err(message) = targets {
+                    problems.push(DirectiveProblem { line, message });
+                }
+                open.push(line);
+            }
+            Directive::Endif => {
+                if open.pop().is_none() {
+                    problems.push(DirectiveProblem {
+                        line,
+                        message: "'skc:endif' without a matching 'skc:if'".to_string(),
+                    });
+                }
+            }
+        }
+    }
+    for line in open {
+        problems.push(DirectiveProblem {
+            line,
+            message: "'skc:if' is never closed".to_string(),
+        });
+    }
+
+    problems.sort_by_key(|p| p.line);
+    problems
+}
+
+/// Targets named by any conditional in `content`.
+pub fn referenced_targets(content: &str) -> BTreeSet<Target> {
+    directives(content)
+        .filter_map(|(_, directive)| match directive {
+            Directive::If(Ok(targets)) => Some(targets),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Resolve conditionals for `target`.
+///
+/// With `None`, every conditional block is dropped: that is the content seen
+/// by targets no condition names. Assumes `content` passed [`check`].
+pub fn filter(content: &str, target: Option<Target>) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let keep = stack.iter().all(|&k| k);
+        if markdown::is_code_fence(line) {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            match parse_directive(line) {
+                Some(Directive::If(targets)) => {
+                    let matches = match (targets, target) {
+                        (Ok(targets), Some(target)) => targets.contains(&target),
+                        _ => false,
+                    };
+                    stack.push(matches);
+                    continue;
+                }
+                Some(Directive::Endif) => {
+                    stack.pop();
+                    continue;
+                }
+                None => {}
+            }
+        }
+        if keep {
+            out.push(line);
+        }
+    }
+
+    let mut result = out.join("\n");
+    if content.ends_with('\n') && !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Skill\n\nShared.\n\n<!-- skc:if target=claude -->\nClaude only.\n<!-- skc:if target=claude,codex -->\nNested.\n<!-- skc:endif -->\n<!-- skc:endif -->\n\n<!-- skc:if target=codex -->\nCodex only.\n<!-- skc:endif -->\nEnd.\n";
+
+    #[test]
+    fn test_filter_per_target() {
+        assert_eq!(
+            filter(CONTENT, Some(Target::Claude)),
+            "# Skill\n\nShared.\n\nClaude only.\nNested.\n\nEnd.\n"
+        );
+        assert_eq!(
+            filter(CONTENT, Some(Target::Codex)),
+            "# Skill\n\nShared.\n\n\nCodex only.\nEnd.\n"
+        );
+        assert_eq!(filter(CONTENT, None), "# Skill\n\nShared.\n\n\nEnd.\n");
+    }
+
+    #[test]
+    fn test_referenced_targets() {
+        let targets: Vec<Target> = referenced_targets(CONTENT).into_iter().collect();
+        assert_eq!(targets, vec![Target::Claude, Target::Codex]);
+        assert!(has_conditionals(CONTENT));
+        assert!(!has_conditionals("# Plain\n"));
+    }
+
+    #[test]
+    fn test_check_reports_problems() {
+        assert!(check(CONTENT).is_empty());
+
+        let content = "<!-- skc:endif -->\n<!-- skc:if target=nope -->\n<!-- skc:if os=linux -->\n<!-- skc:endif -->\n";
+        let problems = check(content);
+        let summary: Vec<(usize, &str)> = problems
+            .iter()
+            .map(|p| (p.line, p.message.as_str()))
+            .collect();
+        assert_eq!(summary.len(), 4, "got: {:?}", summary);
+        assert_eq!(summary[0].0, 1);
+        assert!(summary[0].1.contains("without a matching"));
+        assert_eq!(summary[1], (2, "unknown target 'nope'"));
+        assert_eq!(summary[2], (2, "'skc:if' is never closed"));
+        assert!(summary[3].1.contains("unsupported condition 'os=linux'"));
+    }
+
+    #[test]
+    fn test_fenced_directives_are_ignored() {
+        let content = "```\n<!-- skc:if target=claude -->\n```\n";
+        assert!(check(content).is_empty());
+        assert!(!has_conditionals(content));
+        assert_eq!(filter(content, None), content);
+    }
+}
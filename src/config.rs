@@ -1,12 +1,15 @@
 //! Configuration and path helpers per [[RFC-0009]] and [[ADR-0001]]/[[ADR-0002]]
 
+use crate::emit::NativeFormat;
 use crate::error::{Result, SkillcError};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use strum::EnumProperty;
+use std::sync::OnceLock;
+use strum::{EnumProperty, IntoEnumIterator};
 
 /// Tokenizer preference for search indexing per [[RFC-0009:C-TOKENIZER]]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
@@ -50,6 +53,8 @@ impl Tokenizer {
     Copy,
     PartialEq,
     Eq,
+    PartialOrd,
+    Ord,
     Hash,
     clap::ValueEnum,
     strum::Display,
@@ -106,13 +111,53 @@ impl Target {
     }
 }
 
-/// Target specification for CLI: either a known target or a custom path.
+/// User-defined target from a `[targets.<name>]` config section per [[RFC-0009:C-FILES]].
 ///
-/// This allows `--target claude` (known) or `--target /custom/path` (custom).
+/// Resolves like a built-in target: `<home>/<dir>/<skills_subdir>/` globally,
+/// `<project>/<dir>/<skills_subdir>/` inside a project.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TargetConfig {
+    /// Agent directory under the home or project root (e.g. ".foo")
+    pub dir: String,
+
+    /// Skills directory inside `dir`
+    #[serde(default = "default_skills_subdir")]
+    pub skills_subdir: String,
+
+    /// Native instruction format rendered by `skc build --native`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<NativeFormat>,
+}
+
+fn default_skills_subdir() -> String {
+    "skills".to_string()
+}
+
+impl TargetConfig {
+    /// Get the global skills path for this target.
+    pub fn global_path(&self) -> Result<PathBuf> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            SkillcError::Internal("could not determine home directory".to_string())
+        })?;
+        Ok(home.join(&self.dir).join(&self.skills_subdir))
+    }
+
+    /// Get the project-local skills path for this target.
+    pub fn project_path(&self, project_root: &Path) -> PathBuf {
+        project_root.join(&self.dir).join(&self.skills_subdir)
+    }
+}
+
+/// Target specification for CLI: a known target, a configured target, or a custom path.
+///
+/// This allows `--target claude` (known), `--target foo` (defined under
+/// `[targets.foo]`), or `--target /custom/path` (custom).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TargetSpec {
     /// A known agent target
     Known(Target),
+    /// A target defined in config
+    Configured(String, TargetConfig),
     /// A custom path (for testing or advanced users)
     Custom(PathBuf),
 }
@@ -120,7 +165,7 @@ pub enum TargetSpec {
 impl TargetSpec {
     /// Get the skills directory path for this target.
     ///
-    /// For known targets, resolves to global or project-local path.
+    /// For known and configured targets, resolves to global or project-local path.
     /// For custom paths, returns the path directly.
     pub fn skills_path(&self, project_root: Option<&Path>) -> Result<PathBuf> {
         match self {
@@ -128,13 +173,26 @@ impl TargetSpec {
                 Some(root) => Ok(t.project_path(root)),
                 None => t.global_path(),
             },
+            TargetSpec::Configured(_, config) => match project_root {
+                Some(root) => Ok(config.project_path(root)),
+                None => config.global_path(),
+            },
             TargetSpec::Custom(p) => Ok(p.clone()),
         }
     }
 
-    /// Check if this is a known target (eligible for project-local deployment).
+    /// Check if this is a named target (eligible for project-local deployment).
     pub fn is_known(&self) -> bool {
-        matches!(self, TargetSpec::Known(_))
+        !matches!(self, TargetSpec::Custom(_))
+    }
+
+    /// Native instruction format rendered for this target by `skc build --native`.
+    pub fn native_format(&self) -> Option<NativeFormat> {
+        match self {
+            TargetSpec::Known(t) => NativeFormat::for_target(*t),
+            TargetSpec::Configured(_, config) => config.layout,
+            TargetSpec::Custom(_) => None,
+        }
     }
 }
 
@@ -142,23 +200,48 @@ impl std::fmt::Display for TargetSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TargetSpec::Known(t) => write!(f, "{}", t),
+            TargetSpec::Configured(name, _) => write!(f, "{}", name),
             TargetSpec::Custom(p) => write!(f, "{}", p.display()),
         }
     }
 }
 
+impl TargetSpec {
+    /// Resolve a target name against the given configured targets.
+    pub fn resolve(s: &str, configured: &BTreeMap<String, TargetConfig>) -> Self {
+        // Try known target first, then configured targets, fall back to custom path
+        if let Ok(t) = s.parse::<Target>() {
+            return TargetSpec::Known(t);
+        }
+        match configured.get(s) {
+            Some(config) => TargetSpec::Configured(s.to_string(), config.clone()),
+            None => TargetSpec::Custom(PathBuf::from(s)),
+        }
+    }
+}
+
 impl FromStr for TargetSpec {
     type Err = std::convert::Infallible;
 
+    /// Resolves against [`loaded_targets`], so the config is read only once
+    /// however many targets are parsed.
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        // Try known target first, fall back to custom path
-        match s.parse::<Target>() {
-            Ok(t) => Ok(TargetSpec::Known(t)),
-            Err(_) => Ok(TargetSpec::Custom(PathBuf::from(s))),
-        }
+        Ok(TargetSpec::resolve(s, loaded_targets()))
     }
 }
 
+/// Every named target: the built-in ones, then those defined in config.
+pub fn all_targets() -> Vec<TargetSpec> {
+    Target::iter()
+        .map(TargetSpec::Known)
+        .chain(
+            loaded_targets()
+                .iter()
+                .map(|(name, config)| TargetSpec::Configured(name.clone(), config.clone())),
+        )
+        .collect()
+}
+
 /// Search configuration section per [[RFC-0009:C-FILES]]
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct SearchConfig {
@@ -167,6 +250,60 @@ pub struct SearchConfig {
     pub tokenizer: Option<Tokenizer>,
 }
 
+/// What to do when the compiled stub exceeds its token budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetMode {
+    /// Emit a warning and continue (default)
+    #[default]
+    Warn,
+    /// Fail the build
+    Error,
+}
+
+/// How the stub picks entries when a listing exceeds its limit per [[RFC-0001:C-SECTIONS]]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionOrder {
+    /// Keep the first entries in document order (default)
+    #[default]
+    Document,
+    /// Keep the most-accessed entries according to the runtime's access log
+    Usage,
+}
+
+/// Build configuration section per [[RFC-0009:C-FILES]]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BuildConfig {
+    /// Maximum estimated tokens for the compiled stub
+    #[serde(default)]
+    pub stub_token_budget: Option<usize>,
+
+    /// Whether exceeding the budget warns or fails
+    #[serde(default)]
+    pub stub_budget_mode: Option<BudgetMode>,
+
+    /// Shell commands run before each build
+    #[serde(default)]
+    pub pre: Option<Vec<String>>,
+
+    /// Shell commands run after each build that writes the runtime
+    #[serde(default)]
+    pub post: Option<Vec<String>>,
+
+    /// Which entries the stub keeps when sections or references are truncated
+    #[serde(default)]
+    pub section_order: Option<SectionOrder>,
+
+    /// How many replaced runtime builds to retain for `skc rollback`
+    #[serde(default)]
+    pub keep_builds: Option<usize>,
+
+    /// Whether builds started over MCP run the hooks (global config only)
+    #[serde(default)]
+    pub mcp_hooks: Option<bool>,
+}
+
 /// Configuration file schema per [[RFC-0009:C-FILES]]
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct SkillcConfig {
@@ -177,49 +314,91 @@ pub struct SkillcConfig {
     /// Search settings
     #[serde(default)]
     pub search: SearchConfig,
+
+    /// Build settings
+    #[serde(default)]
+    pub build: BuildConfig,
+
+    /// User-defined deployment targets, keyed by name
+    #[serde(default)]
+    pub targets: BTreeMap<String, TargetConfig>,
 }
 
 /// Load and parse a config file, handling errors per [[RFC-0009:C-FILES]]
 fn load_config_file(path: &Path) -> Option<SkillcConfig> {
+    let (config, diagnostic) = parse_config_file(path);
+    if let Some(diagnostic) = diagnostic {
+        eprintln!("{}", diagnostic);
+    }
+    config
+}
+
+/// Parse a config file without printing anything.
+///
+/// Returns the usable config (if any) and the `warning: ...` or `error: ...`
+/// line to report for it per [[RFC-0009:C-FILES]].
+fn parse_config_file(path: &Path) -> (Option<SkillcConfig>, Option<String>) {
     let content = match fs::read_to_string(path) {
         Ok(c) => c,
-        Err(_) => return None,
+        Err(_) => return (None, None),
     };
 
     // Parse TOML
     let config: SkillcConfig = match toml::from_str(&content) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!(
-                "warning: Failed to parse config file {}: {}",
-                path.display(),
-                e
+            return (
+                None,
+                Some(format!(
+                    "warning: Failed to parse config file {}: {}",
+                    path.display(),
+                    e
+                )),
             );
-            return None;
         }
     };
 
     // Version validation per [[RFC-0009:C-FILES]]
     if let Some(version) = config.version {
         if version == 0 {
-            eprintln!(
-                "error: Invalid config version {} in {} (must be positive integer)",
-                version,
-                path.display()
+            // Ignore entire file
+            return (
+                None,
+                Some(format!(
+                    "error: Invalid config version {} in {} (must be positive integer)",
+                    version,
+                    path.display()
+                )),
             );
-            return None; // Ignore entire file
         }
         if version > 1 {
-            eprintln!(
-                "warning: Config version {} in {} is newer than supported (1), using recognized fields only",
-                version,
-                path.display()
-            );
             // Continue with recognized fields
+            return (
+                Some(config),
+                Some(format!(
+                    "warning: Config version {} in {} is newer than supported (1), using recognized fields only",
+                    version,
+                    path.display()
+                )),
+            );
         }
     }
 
-    Some(config)
+    (Some(config), None)
+}
+
+/// Problems with the project and global config files, as the lines that
+/// loading them would print.
+pub fn config_diagnostics() -> Vec<(PathBuf, String)> {
+    let global = global_skillc_dir().ok().map(|d| d.join("config.toml"));
+    find_project_config()
+        .into_iter()
+        .chain(global)
+        .filter_map(|path| {
+            let (_, diagnostic) = parse_config_file(&path);
+            diagnostic.map(|d| (path, d))
+        })
+        .collect()
 }
 
 /// Find project config by walking up from cwd per [[RFC-0009:C-RESOLUTION]]
@@ -280,6 +459,94 @@ pub fn get_tokenizer() -> Tokenizer {
     Tokenizer::default()
 }
 
+/// Resolve a config value from project config, then global config, per [[RFC-0009:C-RESOLUTION]].
+fn resolve_config_value<T>(get: impl Fn(&SkillcConfig) -> Option<T>) -> Option<T> {
+    if let Some(path) = find_project_config()
+        && let Some(config) = load_config_file(&path)
+        && let Some(value) = get(&config)
+    {
+        return Some(value);
+    }
+
+    let global_config_path = global_skillc_dir().ok()?.join("config.toml");
+    load_config_file(&global_config_path).and_then(|config| get(&config))
+}
+
+/// Get the targets defined under `[targets.<name>]` per [[RFC-0009:C-RESOLUTION]].
+///
+/// Global definitions come first; a project definition with the same name
+/// replaces the global one. Names of built-in targets are ignored.
+pub fn configured_targets() -> BTreeMap<String, TargetConfig> {
+    let global = global_skillc_dir().ok().map(|d| d.join("config.toml"));
+    let mut targets = BTreeMap::new();
+    for path in global.into_iter().chain(find_project_config()) {
+        if let Some(config) = load_config_file(&path) {
+            targets.extend(config.targets);
+        }
+    }
+    targets.retain(|name, _| name.parse::<Target>().is_err());
+    targets
+}
+
+/// The configured targets, loaded on first use and kept for the process.
+///
+/// Parsing every `--target` value or registry entry through
+/// [`configured_targets`] would re-read both config files, and repeat any
+/// warning about them, each time.
+fn loaded_targets() -> &'static BTreeMap<String, TargetConfig> {
+    static TARGETS: OnceLock<BTreeMap<String, TargetConfig>> = OnceLock::new();
+    TARGETS.get_or_init(configured_targets)
+}
+
+/// Get the stub token budget and its enforcement mode, if configured.
+///
+/// The first config (project, then global) that sets `stub_token_budget`
+/// wins; `stub_budget_mode` is read from that same file and defaults to warn.
+pub fn get_stub_budget() -> Option<(usize, BudgetMode)> {
+    resolve_config_value(|config| {
+        config
+            .build
+            .stub_token_budget
+            .map(|budget| (budget, config.build.stub_budget_mode.unwrap_or_default()))
+    })
+}
+
+/// Get the pre- and post-build hook commands.
+///
+/// Each list resolves independently: the first config (project, then
+/// global) that sets it wins. An explicit empty list disables the hooks.
+pub fn get_build_hooks() -> (Vec<String>, Vec<String>) {
+    (
+        resolve_config_value(|config| config.build.pre.clone()).unwrap_or_default(),
+        resolve_config_value(|config| config.build.post.clone()).unwrap_or_default(),
+    )
+}
+
+/// Whether builds started over MCP (`skc_build`) run the build hooks.
+///
+/// Read from the global config only, so a project cannot opt itself in to
+/// having an agent run its shell commands. Defaults to false.
+pub fn get_mcp_hooks() -> bool {
+    global_skillc_dir()
+        .ok()
+        .and_then(|dir| load_config_file(&dir.join("config.toml")))
+        .and_then(|config| config.build.mcp_hooks)
+        .unwrap_or(false)
+}
+
+/// Get how the stub selects entries when truncating.
+pub fn get_section_order() -> SectionOrder {
+    resolve_config_value(|config| config.build.section_order).unwrap_or_default()
+}
+
+/// Replaced runtime builds retained when `keep_builds` is not configured
+pub const DEFAULT_KEEP_BUILDS: usize = 3;
+
+/// Get how many replaced runtime builds to retain for `skc rollback`.
+pub fn get_keep_builds() -> usize {
+    resolve_config_value(|config| config.build.keep_builds).unwrap_or(DEFAULT_KEEP_BUILDS)
+}
+
 /// Get the global skillc directory.
 ///
 /// Per [[RFC-0009:C-ENV-OVERRIDE]], checks `SKILLC_HOME` first, then falls back to `~/.skillc/`.
@@ -358,12 +625,10 @@ pub fn resolve_source_store() -> Result<(PathBuf, bool)> {
 
 /// Get the target path from a string (for MCP/legacy compatibility).
 ///
-/// Parses to `Target` enum if known, otherwise treats as direct path.
+/// Parses to a known or configured target, otherwise treats as direct path.
 pub fn get_target_path(target: &str) -> Result<PathBuf> {
-    match target.parse::<Target>() {
-        Ok(t) => t.global_path(),
-        Err(_) => Ok(PathBuf::from(target)),
-    }
+    let Ok(spec) = target.parse::<TargetSpec>();
+    spec.skills_path(None)
 }
 
 /// Get the global registry path (~/.skillc/registry.json)
@@ -555,6 +820,64 @@ foo = "bar"
         assert_eq!(config.search.tokenizer, Some(Tokenizer::Ascii));
     }
 
+    #[test]
+    fn test_load_config_file_with_build_budget() {
+        let temp = TempDir::new().expect("create temp dir");
+        let config_path = temp.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"
+[build]
+stub_token_budget = 800
+stub_budget_mode = "error"
+"#,
+        )
+        .expect("test operation");
+
+        let config = load_config_file(&config_path).expect("expected result");
+        assert_eq!(config.build.stub_token_budget, Some(800));
+        assert_eq!(config.build.stub_budget_mode, Some(BudgetMode::Error));
+    }
+
+    #[test]
+    fn test_load_config_file_with_targets() {
+        let temp = TempDir::new().expect("create temp dir");
+        let config_path = temp.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"
+[targets.foo]
+dir = ".foo"
+
+[targets.bar]
+dir = ".bar"
+skills_subdir = "agents/skills"
+layout = "agents-md"
+"#,
+        )
+        .expect("test operation");
+
+        let config = load_config_file(&config_path).expect("expected result");
+        let foo = &config.targets["foo"];
+        assert_eq!(foo.skills_subdir, "skills");
+        assert_eq!(foo.layout, None);
+        let bar = &config.targets["bar"];
+        assert_eq!(
+            bar.project_path(Path::new("/work")),
+            PathBuf::from("/work/.bar/agents/skills")
+        );
+        assert_eq!(bar.layout, Some(NativeFormat::AgentsMd));
+
+        let spec = TargetSpec::Configured("bar".to_string(), bar.clone());
+        assert_eq!(spec.to_string(), "bar");
+        assert!(spec.is_known());
+        assert_eq!(
+            spec.skills_path(Some(Path::new("/work")))
+                .expect("skills path"),
+            PathBuf::from("/work/.bar/agents/skills")
+        );
+    }
+
     #[test]
     fn test_load_config_file_invalid_toml() {
         let temp = TempDir::new().expect("create temp dir");
@@ -0,0 +1,155 @@
+//! CSV parsing for row-level indexing per [[RFC-0004:C-FORMATS]].
+//!
+//! Handles RFC 4180 quoting: fields in double quotes may contain commas,
+//! line breaks, and doubled quotes (`""`). The first record is the header.
+
+/// One data row of a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRow {
+    /// 1-based row number, not counting the header
+    pub number: usize,
+    pub fields: Vec<String>,
+}
+
+impl CsvRow {
+    /// Section name for the row: its first column, or its row number when
+    /// the first column is empty.
+    pub fn section(&self) -> String {
+        match self.fields.first().map(|f| f.trim()) {
+            Some(first) if !first.is_empty() => first.to_string(),
+            _ => self.number.to_string(),
+        }
+    }
+
+    /// Render the row as `column: value` lines, skipping empty values.
+    ///
+    /// Columns without a header name are labeled by 1-based position.
+    pub fn render(&self, header: &[String]) -> String {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.trim().is_empty())
+            .map(|(i, value)| {
+                let column = header
+                    .get(i)
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .map_or_else(|| (i + 1).to_string(), str::to_string);
+                format!("{}: {}", column, value.trim())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A parsed CSV file: header plus data rows.
+#[derive(Debug, Default)]
+pub struct CsvTable {
+    pub header: Vec<String>,
+    pub rows: Vec<CsvRow>,
+}
+
+impl CsvTable {
+    /// Parse CSV content. Blank lines are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut records = parse_records(content).into_iter();
+        let header = records.next().unwrap_or_default();
+        let rows = records
+            .enumerate()
+            .map(|(i, fields)| CsvRow {
+                number: i + 1,
+                fields,
+            })
+            .collect();
+        Self { header, rows }
+    }
+
+    /// Find a row by locator: the first row whose section (first column)
+    /// matches case-insensitively, else the row with that 1-based number.
+    pub fn find(&self, locator: &str) -> Option<&CsvRow> {
+        let locator = locator.trim();
+        self.rows
+            .iter()
+            .find(|row| row.section().eq_ignore_ascii_case(locator))
+            .or_else(|| {
+                let number: usize = locator.parse().ok()?;
+                self.rows.iter().find(|row| row.number == number)
+            })
+    }
+}
+
+/// Split CSV content into records of fields.
+fn parse_records(content: &str) -> Vec<Vec<String>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record);
+    }
+
+    records
+}
+
+/// Keep a record unless it is a blank line.
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if !(record.len() == 1 && record[0].is_empty()) {
+        records.push(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let table = CsvTable::parse(
+            "code,message\r\nE001,\"not found, check \"\"name\"\"\"\n\nE002,\"line one\nline two\"\n",
+        );
+        assert_eq!(table.header, vec!["code", "message"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].fields[1], "not found, check \"name\"");
+        assert_eq!(table.rows[1].fields[1], "line one\nline two");
+        assert_eq!(table.rows[1].number, 2);
+    }
+
+    #[test]
+    fn test_render_and_find_rows() {
+        let table = CsvTable::parse("code,,note\nE001,x,\n,y,z\n");
+        assert_eq!(table.rows[0].render(&table.header), "code: E001\n2: x");
+        assert_eq!(table.rows[0].section(), "E001");
+        assert_eq!(table.rows[1].section(), "2");
+
+        assert_eq!(table.find("e001").map(|r| r.number), Some(1));
+        assert_eq!(table.find("2").map(|r| r.number), Some(2));
+        assert!(table.find("E999").is_none());
+    }
+}
@@ -4,12 +4,17 @@
 //! using symlinks (Unix), junctions (Windows), or copies (fallback).
 
 use crate::Result;
+use crate::compiler::read_manifest;
 use crate::config::{TargetSpec, ensure_dir};
 use crate::error::SkillcError;
+use crate::registry::{self, RegistryEntry};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Deployment method used to create agent directory entry
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DeployMethod {
     /// Unix symlink
     Symlink,
@@ -17,6 +22,8 @@ pub enum DeployMethod {
     Junction,
     /// Full directory copy (fallback or forced)
     Copy,
+    /// Native instruction file rendered by `build --native`
+    Native,
 }
 
 impl std::fmt::Display for DeployMethod {
@@ -25,6 +32,7 @@ impl std::fmt::Display for DeployMethod {
             DeployMethod::Symlink => write!(f, "symlink"),
             DeployMethod::Junction => write!(f, "junction"),
             DeployMethod::Copy => write!(f, "copy"),
+            DeployMethod::Native => write!(f, "native"),
         }
     }
 }
@@ -40,6 +48,10 @@ pub struct DeployResult {
 
 /// Deploy a compiled skill to an agent directory.
 ///
+/// Known targets with their own runtime variant (see
+/// [`crate::util::target_variant_dir`]) get that variant instead of the
+/// shared runtime.
+///
 /// # Arguments
 ///
 /// * `ssot_path` - Path to the compiled skill in SSOT location
@@ -73,57 +85,567 @@ pub fn deploy_to_agent(
         )));
     }
 
-    // Resolve agent directory
-    // Only known targets get project-local treatment; custom paths are used as-is
-    let agent_dir = if target.is_known() {
-        target.skills_path(project_root)?
-    } else {
-        target.skills_path(None)?
-    };
+    let ssot_path = &deploy_source(ssot_path, target);
+    let agent_dir = agent_skills_dir(target, project_root)?;
     let dest = agent_dir.join(skill_name);
 
     // Ensure parent directory exists
     ensure_dir(&agent_dir)?;
 
-    // Handle existing destination
-    if dest.exists() {
-        if is_link(&dest) {
-            // Always remove existing symlinks/junctions
-            remove_link(&dest)?;
-        } else if force_copy {
-            // With --copy, remove existing directory to replace it
-            std::fs::remove_dir_all(&dest).map_err(|e| {
-                SkillcError::Internal(format!(
-                    "Failed to remove existing directory {}: {}",
+    // Existing links are always replaced; directories only with --copy
+    if dest.exists() && !is_link(&dest) && !force_copy {
+        return Err(SkillcError::Internal(format!(
+            "Destination exists and is not a symlink: {}. Use --copy to overwrite.",
+            dest.display()
+        )));
+    }
+
+    let method = place_entry(ssot_path, &dest, force_copy)?;
+
+    Ok(DeployResult {
+        target: dest,
+        method,
+    })
+}
+
+/// One target's deployment, as reported by a build.
+#[derive(Debug, Clone, Serialize)]
+pub struct Deployment {
+    /// Target name or custom path
+    pub target: String,
+    /// Skill entry in the agent directory
+    pub path: PathBuf,
+    pub method: DeployMethod,
+    /// True if the existing entry was already current and left alone
+    pub unchanged: bool,
+    /// True if this is a stale copy outside the requested targets that was
+    /// refreshed from the new build
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub refreshed: bool,
+}
+
+/// Deploy the SSOT to each target, leaving current deployments untouched unless forced.
+///
+/// Copy deployments of the skill outside `targets` (see [`copy_deployments`])
+/// are refreshed when they no longer match the SSOT, so a rebuild never
+/// leaves an earlier snapshot behind. Every deployment is recorded in the
+/// global registry. A registry write or copy refresh failure is reported as
+/// a warning and does not fail the deploy.
+pub fn deploy_targets(
+    ssot_path: &Path,
+    targets: &[TargetSpec],
+    skill_name: &str,
+    force_copy: bool,
+    project_root: Option<&Path>,
+    force: bool,
+) -> Result<Vec<Deployment>> {
+    let mut deployments = Vec::new();
+    for target in targets {
+        let existing = if force {
+            None
+        } else {
+            current_deployment(ssot_path, target, skill_name, force_copy, project_root)?
+        };
+        let (result, unchanged) = match existing {
+            Some(result) => (result, true),
+            None => (
+                deploy_to_agent(ssot_path, target, skill_name, force_copy, project_root)?,
+                false,
+            ),
+        };
+        deployments.push(Deployment {
+            target: target.to_string(),
+            path: result.target,
+            method: result.method,
+            unchanged,
+            refreshed: false,
+        });
+    }
+
+    let mut targets = targets.to_vec();
+    let registry = registry::Registry::load().unwrap_or_default();
+    for (target, dest) in copy_deployments(ssot_path, skill_name, &registry) {
+        if deployments
+            .iter()
+            .any(|d| registry::same_path(&d.path, &dest))
+        {
+            continue;
+        }
+        let source = deploy_source(ssot_path, &target);
+        if copy_is_current(&source, &dest) {
+            continue;
+        }
+        if let Err(e) = refresh_copy(&source, &dest) {
+            eprintln!(
+                "warning: failed to refresh copy at {}: {}",
+                dest.display(),
+                e
+            );
+            continue;
+        }
+        deployments.push(Deployment {
+            target: target.to_string(),
+            path: dest,
+            method: DeployMethod::Copy,
+            unchanged: false,
+            refreshed: true,
+        });
+        targets.push(target);
+    }
+
+    record_deployments(ssot_path, &targets, skill_name, &deployments);
+    Ok(deployments)
+}
+
+/// Targets whose native files a build of the runtime at `ssot_path` renders and deploys.
+///
+/// With `native`, each of `targets` that has a native format. Targets the
+/// registry records a native deployment of this runtime for are always
+/// included, so a later build without `--native` keeps those files in step
+/// with the runtime, as it does copies.
+pub fn native_targets(ssot_path: &Path, targets: &[TargetSpec], native: bool) -> Vec<TargetSpec> {
+    let mut native_targets: Vec<TargetSpec> = if native {
+        targets
+            .iter()
+            .filter(|t| t.native_format().is_some())
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let registry = registry::Registry::load().unwrap_or_default();
+    for entry in registry.for_runtime(ssot_path) {
+        if entry.method != DeployMethod::Native {
+            continue;
+        }
+        let Ok(target) = entry.target.parse::<TargetSpec>();
+        if target.native_format().is_some() && !native_targets.contains(&target) {
+            native_targets.push(target);
+        }
+    }
+    native_targets
+}
+
+/// Deploy the runtime's native files to each target's native path.
+///
+/// `targets` come from [`native_targets`], and the build must already have
+/// rendered their files into the runtime (see [`crate::emit::emit_native`]).
+/// Registered native deployments of the runtime are refreshed too. A file is
+/// swapped in by rename and left alone when already identical. A path that
+/// skillc did not deploy from this runtime, such as a hand-written
+/// `AGENTS.md` or another skill's, is never overwritten: it is reported as a
+/// warning and skipped, as is a target with no native path in this scope.
+/// Every deployment is recorded in the global registry.
+pub fn deploy_native(
+    ssot_path: &Path,
+    targets: &[TargetSpec],
+    skill_name: &str,
+    project_root: Option<&Path>,
+) -> Result<Vec<Deployment>> {
+    let registry = registry::Registry::load().unwrap_or_default();
+    let mut planned: Vec<(TargetSpec, PathBuf, bool)> = Vec::new();
+    for target in targets {
+        match crate::emit::native_path(target, skill_name, project_root) {
+            Some(dest) => planned.push((target.clone(), dest, false)),
+            None => eprintln!(
+                "warning: {} has no {} native instruction file; skipping",
+                target,
+                if project_root.is_some() {
+                    "project"
+                } else {
+                    "global"
+                }
+            ),
+        }
+    }
+    for entry in registry.for_runtime(ssot_path) {
+        if entry.method == DeployMethod::Native {
+            let Ok(target) = entry.target.parse::<TargetSpec>();
+            planned.push((target, entry.path.clone(), true));
+        }
+    }
+
+    let mut deployments: Vec<Deployment> = Vec::new();
+    let mut deployed_targets = Vec::new();
+    for (target, dest, refreshed) in planned {
+        if deployments
+            .iter()
+            .any(|d| registry::same_path(&d.path, &dest))
+        {
+            continue;
+        }
+        let Some(source) = crate::emit::native_output(ssot_path, &target, skill_name)
+            .filter(|source| source.is_file())
+        else {
+            continue;
+        };
+
+        let unchanged = std::fs::read(&source).ok() == std::fs::read(&dest).ok();
+        if unchanged && refreshed {
+            continue;
+        }
+        if !unchanged {
+            if dest.symlink_metadata().is_ok()
+                && owned_deployment(&dest, ssot_path, skill_name, &registry)
+                    != Some(DeployMethod::Native)
+            {
+                eprintln!(
+                    "warning: {} was not deployed by skillc from {}; skipping",
                     dest.display(),
-                    e
-                ))
-            })?;
+                    skill_name
+                );
+                continue;
+            }
+            place_file(&source, &dest)?;
+        }
+        deployments.push(Deployment {
+            target: target.to_string(),
+            path: dest,
+            method: DeployMethod::Native,
+            unchanged,
+            refreshed,
+        });
+        deployed_targets.push(target);
+    }
+
+    record_deployments(ssot_path, &deployed_targets, skill_name, &deployments);
+    Ok(deployments)
+}
+
+/// Copy deployments of the runtime at `ssot_path`, with their targets.
+///
+/// Found through the registry and by scanning each target's skills directory
+/// (project-local for a project runtime, global otherwise) for a copy whose
+/// manifest names the skill. Scanned entries the registry attributes to a
+/// different runtime are skipped.
+pub fn copy_deployments(
+    ssot_path: &Path,
+    skill_name: &str,
+    registry: &registry::Registry,
+) -> Vec<(TargetSpec, PathBuf)> {
+    let project_root = crate::verify::project_root_of_runtime(ssot_path);
+    let mut candidates: Vec<(TargetSpec, PathBuf)> = crate::config::all_targets()
+        .into_iter()
+        .filter_map(|target| {
+            let dir = target.skills_path(project_root.as_deref()).ok()?;
+            Some((target, dir.join(skill_name)))
+        })
+        .filter(|(_, dest)| registry.entry_at(dest).is_none())
+        .collect();
+    for entry in registry.for_runtime(ssot_path) {
+        if entry.method == DeployMethod::Copy {
+            let Ok(target) = entry.target.parse::<TargetSpec>();
+            candidates.push((target, entry.path.clone()));
+        }
+    }
+
+    let mut seen: Vec<PathBuf> = Vec::new();
+    candidates.retain(|(_, dest)| {
+        if seen.iter().any(|p| registry::same_path(p, dest)) {
+            return false;
+        }
+        seen.push(dest.clone());
+        dest.is_dir()
+            && owned_deployment(dest, ssot_path, skill_name, registry) == Some(DeployMethod::Copy)
+    });
+    candidates
+}
+
+/// True if the copy at `dest` has the same stub and manifest as `source`.
+pub fn copy_is_current(source: &Path, dest: &Path) -> bool {
+    let same_file = |rel: &Path| match (
+        std::fs::read(source.join(rel)),
+        std::fs::read(dest.join(rel)),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    same_file(Path::new("SKILL.md")) && same_file(&Path::new(".skillc-meta").join("manifest.json"))
+}
+
+/// Record deployments of the SSOT to `targets` in the global registry.
+///
+/// A registry write failure is reported as a warning.
+pub(crate) fn record_deployments(
+    ssot_path: &Path,
+    targets: &[TargetSpec],
+    skill_name: &str,
+    deployments: &[Deployment],
+) {
+    if let Err(e) = registry::record(registry_entries(
+        ssot_path,
+        targets,
+        skill_name,
+        deployments,
+    )) {
+        eprintln!("warning: failed to update deployment registry: {}", e);
+    }
+}
+
+fn registry_entries(
+    ssot_path: &Path,
+    targets: &[TargetSpec],
+    skill_name: &str,
+    deployments: &[Deployment],
+) -> Vec<RegistryEntry> {
+    let ssot = ssot_path
+        .canonicalize()
+        .unwrap_or_else(|_| ssot_path.to_path_buf());
+    let deployed_at = Utc::now().to_rfc3339();
+    targets
+        .iter()
+        .zip(deployments)
+        .map(|(target, deployment)| RegistryEntry {
+            skill: skill_name.to_string(),
+            ssot: ssot.clone(),
+            target: deployment.target.clone(),
+            path: std::path::absolute(&deployment.path).unwrap_or_else(|_| deployment.path.clone()),
+            method: deployment.method,
+            deployed_at: deployed_at.clone(),
+            source_hash: read_manifest(&deploy_source(ssot_path, target))
+                .map(|m| m.source_hash)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Return the existing deployment if it already reflects the SSOT.
+///
+/// A link deployment is current when it resolves to `ssot_path`. A copy
+/// deployment is current when its stub and manifest match the SSOT's.
+/// Returns `None` if the deployment is missing, stale, or uses a different
+/// method than requested.
+pub fn current_deployment(
+    ssot_path: &Path,
+    target: &TargetSpec,
+    skill_name: &str,
+    force_copy: bool,
+    project_root: Option<&Path>,
+) -> Result<Option<DeployResult>> {
+    let ssot_path = &deploy_source(ssot_path, target);
+    let dest = agent_skills_dir(target, project_root)?.join(skill_name);
+    if !dest.exists() {
+        return Ok(None);
+    }
+
+    if is_link(&dest) {
+        if force_copy {
+            return Ok(None);
+        }
+        let resolved = dest.canonicalize().ok();
+        let expected = ssot_path.canonicalize().ok();
+        if resolved.is_some() && resolved == expected {
+            return Ok(Some(DeployResult {
+                target: dest,
+                method: DeployMethod::Symlink,
+            }));
+        }
+        return Ok(None);
+    }
+
+    if !force_copy {
+        return Ok(None);
+    }
+
+    if copy_is_current(ssot_path, &dest) {
+        return Ok(Some(DeployResult {
+            target: dest,
+            method: DeployMethod::Copy,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// How skillc created an agent directory entry for the runtime at `ssot_path`, if it did.
+///
+/// Links count when they point into the runtime (shared or a target variant),
+/// even if the runtime has since been deleted. A copy counts when `registry`
+/// records it as deployed from this runtime and its manifest still carries
+/// the recorded source hash, so a same-named copy from another runtime, or
+/// one replaced by hand, is not ours. Only when the registry has no entry for
+/// `dest` (copies deployed before the registry existed) does a copy count by
+/// name alone: its manifest names the same skill as the runtime's, or
+/// `skill_name` when the runtime is gone. A native instruction file counts
+/// only when `registry` records it as deployed from this runtime. Anything
+/// else was not created by skillc.
+pub fn owned_deployment(
+    dest: &Path,
+    ssot_path: &Path,
+    skill_name: &str,
+    registry: &registry::Registry,
+) -> Option<DeployMethod> {
+    if is_link(dest) {
+        let link = std::fs::read_link(dest).ok()?;
+        let points_into = |base: &Path| {
+            link.starts_with(base)
+                || dest
+                    .canonicalize()
+                    .ok()
+                    .is_some_and(|resolved| resolved.starts_with(base))
+        };
+        let ssot_canonical = ssot_path.canonicalize().ok();
+        let owned = points_into(ssot_path) || ssot_canonical.as_deref().is_some_and(points_into);
+        let method = if cfg!(windows) {
+            DeployMethod::Junction
         } else {
-            // Non-symlink directory without --copy: error
-            return Err(SkillcError::Internal(format!(
-                "Destination exists and is not a symlink: {}. Use --copy to overwrite.",
-                dest.display()
-            )));
+            DeployMethod::Symlink
+        };
+        return owned.then_some(method);
+    }
+
+    if let Some(entry) = registry.entry_at(dest)
+        && entry.method == DeployMethod::Native
+    {
+        let owned = dest.is_file() && registry::same_path(&entry.ssot, ssot_path);
+        return owned.then_some(DeployMethod::Native);
+    }
+
+    let deployed = crate::compiler::read_manifest(dest)?;
+    let owned = match registry.entry_at(dest) {
+        Some(entry) => {
+            registry::same_path(&entry.ssot, ssot_path) && deployed.source_hash == entry.source_hash
+        }
+        None => {
+            let expected = crate::compiler::read_manifest(ssot_path)
+                .map(|m| m.skill)
+                .unwrap_or_else(|| skill_name.to_string());
+            deployed.skill == expected
         }
+    };
+    owned.then_some(DeployMethod::Copy)
+}
+
+/// Remove an agent directory entry created with `method`.
+pub fn remove_deployment(dest: &Path, method: DeployMethod) -> Result<()> {
+    match method {
+        DeployMethod::Symlink | DeployMethod::Junction => remove_link(dest),
+        DeployMethod::Copy => Ok(std::fs::remove_dir_all(dest)?),
+        DeployMethod::Native => Ok(std::fs::remove_file(dest)?),
     }
+}
+
+/// Replace a copy deployment with a fresh copy of `source`.
+pub fn refresh_copy(source: &Path, dest: &Path) -> Result<()> {
+    place_entry(source, dest, true).map(|_| ())
+}
 
-    // Deploy using appropriate method
-    let method = if force_copy {
-        crate::util::copy_dir_recursive(ssot_path, &dest)?;
-        DeployMethod::Copy
+/// Create an agent directory entry for `source` beside `dest`, then swap it in.
+///
+/// The previous entry stays in place until the new one is complete, so a
+/// failed copy never leaves a half-written deployment behind.
+pub(crate) fn place_entry(source: &Path, dest: &Path, copy: bool) -> Result<DeployMethod> {
+    let staged = sibling_entry(dest, "new");
+    if staged.symlink_metadata().is_ok() {
+        remove_entry(&staged)?;
+    }
+
+    let created = if copy {
+        crate::util::copy_dir_recursive(source, &staged)
+            .map(|_| DeployMethod::Copy)
+            .map_err(SkillcError::from)
     } else {
-        create_link(ssot_path, &dest)?
+        create_link(source, &staged)
+    };
+    let method = match created {
+        Ok(method) => method,
+        Err(e) => {
+            let _ = remove_entry(&staged);
+            return Err(e);
+        }
     };
 
-    Ok(DeployResult {
-        target: dest,
-        method,
-    })
+    if let Err(e) = replace_entry(&staged, dest) {
+        let _ = remove_entry(&staged);
+        return Err(e);
+    }
+    Ok(method)
+}
+
+/// Copy a native file to `dest` through a temporary sibling, then swap it in.
+///
+/// Agents reading `dest` see the old file or the new one, never a partial write.
+pub(crate) fn place_file(source: &Path, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        ensure_dir(parent)?;
+    }
+    let staged = sibling_entry(dest, "new");
+    let placed = std::fs::copy(source, &staged).and_then(|_| std::fs::rename(&staged, dest));
+    if let Err(e) = placed {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Move `new` to `dest`, replacing whatever is there.
+///
+/// A link replaces a link in a single rename. Where the platform refuses to
+/// rename over the existing entry (directories, Windows junctions), the old
+/// entry is moved aside first and restored if the second rename fails.
+fn replace_entry(new: &Path, dest: &Path) -> Result<()> {
+    if std::fs::rename(new, dest).is_ok() {
+        return Ok(());
+    }
+    if dest.symlink_metadata().is_err() {
+        std::fs::rename(new, dest)?;
+        return Ok(());
+    }
+
+    let old = sibling_entry(dest, "old");
+    if old.symlink_metadata().is_ok() {
+        remove_entry(&old)?;
+    }
+    std::fs::rename(dest, &old)?;
+    if let Err(e) = std::fs::rename(new, dest) {
+        let _ = std::fs::rename(&old, dest);
+        return Err(e.into());
+    }
+    remove_entry(&old)
+}
+
+/// Temporary name next to an agent directory entry, e.g. `.my-skill.skillc-new`.
+fn sibling_entry(dest: &Path, suffix: &str) -> PathBuf {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    dest.with_file_name(format!(".{}.skillc-{}", name, suffix))
+}
+
+/// Remove a link, directory, or file.
+fn remove_entry(path: &Path) -> Result<()> {
+    if is_link(path) {
+        remove_link(path)
+    } else if path.is_dir() {
+        Ok(std::fs::remove_dir_all(path)?)
+    } else {
+        Ok(std::fs::remove_file(path)?)
+    }
+}
+
+/// Runtime to deploy to a target: its variant if the build produced one,
+/// else the shared runtime at `ssot_path`.
+pub fn deploy_source(ssot_path: &Path, target: &TargetSpec) -> PathBuf {
+    if let TargetSpec::Known(t) = target {
+        let variant = crate::util::target_variant_dir(ssot_path, *t);
+        if variant.is_dir() {
+            return variant;
+        }
+    }
+    ssot_path.to_path_buf()
+}
+
+/// Resolve the agent skills directory for a target.
+///
+/// Only known targets get project-local treatment; custom paths are used as-is.
+fn agent_skills_dir(target: &TargetSpec, project_root: Option<&Path>) -> Result<PathBuf> {
+    if target.is_known() {
+        target.skills_path(project_root)
+    } else {
+        target.skills_path(None)
+    }
 }
 
 /// Check if a path is a symlink or junction
-fn is_link(path: &Path) -> bool {
+pub(crate) fn is_link(path: &Path) -> bool {
     path.symlink_metadata()
         .map(|m| m.file_type().is_symlink())
         .unwrap_or(false)
@@ -307,6 +829,49 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not a symlink"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_place_entry_swaps_in_complete_entries() {
+        let temp = TempDir::new().expect("create temp dir");
+        let ssot = temp.path().join("ssot");
+        std::fs::create_dir_all(&ssot).expect("create ssot");
+        std::fs::write(ssot.join("SKILL.md"), "# New").expect("write stub");
+        let dest = temp.path().join("agent").join("test-skill");
+        std::fs::create_dir_all(&dest).expect("create deployment");
+        std::fs::write(dest.join("SKILL.md"), "# Old").expect("write stub");
+
+        // A failed copy leaves the existing deployment alone
+        let missing = temp.path().join("missing");
+        assert!(place_entry(&missing, &dest, true).is_err());
+        assert_eq!(
+            std::fs::read_to_string(dest.join("SKILL.md")).expect("read stub"),
+            "# Old"
+        );
+
+        // A link replaces the copy, and a copy replaces the link
+        assert_eq!(
+            place_entry(&ssot, &dest, false).expect("link"),
+            DeployMethod::Symlink
+        );
+        assert!(is_link(&dest));
+        assert_eq!(
+            place_entry(&ssot, &dest, true).expect("copy"),
+            DeployMethod::Copy
+        );
+        assert!(!is_link(&dest));
+        assert_eq!(
+            std::fs::read_to_string(dest.join("SKILL.md")).expect("read stub"),
+            "# New"
+        );
+
+        // No temporary entries are left behind
+        let entries: Vec<_> = std::fs::read_dir(temp.path().join("agent"))
+            .expect("read agent dir")
+            .map(|e| e.expect("entry").file_name())
+            .collect();
+        assert_eq!(entries, vec!["test-skill"]);
+    }
+
     /// Internal test helper that takes agent_dir directly instead of agent name
     fn deploy_to_agent_internal(
         ssot_path: &Path,
@@ -350,4 +915,102 @@ mod tests {
 
         Ok(DeployResult { target, method })
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_owned_deployment() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path().join("runtime").join("my-skill");
+        let meta = runtime.join(".skillc-meta");
+        std::fs::create_dir_all(&meta).expect("create runtime");
+        std::fs::write(
+            meta.join("manifest.json"),
+            r#"{"skill":"my-skill","version":5,"built_at":"","source_hash":""}"#,
+        )
+        .expect("write manifest");
+        let agent = temp.path().join("agent");
+        std::fs::create_dir_all(&agent).expect("create agent dir");
+        let none = registry::Registry::default();
+
+        // Link into the runtime
+        let link = agent.join("linked");
+        std::os::unix::fs::symlink(&runtime, &link).expect("create link");
+        assert_eq!(
+            owned_deployment(&link, &runtime, "my-skill", &none),
+            Some(DeployMethod::Symlink)
+        );
+
+        // Link elsewhere
+        let foreign = agent.join("foreign");
+        std::os::unix::fs::symlink(temp.path(), &foreign).expect("create link");
+        assert_eq!(
+            owned_deployment(&foreign, &runtime, "my-skill", &none),
+            None
+        );
+
+        // Copy with a matching manifest, and a hand-written skill
+        let copy = agent.join("copy");
+        crate::util::copy_dir_recursive(&runtime, &copy).expect("copy runtime");
+        assert_eq!(
+            owned_deployment(&copy, &runtime, "my-skill", &none),
+            Some(DeployMethod::Copy)
+        );
+        let manual = agent.join("manual");
+        std::fs::create_dir_all(&manual).expect("create manual skill");
+        std::fs::write(manual.join("SKILL.md"), "# Manual").expect("write skill");
+        assert_eq!(owned_deployment(&manual, &runtime, "my-skill", &none), None);
+
+        // With a registry entry, the copy must come from this runtime and
+        // still carry the recorded source hash
+        let entry = |ssot: &Path, hash: &str| registry::Registry {
+            deployments: vec![registry::RegistryEntry {
+                skill: "my-skill".to_string(),
+                ssot: ssot.to_path_buf(),
+                target: "claude".to_string(),
+                path: copy.clone(),
+                method: DeployMethod::Copy,
+                deployed_at: String::new(),
+                source_hash: hash.to_string(),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            owned_deployment(&copy, &runtime, "my-skill", &entry(&runtime, "")),
+            Some(DeployMethod::Copy)
+        );
+        let other_runtime = temp.path().join("global").join("my-skill");
+        assert_eq!(
+            owned_deployment(&copy, &runtime, "my-skill", &entry(&other_runtime, "")),
+            None
+        );
+        assert_eq!(
+            owned_deployment(&copy, &runtime, "my-skill", &entry(&runtime, "replaced")),
+            None
+        );
+
+        // A native file is ours only when registered from this runtime
+        let rule = agent.join("my-skill.mdc");
+        std::fs::write(&rule, "rule").expect("write rule");
+        let native = |ssot: &Path| registry::Registry {
+            deployments: vec![registry::RegistryEntry {
+                skill: "my-skill".to_string(),
+                ssot: ssot.to_path_buf(),
+                target: "cursor".to_string(),
+                path: rule.clone(),
+                method: DeployMethod::Native,
+                deployed_at: String::new(),
+                source_hash: String::new(),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            owned_deployment(&rule, &runtime, "my-skill", &native(&runtime)),
+            Some(DeployMethod::Native)
+        );
+        assert_eq!(
+            owned_deployment(&rule, &runtime, "my-skill", &native(&other_runtime)),
+            None
+        );
+        assert_eq!(owned_deployment(&rule, &runtime, "my-skill", &none), None);
+    }
 }
@@ -0,0 +1,294 @@
+//! Changes since the last build (`skc diff`).
+//!
+//! Compares the current source against the last-built state: the file
+//! inventory in the manifest, the headings table in the search index per
+//! [[RFC-0004:C-INDEX]], and the shared runtime's stub. Current headings and
+//! stub come from the build pipeline itself (includes expanded, conditional
+//! blocks dropped), so a skill that was just built shows no changes.
+
+use crate::OutputFormat;
+use crate::compiler::{compute_file_hashes, read_manifest, render_runtime};
+use crate::error::{Result, SkillcError};
+use crate::index::{get_all_headings, open_index};
+use crate::resolver::resolve_skill;
+use crate::verify::{FileChange, diff_inventories};
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// How a heading differs from the last build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadingChangeKind {
+    Added,
+    Removed,
+    Renamed,
+}
+
+impl std::fmt::Display for HeadingChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadingChangeKind::Added => write!(f, "added"),
+            HeadingChangeKind::Removed => write!(f, "removed"),
+            HeadingChangeKind::Renamed => write!(f, "renamed"),
+        }
+    }
+}
+
+/// A heading that differs from the last build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeadingChange {
+    pub file: String,
+    pub level: usize,
+    pub change: HeadingChangeKind,
+    /// Current text (previous text for removals)
+    pub text: String,
+    /// Previous text, for renames
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_text: Option<String>,
+}
+
+/// Changes to a skill since its last build.
+#[derive(Debug, Serialize)]
+pub struct DiffReport {
+    pub skill: String,
+    pub files: Vec<FileChange>,
+    pub headings: Vec<HeadingChange>,
+    /// Unified diff of the deployed stub against the regenerated one (empty if identical)
+    pub stub_diff: String,
+}
+
+impl DiffReport {
+    /// True if anything differs from the last build.
+    pub fn has_changes(&self) -> bool {
+        !self.files.is_empty() || !self.headings.is_empty() || !self.stub_diff.is_empty()
+    }
+}
+
+/// A heading reduced to what identifies it for comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HeadingKey {
+    file: String,
+    text: String,
+    level: usize,
+}
+
+/// Compare a skill's current source against its last build.
+pub fn diff(skill: &str) -> Result<DiffReport> {
+    let resolved = resolve_skill(skill)?;
+
+    let manifest = read_manifest(&resolved.runtime_dir)
+        .ok_or_else(|| SkillcError::BuildOutdated(resolved.name.clone()))?;
+    let conn = open_index(&resolved.runtime_dir, &resolved.source_dir, &resolved.name)?;
+
+    let files = diff_inventories(&manifest.files, &compute_file_hashes(&resolved.source_dir)?);
+
+    let built_headings: Vec<HeadingKey> = get_all_headings(&conn)?
+        .into_iter()
+        .map(|h| HeadingKey {
+            file: h.file,
+            text: h.text,
+            level: h.level,
+        })
+        .collect();
+    let rendered = render_runtime(&resolved.source_dir, &resolved.runtime_dir)?;
+    let current_headings: Vec<HeadingKey> = rendered
+        .headings
+        .into_iter()
+        .map(|h| HeadingKey {
+            file: h.file.to_string_lossy().to_string(),
+            text: h.text,
+            level: h.level,
+        })
+        .collect();
+    let headings = diff_headings(&built_headings, &current_headings);
+
+    let deployed = fs::read_to_string(resolved.runtime_dir.join("SKILL.md")).unwrap_or_default();
+    let regenerated = rendered.stub;
+    let stub_diff = if deployed == regenerated {
+        String::new()
+    } else {
+        TextDiff::from_lines(&deployed, &regenerated)
+            .unified_diff()
+            .header("deployed/SKILL.md", "regenerated/SKILL.md")
+            .to_string()
+    };
+
+    Ok(DiffReport {
+        skill: resolved.name,
+        files,
+        headings,
+        stub_diff,
+    })
+}
+
+/// Compare headings per file, in document order.
+///
+/// A removed and an added heading of the same level at the same position in
+/// their file are reported as a rename.
+fn diff_headings(built: &[HeadingKey], current: &[HeadingKey]) -> Vec<HeadingChange> {
+    let group = |headings: &[HeadingKey]| {
+        let mut by_file: BTreeMap<String, Vec<HeadingKey>> = BTreeMap::new();
+        for h in headings {
+            by_file.entry(h.file.clone()).or_default().push(h.clone());
+        }
+        by_file
+    };
+    let built = group(built);
+    let current = group(current);
+
+    let mut files: Vec<&String> = built.keys().chain(current.keys()).collect();
+    files.sort();
+    files.dedup();
+
+    let mut changes = Vec::new();
+    for file in files {
+        let old = built.get(file).map(Vec::as_slice).unwrap_or_default();
+        let new = current.get(file).map(Vec::as_slice).unwrap_or_default();
+
+        // Positions of headings with no identical counterpart on the other side
+        let removed: Vec<usize> = (0..old.len()).filter(|&i| !new.contains(&old[i])).collect();
+        let mut added: Vec<usize> = (0..new.len()).filter(|&j| !old.contains(&new[j])).collect();
+
+        for i in removed {
+            let rename = added
+                .iter()
+                .position(|&j| j == i && new[j].level == old[i].level);
+            match rename {
+                Some(pos) => {
+                    let j = added.remove(pos);
+                    changes.push(HeadingChange {
+                        file: file.clone(),
+                        level: new[j].level,
+                        change: HeadingChangeKind::Renamed,
+                        text: new[j].text.clone(),
+                        old_text: Some(old[i].text.clone()),
+                    });
+                }
+                None => changes.push(HeadingChange {
+                    file: file.clone(),
+                    level: old[i].level,
+                    change: HeadingChangeKind::Removed,
+                    text: old[i].text.clone(),
+                    old_text: None,
+                }),
+            }
+        }
+        for j in added {
+            changes.push(HeadingChange {
+                file: file.clone(),
+                level: new[j].level,
+                change: HeadingChangeKind::Added,
+                text: new[j].text.clone(),
+                old_text: None,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Format a diff report.
+pub fn format_diff(report: &DiffReport, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(report)?);
+    }
+
+    if !report.has_changes() {
+        return Ok(format!("No changes to {} since last build", report.skill));
+    }
+
+    let mut lines = vec![format!("Changes to {} since last build", report.skill)];
+
+    if !report.files.is_empty() {
+        lines.push(String::new());
+        lines.push("Files:".to_string());
+        for file in &report.files {
+            lines.push(format!("  {:<9} {}", file.change.to_string(), file.path));
+        }
+    }
+
+    if !report.headings.is_empty() {
+        lines.push(String::new());
+        lines.push("Headings:".to_string());
+        for heading in &report.headings {
+            let marker = "#".repeat(heading.level);
+            let text = match &heading.old_text {
+                Some(old) => format!("{} {} -> {}", marker, old, heading.text),
+                None => format!("{} {}", marker, heading.text),
+            };
+            lines.push(format!(
+                "  {:<9} {}: {}",
+                heading.change.to_string(),
+                heading.file,
+                text
+            ));
+        }
+    }
+
+    if !report.stub_diff.is_empty() {
+        lines.push(String::new());
+        lines.push("Stub:".to_string());
+        lines.push(report.stub_diff.trim_end().to_string());
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(file: &str, text: &str, level: usize) -> HeadingKey {
+        HeadingKey {
+            file: file.to_string(),
+            text: text.to_string(),
+            level,
+        }
+    }
+
+    #[test]
+    fn test_diff_headings_detects_rename() {
+        let built = vec![
+            heading("SKILL.md", "Intro", 1),
+            heading("SKILL.md", "Setup", 2),
+            heading("SKILL.md", "Usage", 2),
+        ];
+        let current = vec![
+            heading("SKILL.md", "Intro", 1),
+            heading("SKILL.md", "Installation", 2),
+            heading("SKILL.md", "Usage", 2),
+        ];
+
+        let changes = diff_headings(&built, &current);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].change, HeadingChangeKind::Renamed);
+        assert_eq!(changes[0].text, "Installation");
+        assert_eq!(changes[0].old_text.as_deref(), Some("Setup"));
+    }
+
+    #[test]
+    fn test_diff_headings_added_and_removed() {
+        let built = vec![heading("a.md", "Old", 1), heading("b.md", "Gone", 1)];
+        let current = vec![
+            heading("a.md", "Old", 1),
+            heading("a.md", "New", 2),
+            heading("c.md", "Fresh", 1),
+        ];
+
+        let changes = diff_headings(&built, &current);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.file.as_str(), c.change, c.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a.md", HeadingChangeKind::Added, "New"),
+                ("b.md", HeadingChangeKind::Removed, "Gone"),
+                ("c.md", HeadingChangeKind::Added, "Fresh"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! Environment health check (`skc doctor`).
+//!
+//! Walks the project and global runtime stores, every agent directory, and
+//! the deployment registry, reporting problems that keep agents from picking
+//! up a skill, each with a remediation hint. With `fix`, the repairs that
+//! cannot lose user data are applied: removing dangling links into a skillc
+//! runtime, refreshing stale skillc copies, deleting orphaned index files,
+//! syncing fallback logs, and dropping registry entries for deleted paths.
+
+use crate::OutputFormat;
+use crate::compiler::read_manifest;
+use crate::config::{
+    TargetSpec, all_targets, config_diagnostics, find_project_root, global_runtime_store,
+    global_source_store,
+};
+use crate::deploy::{self, DeployMethod, deploy_source, is_link};
+use crate::error::Result;
+use crate::index::{SCHEMA_VERSION, get_index_path};
+use crate::registry::{self, Registry, RegistryEntry};
+use crate::search::current_tokenizer;
+use crate::sync::{list_skills_in_logs_dir, sync_and_purge};
+use crate::util::{project_logs_dir, project_runtime_dir, project_skills_dir, target_variants_dir};
+use crate::verbose;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Options for [`doctor`].
+#[derive(Debug, Default)]
+pub struct DoctorOptions {
+    /// Apply safe repairs
+    pub fix: bool,
+}
+
+/// Kind of problem found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProblemKind {
+    /// Agent directory link whose target no longer exists
+    BrokenLink,
+    /// Copy deployment older than its runtime
+    StaleCopy,
+    /// Registry entry whose agent directory path no longer exists
+    MissingDeployment,
+    /// Search index missing, or built with an old schema or another tokenizer
+    StaleIndex,
+    /// Search index named for this source but recording another source path
+    IndexCollision,
+    /// Search index left behind for a source path that no longer maps here
+    OrphanIndex,
+    /// Fallback access logs not yet synced to the runtime
+    UnsyncedLogs,
+    /// Config file that fails to parse or has an unsupported version
+    Config,
+}
+
+impl std::fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ProblemKind::BrokenLink => "broken-link",
+            ProblemKind::StaleCopy => "stale-copy",
+            ProblemKind::MissingDeployment => "missing-deployment",
+            ProblemKind::StaleIndex => "stale-index",
+            ProblemKind::IndexCollision => "index-collision",
+            ProblemKind::OrphanIndex => "orphan-index",
+            ProblemKind::UnsyncedLogs => "unsynced-logs",
+            ProblemKind::Config => "config",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One problem, with how to fix it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub kind: ProblemKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    pub path: PathBuf,
+    pub message: String,
+    pub hint: String,
+    /// True if `--fix` repaired it
+    pub fixed: bool,
+}
+
+/// Result of [`doctor`].
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub fix: bool,
+    /// Runtimes inspected
+    pub runtimes: usize,
+    /// Agent directory entries inspected
+    pub deployments: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl DoctorReport {
+    /// Problems not repaired by this run.
+    pub fn unfixed(&self) -> usize {
+        self.problems.iter().filter(|p| !p.fixed).count()
+    }
+}
+
+/// A repair `--fix` may apply.
+enum Repair {
+    RemoveLink,
+    RefreshCopy(PathBuf),
+    Forget,
+    DeleteFile,
+    Sync(PathBuf, String),
+}
+
+struct Checker {
+    fix: bool,
+    registry: Registry,
+    report: DoctorReport,
+}
+
+/// Check every store, agent directory, and config file.
+pub fn doctor(options: &DoctorOptions) -> Result<DoctorReport> {
+    let mut checker = Checker {
+        fix: options.fix,
+        registry: Registry::load().unwrap_or_default(),
+        report: DoctorReport {
+            fix: options.fix,
+            runtimes: 0,
+            deployments: 0,
+            problems: Vec::new(),
+        },
+    };
+    let project_root = find_project_root();
+
+    for (path, diagnostic) in config_diagnostics() {
+        // First line only: TOML errors continue with a source excerpt
+        let first_line = diagnostic.lines().next().unwrap_or_default();
+        let message = first_line
+            .split_once(": ")
+            .map_or(first_line, |(_, rest)| rest)
+            .to_string();
+        checker.report(
+            ProblemKind::Config,
+            None,
+            path,
+            message,
+            "edit the file; until then its settings are ignored".to_string(),
+            None,
+        );
+    }
+
+    // Runtimes and their search indexes
+    let mut stores = Vec::new();
+    if let Some(root) = &project_root {
+        stores.push((project_runtime_dir(root), project_skills_dir(root)));
+    }
+    stores.push((global_runtime_store()?, global_source_store()?));
+    for (runtime_store, source_store) in &stores {
+        for (name, runtime_dir) in runtimes_in(runtime_store) {
+            checker.report.runtimes += 1;
+            let source_dir = source_store.join(&name);
+            if source_dir.join("SKILL.md").is_file() {
+                checker.check_indexes(&name, &runtime_dir, &source_dir);
+            }
+        }
+    }
+
+    // Agent directories, then registered paths outside them
+    let mut agent_entries: Vec<(TargetSpec, PathBuf)> = Vec::new();
+    for target in all_targets() {
+        let mut dirs: Vec<PathBuf> = target.skills_path(None).into_iter().collect();
+        if let Some(root) = &project_root {
+            dirs.extend(target.skills_path(Some(root)));
+        }
+        for dir in dirs {
+            for path in dir_entries(&dir) {
+                agent_entries.push((target.clone(), path));
+            }
+        }
+    }
+    for entry in checker.registry.deployments.clone() {
+        let Ok(target) = entry.target.parse::<TargetSpec>();
+        agent_entries.push((target, entry.path));
+    }
+    let mut seen = std::collections::HashSet::new();
+    agent_entries.retain(|(_, path)| seen.insert(registry::location(path)));
+    for (target, path) in agent_entries {
+        checker.check_deployment(&target, &path, &stores);
+    }
+
+    // Fallback logs in the current directory and the project root
+    let mut log_roots: Vec<PathBuf> = std::env::current_dir().into_iter().collect();
+    if let Some(root) = project_root
+        && !log_roots.contains(&root)
+    {
+        log_roots.push(root);
+    }
+    for root in log_roots {
+        let logs_dir = project_logs_dir(&root);
+        for skill in list_skills_in_logs_dir(&logs_dir) {
+            let db = logs_dir.join(&skill).join(".skillc-meta").join("logs.db");
+            let entries = count_log_entries(&db);
+            if entries == 0 {
+                continue;
+            }
+            checker.report(
+                ProblemKind::UnsyncedLogs,
+                Some(&skill),
+                db,
+                format!("{} fallback log entries not synced", entries),
+                "run `skc sync` to move them into the runtime".to_string(),
+                Some(Repair::Sync(logs_dir.clone(), skill.clone())),
+            );
+        }
+    }
+
+    Ok(checker.report)
+}
+
+impl Checker {
+    /// Record a problem, applying its repair when fixing.
+    fn report(
+        &mut self,
+        kind: ProblemKind,
+        skill: Option<&str>,
+        path: PathBuf,
+        message: String,
+        hint: String,
+        repair: Option<Repair>,
+    ) {
+        let fixed = match repair {
+            Some(repair) if self.fix => match apply(&repair, &path) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("warning: failed to fix {}: {}", path.display(), e);
+                    false
+                }
+            },
+            _ => false,
+        };
+        verbose!("doctor: {} {} (fixed: {})", kind, path.display(), fixed);
+        self.report.problems.push(Problem {
+            kind,
+            skill: skill.map(str::to_string),
+            path,
+            message,
+            hint,
+            fixed,
+        });
+    }
+
+    /// Check the index files in a runtime against its source.
+    /// Check the search indexes of a runtime and of each of its target variants.
+    fn check_indexes(&mut self, skill: &str, runtime_dir: &Path, source_dir: &Path) {
+        let variants = dir_entries(&target_variants_dir(runtime_dir))
+            .into_iter()
+            .filter(|dir| dir.is_dir());
+        for dir in std::iter::once(runtime_dir.to_path_buf()).chain(variants) {
+            self.check_index_dir(skill, &dir, source_dir);
+        }
+    }
+
+    /// Check the search index files in one runtime's `.skillc-meta/`.
+    fn check_index_dir(&mut self, skill: &str, runtime_dir: &Path, source_dir: &Path) {
+        let expected = get_index_path(runtime_dir, source_dir);
+        let rebuild = format!("run `skc build {} --force` to rebuild it", skill);
+
+        if !expected.exists() {
+            self.report(
+                ProblemKind::StaleIndex,
+                Some(skill),
+                expected.clone(),
+                "search index is missing".to_string(),
+                rebuild.clone(),
+                None,
+            );
+        }
+
+        for path in dir_entries(&runtime_dir.join(".skillc-meta")) {
+            let is_index = path.file_name().is_some_and(|n| {
+                let n = n.to_string_lossy();
+                n.starts_with("search-") && n.ends_with(".db")
+            });
+            if !is_index {
+                continue;
+            }
+            if path != expected {
+                self.report(
+                    ProblemKind::OrphanIndex,
+                    Some(skill),
+                    path,
+                    "index does not belong to the current source path".to_string(),
+                    "delete it; searches never read it".to_string(),
+                    Some(Repair::DeleteFile),
+                );
+                continue;
+            }
+
+            let meta = read_index_meta(&path);
+            let canonical_source = source_dir
+                .canonicalize()
+                .unwrap_or_else(|_| source_dir.to_path_buf());
+            let message = match meta.get("skill_path") {
+                None => Some((
+                    ProblemKind::StaleIndex,
+                    "search index is unreadable".to_string(),
+                )),
+                Some(stored) if Path::new(stored) != canonical_source => Some((
+                    ProblemKind::IndexCollision,
+                    format!("index file belongs to {}", stored),
+                )),
+                Some(_) => stale_index_reason(&meta).map(|r| (ProblemKind::StaleIndex, r)),
+            };
+            if let Some((kind, message)) = message {
+                self.report(kind, Some(skill), path, message, rebuild.clone(), None);
+            }
+        }
+    }
+
+    /// Check one agent directory entry (or registered path).
+    fn check_deployment(
+        &mut self,
+        target: &TargetSpec,
+        path: &Path,
+        stores: &[(PathBuf, PathBuf)],
+    ) {
+        let registered = self.registry.entry_at(path).cloned();
+
+        if path.symlink_metadata().is_err() {
+            if let Some(entry) = registered {
+                self.report(
+                    ProblemKind::MissingDeployment,
+                    Some(&entry.skill),
+                    path.to_path_buf(),
+                    "registered deployment no longer exists".to_string(),
+                    format!("run `skc build {}` to redeploy it", entry.skill),
+                    Some(Repair::Forget),
+                );
+            }
+            return;
+        }
+        self.report.deployments += 1;
+        let skill = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if is_link(path) {
+            if path.canonicalize().is_ok() {
+                return;
+            }
+            let link = fs::read_link(path).unwrap_or_default();
+            let ours = in_runtime_store(&link);
+            self.report(
+                ProblemKind::BrokenLink,
+                Some(&skill),
+                path.to_path_buf(),
+                format!("links to missing {}", link.display()),
+                if ours {
+                    format!(
+                        "remove it, or run `skc build {}` if the skill still exists",
+                        skill
+                    )
+                } else {
+                    "remove it; it was not created by skillc".to_string()
+                },
+                ours.then_some(Repair::RemoveLink),
+            );
+            return;
+        }
+
+        // Only skillc copies carry a manifest
+        let Some(deployed) = read_manifest(path) else {
+            return;
+        };
+        let ssot = registered.as_ref().map(|e| e.ssot.clone()).or_else(|| {
+            stores
+                .iter()
+                .map(|(runtime_store, _)| runtime_store.join(&deployed.skill))
+                .find(|runtime| runtime.is_dir())
+        });
+        let Some(ssot) = ssot.filter(|s| s.is_dir()) else {
+            self.report(
+                ProblemKind::StaleCopy,
+                Some(&deployed.skill),
+                path.to_path_buf(),
+                "copy of a skill whose runtime no longer exists".to_string(),
+                "delete it, or rebuild the skill to redeploy".to_string(),
+                None,
+            );
+            return;
+        };
+        if deploy::owned_deployment(path, &ssot, &deployed.skill, &self.registry)
+            != Some(DeployMethod::Copy)
+        {
+            return;
+        }
+
+        let source = deploy_source(&ssot, target);
+        let Some(current) = read_manifest(&source) else {
+            return;
+        };
+        let same_stub =
+            fs::read(source.join("SKILL.md")).ok() == fs::read(path.join("SKILL.md")).ok();
+        if current.source_hash == deployed.source_hash && same_stub {
+            return;
+        }
+        self.report(
+            ProblemKind::StaleCopy,
+            Some(&deployed.skill),
+            path.to_path_buf(),
+            format!("copy is behind its runtime {}", ssot.display()),
+            format!(
+                "run `skc build {} --copy --force` to redeploy it",
+                deployed.skill
+            ),
+            Some(Repair::RefreshCopy(source)),
+        );
+        if self.report.problems.last().is_some_and(|p| p.fixed) {
+            let entry = RegistryEntry {
+                skill: deployed.skill.clone(),
+                ssot: ssot.canonicalize().unwrap_or(ssot),
+                target: registered.map_or_else(|| target.to_string(), |e| e.target),
+                path: path.to_path_buf(),
+                method: DeployMethod::Copy,
+                deployed_at: chrono::Utc::now().to_rfc3339(),
+                source_hash: current.source_hash,
+            };
+            if let Err(e) = registry::record(vec![entry]) {
+                eprintln!("warning: failed to update deployment registry: {}", e);
+            }
+        }
+    }
+}
+
+fn apply(repair: &Repair, path: &Path) -> Result<()> {
+    match repair {
+        Repair::RemoveLink => {
+            deploy::remove_deployment(path, DeployMethod::Symlink)?;
+            registry::forget(&[path.to_path_buf()])
+        }
+        Repair::RefreshCopy(source) => deploy::refresh_copy(source, path),
+        Repair::Forget => registry::forget(&[path.to_path_buf()]),
+        Repair::DeleteFile => Ok(fs::remove_file(path)?),
+        Repair::Sync(logs_dir, skill) => sync_and_purge(logs_dir, skill).map(|_| ()),
+    }
+}
+
+/// Why an index needs rebuilding, if it does.
+fn stale_index_reason(meta: &HashMap<String, String>) -> Option<String> {
+    let schema: i32 = meta
+        .get("schema_version")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    if schema < SCHEMA_VERSION {
+        return Some(format!(
+            "index schema v{} is older than v{}",
+            schema, SCHEMA_VERSION
+        ));
+    }
+    let tokenizer = current_tokenizer();
+    match meta.get("tokenizer") {
+        Some(stored) if *stored != tokenizer => Some(format!(
+            "index uses tokenizer '{}', config wants '{}'",
+            stored, tokenizer
+        )),
+        _ => None,
+    }
+}
+
+fn read_index_meta(path: &Path) -> HashMap<String, String> {
+    let read = || -> rusqlite::Result<HashMap<String, String>> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut stmt = conn.prepare("SELECT key, value FROM index_meta")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    };
+    read().unwrap_or_default()
+}
+
+fn count_log_entries(db: &Path) -> i64 {
+    Connection::open_with_flags(db, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|conn| conn.query_row("SELECT COUNT(*) FROM access_log", [], |row| row.get(0)))
+        .unwrap_or(0)
+}
+
+/// Built runtimes (directories with a manifest) in a runtime store.
+fn runtimes_in(store: &Path) -> Vec<(String, PathBuf)> {
+    dir_entries(store)
+        .into_iter()
+        .filter(|path| path.join(".skillc-meta").join("manifest.json").is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            Some((name, path))
+        })
+        .collect()
+}
+
+/// Entries of a directory, sorted; empty if it cannot be read.
+fn dir_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+/// Whether a link target lies inside a skillc runtime store (`.skillc/runtime`).
+fn in_runtime_store(path: &Path) -> bool {
+    let names: Vec<_> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(n) => Some(n),
+            _ => None,
+        })
+        .collect();
+    names
+        .windows(2)
+        .any(|w| w[0] == ".skillc" && w[1] == "runtime")
+}
+
+/// Format a doctor report.
+pub fn format_doctor(report: &DoctorReport, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(report)?);
+    }
+
+    let mut lines = Vec::new();
+    for problem in &report.problems {
+        let status = if problem.fixed { "fixed" } else { "problem" };
+        let skill = problem
+            .skill
+            .as_deref()
+            .map(|s| format!("{}: ", s))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{:<8} {:<18} {}{}",
+            status,
+            problem.kind.to_string(),
+            skill,
+            problem.message
+        ));
+        lines.push(format!("         {:<18} {}", "", problem.path.display()));
+        if !problem.fixed {
+            lines.push(format!("         {:<18} hint: {}", "", problem.hint));
+        }
+    }
+
+    let summary = format!(
+        "Checked {} runtime(s) and {} deployment(s)",
+        report.runtimes, report.deployments
+    );
+    let fixed = report.problems.len() - report.unfixed();
+    lines.push(match (report.problems.len(), fixed) {
+        (0, _) => format!("{}: no problems found", summary),
+        (n, 0) if !report.fix => format!(
+            "{}: {} problem(s); run `skc doctor --fix` to apply safe repairs",
+            summary, n
+        ),
+        (n, fixed) => format!("{}: {} problem(s), {} fixed", summary, n, fixed),
+    });
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_runtime_store() {
+        assert!(in_runtime_store(Path::new(
+            "/home/u/.skillc/runtime/my-skill"
+        )));
+        assert!(in_runtime_store(Path::new(
+            "/work/app/.skillc/runtime/my-skill/.skillc-meta/targets/codex"
+        )));
+        assert!(!in_runtime_store(Path::new("/home/u/skills/my-skill")));
+        assert!(!in_runtime_store(Path::new(
+            "/home/u/.skillc/skills/my-skill"
+        )));
+    }
+
+    #[test]
+    fn test_stale_index_reason() {
+        let meta = |schema: &str, tokenizer: &str| {
+            HashMap::from([
+                ("schema_version".to_string(), schema.to_string()),
+                ("tokenizer".to_string(), tokenizer.to_string()),
+            ])
+        };
+        let current = current_tokenizer();
+        assert_eq!(
+            stale_index_reason(&meta(&SCHEMA_VERSION.to_string(), &current)),
+            None
+        );
+        assert_eq!(
+            stale_index_reason(&meta("1", &current)),
+            Some(format!("index schema v1 is older than v{}", SCHEMA_VERSION))
+        );
+        assert!(
+            stale_index_reason(&meta(&SCHEMA_VERSION.to_string(), "other"))
+                .is_some_and(|r| r.contains("tokenizer 'other'"))
+        );
+    }
+}
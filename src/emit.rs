@@ -0,0 +1,330 @@
+//! Target-native instruction formats (`skc build --native`).
+//!
+//! Some agents read their own instruction files instead of (or as well as)
+//! a skills directory: Cursor loads `.mdc` rules, Copilot reads
+//! `copilot-instructions.md`, and Codex and OpenCode read `AGENTS.md`. For
+//! those targets the build renders the compiled stub into the native format
+//! under `.skillc-meta/native/<target>/` of the staged runtime, and the
+//! deploy step places it at the agent's own path (see [`native_path`]).
+
+use crate::config::{Target, TargetSpec};
+use crate::deploy::deploy_source;
+use crate::error::{Result, SkillcError};
+use crate::frontmatter::{self, Frontmatter};
+use crate::markdown;
+use crate::verbose;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Frontmatter key listing the file globs a skill applies to.
+pub const GLOBS_FIELD: &str = "globs";
+
+/// A target's native instruction format.
+///
+/// Configured targets select one with `layout` (e.g. `layout = "agents-md"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NativeFormat {
+    /// Cursor project rule (`<skill>.mdc`) with `description`/`globs` frontmatter
+    CursorRule,
+    /// GitHub Copilot repository instructions (`copilot-instructions.md`)
+    CopilotInstructions,
+    /// `AGENTS.md`, read by Codex and OpenCode
+    AgentsMd,
+    /// `GEMINI.md` context file
+    GeminiMd,
+}
+
+impl NativeFormat {
+    /// Native format for a target, if it has one besides `SKILL.md`.
+    pub fn for_target(target: Target) -> Option<Self> {
+        match target {
+            Target::Cursor => Some(NativeFormat::CursorRule),
+            Target::Copilot => Some(NativeFormat::CopilotInstructions),
+            Target::Codex | Target::Opencode => Some(NativeFormat::AgentsMd),
+            Target::Gemini => Some(NativeFormat::GeminiMd),
+            Target::Claude | Target::Kiro | Target::Trae => None,
+        }
+    }
+
+    /// Name used for `layout` in config.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NativeFormat::CursorRule => "cursor-rule",
+            NativeFormat::CopilotInstructions => "copilot-instructions",
+            NativeFormat::AgentsMd => "agents-md",
+            NativeFormat::GeminiMd => "gemini-md",
+        }
+    }
+
+    /// Output file name for a skill.
+    pub fn file_name(&self, skill: &str) -> String {
+        match self {
+            NativeFormat::CursorRule => format!("{}.mdc", skill),
+            NativeFormat::CopilotInstructions => "copilot-instructions.md".to_string(),
+            NativeFormat::AgentsMd => "AGENTS.md".to_string(),
+            NativeFormat::GeminiMd => "GEMINI.md".to_string(),
+        }
+    }
+
+    /// Render a compiled stub body in this format.
+    ///
+    /// Cursor rules are never always-applied: without globs, Cursor attaches
+    /// the rule when the description matches, as agents do for skills.
+    pub fn render(&self, frontmatter: &Frontmatter, globs: &[String], body: &str) -> String {
+        let body = body.trim_start_matches('\n');
+        match self {
+            NativeFormat::CursorRule => {
+                let mut out = String::from("---\n");
+                out.push_str(&format!("description: \"{}\"\n", frontmatter.description));
+                out.push_str(&format!("globs: {}\n", globs.join(",")));
+                out.push_str("alwaysApply: false\n");
+                out.push_str("---\n\n");
+                out.push_str(body);
+                out
+            }
+            NativeFormat::CopilotInstructions | NativeFormat::AgentsMd | NativeFormat::GeminiMd => {
+                let mut out = format!(
+                    "<!-- Generated by skillc from skill '{}'. Do not edit. -->\n\n",
+                    frontmatter.name
+                );
+                out.push_str(&format!("> {}\n", frontmatter.description));
+                if !globs.is_empty() {
+                    out.push_str(&format!(">\n> Applies to: `{}`\n", globs.join("`, `")));
+                }
+                out.push('\n');
+                out.push_str(body);
+                out
+            }
+        }
+    }
+}
+
+/// A native instruction file written by [`emit_native`].
+#[derive(Debug, Clone)]
+pub struct NativeOutput {
+    /// Target name
+    pub target: String,
+    pub format: NativeFormat,
+    pub path: PathBuf,
+}
+
+/// Get the directory holding a target's native output inside a runtime.
+pub fn native_output_dir(runtime_dir: &Path, target: &str) -> PathBuf {
+    runtime_dir.join(".skillc-meta").join("native").join(target)
+}
+
+/// Get a target's native file inside a runtime, if the target has a native format.
+pub fn native_output(runtime_dir: &Path, target: &TargetSpec, skill: &str) -> Option<PathBuf> {
+    let format = target.native_format()?;
+    Some(native_output_dir(runtime_dir, &target.to_string()).join(format.file_name(skill)))
+}
+
+/// Where an agent reads a target's native file.
+///
+/// In a project: `<dir>/rules/<skill>.mdc` for Cursor rules,
+/// `<dir>/copilot-instructions.md` for Copilot, and `AGENTS.md` or
+/// `GEMINI.md` at the project root. Globally only the `AGENTS.md` and
+/// `GEMINI.md` formats have a file, under `~/<dir>/`. `<dir>` is the
+/// target's agent directory (e.g. `.cursor`, or `dir` of a configured
+/// target). Returns `None` for targets without a native file in that scope.
+pub fn native_path(
+    target: &TargetSpec,
+    skill: &str,
+    project_root: Option<&Path>,
+) -> Option<PathBuf> {
+    let format = target.native_format()?;
+    let dir = match target {
+        TargetSpec::Known(t) => t.dir_name().to_string(),
+        TargetSpec::Configured(_, config) => config.dir.clone(),
+        TargetSpec::Custom(_) => return None,
+    };
+    let file = format.file_name(skill);
+    match (format, project_root) {
+        (NativeFormat::CursorRule, Some(root)) => Some(root.join(dir).join("rules").join(file)),
+        (NativeFormat::CopilotInstructions, Some(root)) => Some(root.join(dir).join(file)),
+        (NativeFormat::AgentsMd | NativeFormat::GeminiMd, Some(root)) => Some(root.join(file)),
+        (NativeFormat::AgentsMd | NativeFormat::GeminiMd, None) => {
+            Some(dirs::home_dir()?.join(dir).join(file))
+        }
+        (NativeFormat::CursorRule | NativeFormat::CopilotInstructions, None) => None,
+    }
+}
+
+/// Render the built stub into each target's native format.
+///
+/// Uses the target's runtime variant when the build produced one. Configured
+/// targets use their `layout`. Targets without a native format, and custom
+/// paths, are skipped. Called on the staged build, so native files are
+/// swapped in and rolled back with the rest of the runtime.
+pub fn emit_native(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    targets: &[TargetSpec],
+) -> Result<Vec<NativeOutput>> {
+    let rendered = render_native(source_dir, runtime_dir, targets)?;
+    for (output, content) in &rendered {
+        if let Some(dir) = output.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&output.path, content)?;
+        verbose!("native: wrote {}", output.path.display());
+    }
+    Ok(rendered.into_iter().map(|(output, _)| output).collect())
+}
+
+/// True if every target's native file in the runtime matches a fresh render.
+pub fn native_current(source_dir: &Path, runtime_dir: &Path, targets: &[TargetSpec]) -> bool {
+    render_native(source_dir, runtime_dir, targets).is_ok_and(|rendered| {
+        rendered.iter().all(|(output, content)| {
+            fs::read_to_string(&output.path).is_ok_and(|existing| existing == *content)
+        })
+    })
+}
+
+/// Render each target's native file from the runtime's stub, without writing it.
+fn render_native(
+    source_dir: &Path,
+    runtime_dir: &Path,
+    targets: &[TargetSpec],
+) -> Result<Vec<(NativeOutput, String)>> {
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let frontmatter = frontmatter::parse(&fs::read_to_string(source_dir.join("SKILL.md"))?)?;
+    let globs = frontmatter_globs(&frontmatter)?;
+
+    let mut rendered: Vec<(NativeOutput, String)> = Vec::new();
+    for spec in targets {
+        let target = spec.to_string();
+        let Some(format) = spec.native_format() else {
+            verbose!("native: {} has no native format", target);
+            continue;
+        };
+        if rendered.iter().any(|(o, _)| o.target == target) {
+            continue;
+        }
+
+        let stub = fs::read_to_string(deploy_source(runtime_dir, spec).join("SKILL.md"))?;
+        let body = markdown::strip_frontmatter(&stub);
+        let path =
+            native_output_dir(runtime_dir, &target).join(format.file_name(&frontmatter.name));
+        rendered.push((
+            NativeOutput {
+                target,
+                format,
+                path,
+            },
+            format.render(&frontmatter, &globs, body),
+        ));
+    }
+    Ok(rendered)
+}
+
+/// Read the optional `globs` frontmatter field: a comma-separated string or a list.
+fn frontmatter_globs(frontmatter: &Frontmatter) -> Result<Vec<String>> {
+    let invalid = || {
+        SkillcError::InvalidFrontmatter(format!(
+            "'{}' must be a string or a list of strings",
+            GLOBS_FIELD
+        ))
+    };
+    let globs = match frontmatter.extra.get(GLOBS_FIELD) {
+        None | Some(serde_yaml::Value::Null) => Vec::new(),
+        Some(serde_yaml::Value::String(s)) => s.split(',').map(str::to_string).collect(),
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect::<Result<_>>()?,
+        Some(_) => return Err(invalid()),
+    };
+    Ok(globs
+        .into_iter()
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_frontmatter(extra: &str) -> Frontmatter {
+        frontmatter::parse(&format!(
+            "---\nname: my-skill\ndescription: Helps with Rust\n{}---\n",
+            extra
+        ))
+        .expect("parse frontmatter")
+    }
+
+    #[test]
+    fn test_render_cursor_rule() {
+        let fm = test_frontmatter("globs:\n  - \"**/*.rs\"\n  - Cargo.toml\n");
+        let globs = frontmatter_globs(&fm).expect("globs");
+        let out = NativeFormat::CursorRule.render(&fm, &globs, "\n# my-skill (compiled)\n");
+        assert_eq!(
+            out,
+            "---\ndescription: \"Helps with Rust\"\nglobs: **/*.rs,Cargo.toml\nalwaysApply: false\n---\n\n# my-skill (compiled)\n"
+        );
+        assert_eq!(
+            NativeFormat::CursorRule.file_name("my-skill"),
+            "my-skill.mdc"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_formats() {
+        let fm = test_frontmatter("globs: \"src/**, tests/**\"\n");
+        let globs = frontmatter_globs(&fm).expect("globs");
+        assert_eq!(globs, vec!["src/**", "tests/**"]);
+
+        let out = NativeFormat::AgentsMd.render(&fm, &globs, "# Body\n");
+        assert!(out.starts_with("<!-- Generated by skillc from skill 'my-skill'."));
+        assert!(out.contains("> Helps with Rust\n>\n> Applies to: `src/**`, `tests/**`\n"));
+        assert!(out.ends_with("\n# Body\n"));
+
+        let fm = test_frontmatter("globs: 3\n");
+        assert!(frontmatter_globs(&fm).is_err());
+    }
+
+    #[test]
+    fn test_native_format_per_target() {
+        assert_eq!(
+            NativeFormat::for_target(Target::Cursor),
+            Some(NativeFormat::CursorRule)
+        );
+        assert_eq!(
+            NativeFormat::for_target(Target::Codex),
+            Some(NativeFormat::AgentsMd)
+        );
+        assert_eq!(NativeFormat::for_target(Target::Claude), None);
+    }
+
+    #[test]
+    fn test_native_path_per_scope() {
+        let root = Path::new("/project");
+        let cursor = TargetSpec::Known(Target::Cursor);
+        assert_eq!(
+            native_path(&cursor, "my-skill", Some(root)),
+            Some(PathBuf::from("/project/.cursor/rules/my-skill.mdc"))
+        );
+        assert_eq!(native_path(&cursor, "my-skill", None), None);
+        assert_eq!(
+            native_path(&TargetSpec::Known(Target::Copilot), "my-skill", Some(root)),
+            Some(PathBuf::from("/project/.github/copilot-instructions.md"))
+        );
+        assert_eq!(
+            native_path(&TargetSpec::Known(Target::Codex), "my-skill", Some(root)),
+            Some(PathBuf::from("/project/AGENTS.md"))
+        );
+        assert!(
+            native_path(&TargetSpec::Known(Target::Gemini), "my-skill", None)
+                .is_some_and(|p| p.ends_with(".gemini/GEMINI.md"))
+        );
+        assert_eq!(
+            native_path(&TargetSpec::Known(Target::Claude), "my-skill", Some(root)),
+            None
+        );
+    }
+}
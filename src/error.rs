@@ -24,12 +24,29 @@ pub enum ErrorCode {
     E003,
     /// E004: Search query is empty or whitespace-only
     E004,
+    /// E005: Raw search query is not valid FTS5 syntax
+    E005,
     /// E010: Directory exists but lacks SKILL.md
     E010,
     /// E011: SKILL.md lacks required `name` or `description`
     E011,
     /// E012: Symlink or path traversal would escape skill directory
     E012,
+    /// E013: Build check found a runtime or deployment that needs rebuilding
+    E013,
+    /// E014: Stub template has an unknown or malformed placeholder
+    E014,
+    /// E015: Compiled stub exceeds the line limit
+    E015,
+    /// E016: Compiled stub exceeds the configured token budget
+    E016,
+    /// E017: Verify found files that differ from the last build
+    E017,
+    /// E018: Build directive is malformed (missing include target or section,
+    /// include cycle, unknown or unbalanced conditional)
+    E018,
+    /// E019: A configured pre- or post-build hook failed
+    E019,
     /// E020: Gateway show command found no matching heading
     E020,
     /// E021: Gateway open command target does not exist
@@ -48,6 +65,12 @@ pub enum ErrorCode {
     E042,
     /// E050: Init command target already has SKILL.md
     E050,
+    /// E060: Skill archive is malformed or fails checksum verification
+    E060,
+    /// E070: Doctor found problems that were not fixed
+    E070,
+    /// E080: Rollback found no retained build to restore
+    E080,
     /// E100: CLI parsing failed (unknown flag, missing value, etc.)
     E100,
     /// E999: Internal error (IO, database, parsing failures)
@@ -72,6 +95,8 @@ pub enum WarningCode {
     W002,
     /// W003: Local fallback logs exist and are older than threshold
     W003,
+    /// W020: Compiled stub exceeds the configured token budget
+    W020,
 }
 
 impl fmt::Display for WarningCode {
@@ -92,6 +117,8 @@ pub enum SkillcWarning {
     LoggingDisabled,
     /// W003: Stale local logs
     StaleLogs(String),
+    /// W020: Stub over token budget (estimated tokens, budget)
+    StubOverBudget(usize, usize),
 }
 
 impl SkillcWarning {
@@ -101,6 +128,7 @@ impl SkillcWarning {
             SkillcWarning::MultipleMatches(_) => WarningCode::W001,
             SkillcWarning::LoggingDisabled => WarningCode::W002,
             SkillcWarning::StaleLogs(_) => WarningCode::W003,
+            SkillcWarning::StubOverBudget(_, _) => WarningCode::W020,
         }
     }
 
@@ -116,6 +144,9 @@ impl SkillcWarning {
             SkillcWarning::StaleLogs(s) => {
                 format!("stale local logs for '{}'; run 'skc sync' to upload", s)
             }
+            SkillcWarning::StubOverBudget(tokens, budget) => {
+                format!("stub is ~{} tokens, over budget of {}", tokens, budget)
+            }
         }
     }
 
@@ -147,6 +178,7 @@ pub enum SkillcError {
     IndexUnusable(String),
     IndexHashCollision(String),
     EmptyQuery,
+    InvalidQuery(String),
     NotAValidSkill(String),
 
     // E011–E019: Compilation errors (RFC-0001)
@@ -156,6 +188,23 @@ pub enum SkillcError {
     // E012: Path escape (unified)
     PathEscapesRoot(String),
 
+    // E013: Build check (RFC-0001)
+    BuildOutdated(String),
+
+    // E014–E015: Stub rendering (RFC-0001)
+    InvalidStubTemplate(String),
+    StubTooLong(usize),
+    StubOverBudget(usize, usize),
+
+    // E017: Build verification (RFC-0001)
+    VerifyDrift(String),
+
+    // E018: Build directives (RFC-0001)
+    InvalidDirective(String),
+
+    // E019: Build hooks (RFC-0009)
+    BuildHookFailed(String),
+
     // E020–E029: Gateway errors (RFC-0002)
     SectionNotFound(String),
     /// Section not found with suggestions per [[RFC-0002:C-SHOW]]
@@ -175,6 +224,16 @@ pub enum SkillcError {
     // E050–E059: Scaffolding errors (RFC-0006)
     SkillAlreadyExists(String),
 
+    // E060–E069: Packaging errors (pack, install)
+    InvalidArchive(String),
+
+    // E070–E079: Environment health (doctor)
+    DoctorFoundProblems(usize),
+
+    // E080–E089: Build history (rollback)
+    /// No retained build (skill, requested build number)
+    NoRetainedBuild(String, Option<u64>),
+
     // E100–E199: CLI parsing errors
     InvalidOption(String),
 
@@ -198,10 +257,18 @@ impl SkillcError {
             SkillcError::IndexUnusable(_) => ErrorCode::E002,
             SkillcError::IndexHashCollision(_) => ErrorCode::E003,
             SkillcError::EmptyQuery => ErrorCode::E004,
+            SkillcError::InvalidQuery(_) => ErrorCode::E005,
             SkillcError::NotAValidSkill(_) => ErrorCode::E010,
             SkillcError::MissingFrontmatterField(_) => ErrorCode::E011,
             SkillcError::InvalidFrontmatter(_) => ErrorCode::E011,
             SkillcError::PathEscapesRoot(_) => ErrorCode::E012,
+            SkillcError::BuildOutdated(_) => ErrorCode::E013,
+            SkillcError::InvalidStubTemplate(_) => ErrorCode::E014,
+            SkillcError::StubTooLong(_) => ErrorCode::E015,
+            SkillcError::StubOverBudget(_, _) => ErrorCode::E016,
+            SkillcError::VerifyDrift(_) => ErrorCode::E017,
+            SkillcError::InvalidDirective(_) => ErrorCode::E018,
+            SkillcError::BuildHookFailed(_) => ErrorCode::E019,
             SkillcError::SectionNotFound(_) => ErrorCode::E020,
             SkillcError::SectionNotFoundWithSuggestions(_, _) => ErrorCode::E020,
             SkillcError::FileNotFound(_) => ErrorCode::E021,
@@ -213,6 +280,9 @@ impl SkillcError {
             SkillcError::SyncDestNotWritable(_, _) => ErrorCode::E041,
             SkillcError::SyncSourceNotReadable(_, _) => ErrorCode::E042,
             SkillcError::SkillAlreadyExists(_) => ErrorCode::E050,
+            SkillcError::InvalidArchive(_) => ErrorCode::E060,
+            SkillcError::DoctorFoundProblems(_) => ErrorCode::E070,
+            SkillcError::NoRetainedBuild(_, _) => ErrorCode::E080,
             SkillcError::InvalidOption(_) => ErrorCode::E100,
             // E999: Internal errors
             SkillcError::Io(_) => ErrorCode::E999,
@@ -238,6 +308,7 @@ impl SkillcError {
                 )
             }
             SkillcError::EmptyQuery => "empty query".to_string(),
+            SkillcError::InvalidQuery(s) => format!("invalid search query: {}", s),
             SkillcError::NotAValidSkill(s) => {
                 format!("not a valid skill: '{}' (missing SKILL.md)", s)
             }
@@ -248,6 +319,21 @@ impl SkillcError {
                 format!("invalid frontmatter in SKILL.md: {}", s)
             }
             SkillcError::PathEscapesRoot(s) => format!("path escapes skill root: '{}'", s),
+            SkillcError::BuildOutdated(s) => {
+                format!("skill '{}' is out of date; run 'skc build {}'", s, s)
+            }
+            SkillcError::InvalidStubTemplate(s) => format!("invalid stub template: {}", s),
+            SkillcError::StubTooLong(n) => {
+                format!("compiled stub exceeds 100 lines ({} lines)", n)
+            }
+            SkillcError::StubOverBudget(tokens, budget) => {
+                format!("stub is ~{} tokens, over budget of {}", tokens, budget)
+            }
+            SkillcError::VerifyDrift(s) => {
+                format!("skill '{}' has drifted from its last build", s)
+            }
+            SkillcError::InvalidDirective(s) => format!("invalid directive: {}", s),
+            SkillcError::BuildHookFailed(s) => format!("build hook failed: {}", s),
             SkillcError::SectionNotFound(s) => format!("section not found: '{}'", s),
             SkillcError::SectionNotFoundWithSuggestions(s, suggestions) => {
                 format!("section not found: '{}'{}", s, suggestions)
@@ -265,6 +351,19 @@ impl SkillcError {
                 format!("sync source not readable: '{}' ({})", path, msg)
             }
             SkillcError::SkillAlreadyExists(s) => format!("skill '{}' already exists", s),
+            SkillcError::InvalidArchive(s) => format!("invalid skill archive: {}", s),
+            SkillcError::DoctorFoundProblems(n) => {
+                format!("doctor found {} problem(s); see hints above", n)
+            }
+            SkillcError::NoRetainedBuild(s, None) => {
+                format!("no earlier build of '{}' is retained", s)
+            }
+            SkillcError::NoRetainedBuild(s, Some(n)) => {
+                format!(
+                    "build {} of '{}' is not retained; run 'skc rollback {} --list'",
+                    n, s, s
+                )
+            }
             SkillcError::InvalidOption(s) => format!("invalid option: '{}'", s),
             // Internal errors: pass through the underlying message
             SkillcError::Io(e) => e.to_string(),
@@ -343,6 +442,7 @@ mod tests {
         assert_eq!(WarningCode::W001.to_string(), "W001");
         assert_eq!(WarningCode::W002.to_string(), "W002");
         assert_eq!(WarningCode::W003.to_string(), "W003");
+        assert_eq!(WarningCode::W020.to_string(), "W020");
     }
 
     #[test]
@@ -373,6 +473,47 @@ mod tests {
         assert!(s.contains("error[E021]"));
         assert!(s.contains("file not found: 'README.md'"));
 
+        let err = SkillcError::BuildOutdated("my-skill".to_string());
+        let s = err.to_string();
+        assert!(s.contains("error[E013]"));
+        assert!(s.contains("skill 'my-skill' is out of date; run 'skc build my-skill'"));
+
+        let err = SkillcError::VerifyDrift("my-skill".to_string());
+        let s = err.to_string();
+        assert!(s.contains("error[E017]"));
+        assert!(s.contains("skill 'my-skill' has drifted from its last build"));
+
+        let err = SkillcError::InvalidDirective("'shared.md' not found".to_string());
+        let s = err.to_string();
+        assert!(s.contains("error[E018]"));
+        assert!(s.contains("invalid directive: 'shared.md' not found"));
+
+        let err = SkillcError::InvalidArchive("checksum mismatch for 'SKILL.md'".to_string());
+        let s = err.to_string();
+        assert!(s.contains("error[E060]"));
+        assert!(s.contains("invalid skill archive: checksum mismatch for 'SKILL.md'"));
+
+        let err = SkillcError::DoctorFoundProblems(2);
+        let s = err.to_string();
+        assert!(s.contains("error[E070]"));
+        assert!(s.contains("doctor found 2 problem(s); see hints above"));
+
+        let err = SkillcError::NoRetainedBuild("my-skill".to_string(), None);
+        let s = err.to_string();
+        assert!(s.contains("error[E080]"));
+        assert!(s.contains("no earlier build of 'my-skill' is retained"));
+
+        let err = SkillcError::NoRetainedBuild("my-skill".to_string(), Some(7));
+        assert!(
+            err.to_string()
+                .contains("build 7 of 'my-skill' is not retained")
+        );
+
+        let err = SkillcError::BuildHookFailed("pre hook 'make' exited with status 2".to_string());
+        let s = err.to_string();
+        assert!(s.contains("error[E019]"));
+        assert!(s.contains("build hook failed: pre hook 'make' exited with status 2"));
+
         let err = SkillcError::NoLocalLogs;
         let s = err.to_string();
         assert!(s.contains("error[E040]"));
@@ -394,6 +535,10 @@ mod tests {
             ErrorCode::E003
         );
         assert_eq!(SkillcError::EmptyQuery.code(), ErrorCode::E004);
+        assert_eq!(
+            SkillcError::InvalidQuery("x".to_string()).code(),
+            ErrorCode::E005
+        );
         assert_eq!(
             SkillcError::NotAValidSkill("x".into()).code(),
             ErrorCode::E010
@@ -406,6 +551,28 @@ mod tests {
             SkillcError::PathEscapesRoot("x".into()).code(),
             ErrorCode::E012
         );
+        assert_eq!(
+            SkillcError::BuildOutdated("x".into()).code(),
+            ErrorCode::E013
+        );
+        assert_eq!(
+            SkillcError::InvalidStubTemplate("x".into()).code(),
+            ErrorCode::E014
+        );
+        assert_eq!(SkillcError::StubTooLong(101).code(), ErrorCode::E015);
+        assert_eq!(
+            SkillcError::StubOverBudget(900, 800).code(),
+            ErrorCode::E016
+        );
+        assert_eq!(SkillcError::VerifyDrift("x".into()).code(), ErrorCode::E017);
+        assert_eq!(
+            SkillcError::InvalidDirective("x".into()).code(),
+            ErrorCode::E018
+        );
+        assert_eq!(
+            SkillcError::BuildHookFailed("x".into()).code(),
+            ErrorCode::E019
+        );
         assert_eq!(
             SkillcError::SectionNotFound("x".into()).code(),
             ErrorCode::E020
@@ -431,6 +598,15 @@ mod tests {
             SkillcError::SkillAlreadyExists("x".into()).code(),
             ErrorCode::E050
         );
+        assert_eq!(
+            SkillcError::InvalidArchive("x".into()).code(),
+            ErrorCode::E060
+        );
+        assert_eq!(SkillcError::DoctorFoundProblems(1).code(), ErrorCode::E070);
+        assert_eq!(
+            SkillcError::NoRetainedBuild("x".into(), None).code(),
+            ErrorCode::E080
+        );
         assert_eq!(
             SkillcError::InvalidOption("x".into()).code(),
             ErrorCode::E100
@@ -455,6 +631,11 @@ mod tests {
         let s = warn.to_string();
         assert!(s.contains("warning[W003]"));
         assert!(s.contains("stale local logs for 'rust'; run 'skc sync' to upload"));
+
+        let warn = SkillcWarning::StubOverBudget(900, 800);
+        let s = warn.to_string();
+        assert!(s.contains("warning[W020]"));
+        assert!(s.contains("stub is ~900 tokens, over budget of 800"));
     }
 
     #[test]
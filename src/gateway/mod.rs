@@ -13,6 +13,7 @@ pub use show::show;
 pub use sources::sources;
 
 use crate::error::{Result, SkillcError};
+use crate::skillignore::SkillIgnore;
 use crate::{Heading, markdown};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -27,8 +28,10 @@ pub(crate) fn extract_headings(source_dir: &Path) -> Result<Vec<Heading>> {
 
     // Collect all .md files
     let mut md_files: Vec<PathBuf> = Vec::new();
+    let ignore = SkillIgnore::load(source_dir);
     for entry in WalkDir::new(source_dir)
         .into_iter()
+        .filter_entry(|e| ignore.allows(e))
         .filter_map(|e| e.ok())
         .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
     {
@@ -1,6 +1,7 @@
 //! Open command per [[RFC-0002:C-OPEN]]
 
 use crate::config::get_cwd;
+use crate::csv::CsvTable;
 use crate::error::{Result, SkillcError};
 use crate::logging::{LogEntry, get_run_id, init_log_db, log_access_with_fallback};
 use crate::resolver::resolve_skill;
@@ -13,6 +14,8 @@ use std::time::Instant;
 /// Returns the contents of the specified file.
 ///
 /// The `max_lines` parameter truncates output to the first n lines if specified.
+/// A `<file>.csv#<row>` path returns a single CSV row as `column: value`
+/// lines, located by its first column or 1-based row number.
 pub fn open(
     skill: &str,
     path: &str,
@@ -61,9 +64,9 @@ fn do_open(
     // Validate path doesn't escape skill root
     if path.contains("..") {
         // Check if it actually escapes after canonicalization
-        let full_path = resolved.source_dir.join(path);
+        let full_path = resolved.content_dir.join(path);
         if let Ok(canonical) = full_path.canonicalize() {
-            if !canonical.starts_with(&resolved.source_dir) {
+            if !canonical.starts_with(&resolved.content_dir) {
                 return Err(SkillcError::PathEscapesRoot(path.to_string()));
             }
         } else {
@@ -71,12 +74,24 @@ fn do_open(
         }
     }
 
-    let file_path = resolved.source_dir.join(path);
+    let file_path = resolved.content_dir.join(path);
+
+    // A `<file>.csv#<row>` locator returns one row, as indexed for search
+    if let Some((file, locator)) = path.rsplit_once('#')
+        && file.ends_with(".csv")
+        && !file_path.exists()
+    {
+        let table = CsvTable::parse(&do_open(resolved, file, None, _format)?);
+        let row = table
+            .find(locator)
+            .ok_or_else(|| SkillcError::SectionNotFound(locator.to_string()))?;
+        return Ok(format!("{}\n", row.render(&table.header)));
+    }
 
     // Validate path after canonicalization
     if file_path.exists() {
         let canonical = file_path.canonicalize()?;
-        if !canonical.starts_with(&resolved.source_dir) {
+        if !canonical.starts_with(&resolved.content_dir) {
             return Err(SkillcError::PathEscapesRoot(path.to_string()));
         }
     }
@@ -58,8 +58,7 @@ fn try_outline_from_index(
     resolved: &ResolvedSkill,
     max_level: Option<usize>,
 ) -> Option<Vec<Heading>> {
-    let conn =
-        index::open_index(&resolved.runtime_dir, &resolved.source_dir, &resolved.name).ok()?;
+    let conn = index::open_index(&resolved.index_dir, &resolved.source_dir, &resolved.name).ok()?;
     let entries = index::get_all_headings(&conn).ok()?;
 
     let mut headings: Vec<Heading> = entries
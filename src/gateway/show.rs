@@ -114,7 +114,7 @@ fn do_show(
     verbose!("show: normalized query=\"{}\"", query);
 
     // Try index-based lookup first
-    match index::open_index(&resolved.runtime_dir, &resolved.source_dir, &resolved.name) {
+    match index::open_index(&resolved.index_dir, &resolved.source_dir, &resolved.name) {
         Ok(conn) => do_show_with_index(&conn, resolved, &query, section, file, max_lines),
         Err(_) => {
             // Fallback to runtime parsing for unbuilt skills
@@ -151,7 +151,7 @@ fn do_show_with_index(
     }
 
     let matched = &matches[0];
-    let file_path = resolved.source_dir.join(&matched.file);
+    let file_path = resolved.content_dir.join(&matched.file);
     let content = fs::read_to_string(&file_path)?;
     let lines: Vec<&str> = content.lines().collect();
 
@@ -4,6 +4,7 @@ use crate::config::get_cwd;
 use crate::error::{Result, SkillcError};
 use crate::logging::{LogEntry, get_run_id, init_log_db, log_access_with_fallback};
 use crate::resolver::{ResolvedSkill, resolve_skill};
+use crate::skillignore::SkillIgnore;
 use crate::{OutputFormat, verbose};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -115,16 +116,27 @@ fn do_sources(
         .transpose()
         .map_err(|e| SkillcError::InvalidPath(format!("invalid glob pattern: {}", e)))?;
 
+    // Paths matched by .skillcignore are not part of the skill
+    let ignore = SkillIgnore::load(&resolved.source_dir);
+
     match format {
         OutputFormat::Json => {
             // JSON format: flat list of entries
             let mut entries = Vec::new();
             let mut count = 0;
 
+            // Same order as the tree: directories first, then by name
             for entry in WalkDir::new(&root)
                 .min_depth(1)
                 .max_depth(max_depth.unwrap_or(usize::MAX))
+                .sort_by(|a, b| {
+                    b.file_type()
+                        .is_dir()
+                        .cmp(&a.file_type().is_dir())
+                        .then_with(|| a.file_name().cmp(b.file_name()))
+                })
                 .into_iter()
+                .filter_entry(|e| ignore.allows(e))
                 .filter_map(|e| e.ok())
             {
                 if count >= limit {
@@ -171,8 +183,13 @@ fn do_sources(
         }
         OutputFormat::Text => {
             // Text format: tree display
-            let entries =
-                collect_tree_entries(&root, &resolved.source_dir, max_depth, &glob_pattern)?;
+            let entries = collect_tree_entries(
+                &root,
+                &resolved.source_dir,
+                max_depth,
+                &glob_pattern,
+                &ignore,
+            )?;
             Ok(format_tree(&resolved.name, &entries, limit))
         }
     }
@@ -199,9 +216,18 @@ fn collect_tree_entries(
     skill_root: &Path,
     max_depth: Option<usize>,
     pattern: &Option<glob::Pattern>,
+    ignore: &SkillIgnore,
 ) -> Result<Vec<TreeEntry>> {
     let mut entries = Vec::new();
-    collect_entries_recursive(root, skill_root, 0, max_depth, pattern, &mut entries)?;
+    collect_entries_recursive(
+        root,
+        skill_root,
+        0,
+        max_depth,
+        pattern,
+        ignore,
+        &mut entries,
+    )?;
 
     // Mark last entries at each depth level
     mark_last_entries(&mut entries);
@@ -215,14 +241,17 @@ fn collect_entries_recursive(
     current_depth: usize,
     max_depth: Option<usize>,
     pattern: &Option<glob::Pattern>,
+    ignore: &SkillIgnore,
     entries: &mut Vec<TreeEntry>,
 ) -> Result<()> {
     // Read directory entries
     let mut dir_entries: Vec<_> = fs::read_dir(dir)?
         .filter_map(|e| e.ok())
         .filter(|e| {
-            // Skip hidden files/directories
+            // Skip hidden and ignored files/directories
+            let is_dir = e.file_type().map(|t| t.is_dir()).unwrap_or(false);
             !e.file_name().to_string_lossy().starts_with('.')
+                && !ignore.is_ignored(&e.path(), is_dir)
         })
         .collect();
 
@@ -272,11 +301,12 @@ fn collect_entries_recursive(
                     current_depth + 1,
                     max_depth,
                     pattern,
+                    ignore,
                     entries,
                 )?;
             } else {
                 // Count files in unexpanded directory
-                let count = count_files_in_dir(&path, pattern)?;
+                let count = count_files_in_dir(&path, pattern, ignore)?;
                 entries.push(TreeEntry {
                     path: relative,
                     depth: current_depth,
@@ -300,9 +330,17 @@ fn collect_entries_recursive(
 }
 
 /// Count files in a directory (recursively), respecting glob pattern
-fn count_files_in_dir(dir: &Path, pattern: &Option<glob::Pattern>) -> Result<usize> {
+fn count_files_in_dir(
+    dir: &Path,
+    pattern: &Option<glob::Pattern>,
+    ignore: &SkillIgnore,
+) -> Result<usize> {
     let mut count = 0;
-    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| ignore.allows(e))
+        .filter_map(|e| e.ok())
+    {
         if entry.file_type().is_file() {
             // Skip hidden files
             if entry.file_name().to_string_lossy().starts_with('.') {
@@ -540,7 +578,8 @@ mod tests {
     fn test_count_files_in_dir() {
         let temp = setup_test_skill();
 
-        let count = count_files_in_dir(temp.path(), &None).expect("failed to count files");
+        let ignore = SkillIgnore::none(temp.path());
+        let count = count_files_in_dir(temp.path(), &None, &ignore).expect("failed to count files");
         assert!(count >= 2); // At least SKILL.md and docs/guide.md
     }
 
@@ -549,7 +588,21 @@ mod tests {
         let temp = setup_test_skill();
         let pattern = glob::Pattern::new("*.md").expect("invalid pattern");
 
-        let count = count_files_in_dir(temp.path(), &Some(pattern)).expect("failed to count files");
+        let ignore = SkillIgnore::none(temp.path());
+        let count = count_files_in_dir(temp.path(), &Some(pattern), &ignore)
+            .expect("failed to count files");
         assert!(count >= 2); // .md files only
     }
+
+    #[test]
+    fn test_count_files_respects_skillcignore() {
+        let temp = setup_test_skill();
+        fs::write(temp.path().join(".skillcignore"), "docs/\n").expect("write ignore file");
+
+        let ignore = SkillIgnore::load(temp.path());
+        let with_ignore = count_files_in_dir(temp.path(), &None, &ignore).expect("count");
+        let without =
+            count_files_in_dir(temp.path(), &None, &SkillIgnore::none(temp.path())).expect("count");
+        assert!(with_ignore < without);
+    }
 }
@@ -0,0 +1,515 @@
+//! Staged runtime builds and build history (`skc rollback`).
+//!
+//! A build is written into a staging directory beside the runtime and then
+//! swapped in atomically (see [`install`]), so agents never read a
+//! half-written stub or index.
+//! The runtime it replaces is retained as `.skillc-builds/<skill>/<n>/`
+//! (see [`crate::util::runtime_builds_dir`]), keeping the newest
+//! `[build] keep_builds` of them. `skc rollback` swaps a retained build back
+//! in and repoints the skill's deployments at it.
+
+use crate::OutputFormat;
+use crate::compiler::read_manifest;
+use crate::config::{ensure_dir, get_keep_builds};
+use crate::deploy::{self, DeployMethod, Deployment, deploy_source};
+use crate::emit::native_output;
+use crate::error::{Result, SkillcError};
+use crate::registry::Registry;
+use crate::resolver::resolve_skill;
+use crate::util::runtime_builds_dir;
+use crate::verbose;
+use crate::verify::deployment_candidates;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for [`rollback`].
+#[derive(Debug, Default)]
+pub struct RollbackOptions {
+    /// Retained build to restore; defaults to the most recent one
+    pub to: Option<u64>,
+}
+
+/// A runtime build, live or retained.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    /// Retention number; absent for the live runtime
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    pub path: PathBuf,
+    pub built_at: String,
+    pub source_hash: String,
+}
+
+impl BuildInfo {
+    fn read(id: Option<u64>, path: PathBuf) -> Self {
+        let manifest = read_manifest(&path);
+        Self {
+            id,
+            built_at: manifest
+                .as_ref()
+                .map(|m| m.built_at.clone())
+                .unwrap_or_default(),
+            source_hash: manifest.map(|m| m.source_hash).unwrap_or_default(),
+            path,
+        }
+    }
+}
+
+/// The live runtime of a skill and the builds retained for rollback.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildHistory {
+    pub skill: String,
+    /// Absent if the skill has not been built
+    pub current: Option<BuildInfo>,
+    /// Newest first
+    pub retained: Vec<BuildInfo>,
+}
+
+/// Result of [`rollback`].
+#[derive(Debug, Clone, Serialize)]
+pub struct RollbackResult {
+    pub skill: String,
+    /// The build now live, with the number it was retained under
+    pub restored: BuildInfo,
+    /// Number the replaced runtime is now retained under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retired: Option<u64>,
+    pub deployments: Vec<Deployment>,
+}
+
+/// Entries of `.skillc-meta` that are runtime state rather than build output.
+///
+/// They move to whichever build is swapped in: the access log, with any
+/// SQLite sidecar files. Native renders are build output and stay with
+/// their build.
+fn is_carried_state(name: &str) -> bool {
+    name.starts_with("logs.db")
+}
+
+/// Create an empty staging directory for a new build of `runtime_dir`.
+pub(crate) fn stage(runtime_dir: &Path) -> Result<PathBuf> {
+    let dir = runtime_builds_dir(runtime_dir).join(format!(
+        ".staging-{}-{:08x}",
+        std::process::id(),
+        rand::random::<u32>()
+    ));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Swap a complete build into place at `runtime_dir`.
+///
+/// On Linux the swap is a single `renameat2(RENAME_EXCHANGE)`, so the
+/// runtime path always exists and agent links never dangle. Elsewhere (or on
+/// filesystems without exchange support) it falls back to two renames, with
+/// a brief window in which the runtime path is missing.
+///
+/// The access log moves into the new build under the exclusive
+/// [`lock_runtime_state`] lock, so no entry is written to the build being
+/// retired. The runtime it replaces is retained under the next build number,
+/// then all but the newest `keep` retained builds are deleted. Returns the
+/// number the replaced runtime is retained under, if it was kept.
+pub(crate) fn install(build: &Path, runtime_dir: &Path, keep: usize) -> Result<Option<u64>> {
+    if runtime_dir.symlink_metadata().is_err() {
+        fs::rename(build, runtime_dir)?;
+        return Ok(None);
+    }
+
+    let builds_dir = runtime_builds_dir(runtime_dir);
+    ensure_dir(&builds_dir)?;
+    let id = retained_ids(&builds_dir).into_iter().max().unwrap_or(0) + 1;
+    let retired = builds_dir.join(id.to_string());
+
+    {
+        let _lock = lock_runtime_state(runtime_dir, true)?;
+        carry_state(runtime_dir, build)?;
+        match exchange(build, runtime_dir) {
+            // `build` now holds the replaced runtime; the new one is live
+            // even if retaining the old one fails
+            Ok(true) => {
+                if let Err(e) = fs::rename(build, &retired) {
+                    eprintln!("warning: failed to retain previous build: {}", e);
+                    let _ = fs::remove_dir_all(build);
+                    return Ok(None);
+                }
+            }
+            Ok(false) => {
+                verbose!("build: atomic exchange unavailable, swapping by rename");
+                if let Err(e) = fs::rename(runtime_dir, &retired) {
+                    let _ = carry_state(build, runtime_dir);
+                    return Err(e.into());
+                }
+                if let Err(e) = fs::rename(build, runtime_dir) {
+                    let _ = fs::rename(&retired, runtime_dir);
+                    let _ = carry_state(build, runtime_dir);
+                    return Err(e.into());
+                }
+            }
+            Err(e) => {
+                let _ = carry_state(build, runtime_dir);
+                return Err(e.into());
+            }
+        }
+    }
+    verbose!("build: retained previous runtime as build {}", id);
+
+    prune(&builds_dir, keep)?;
+    Ok((keep > 0).then_some(id))
+}
+
+/// Atomically exchange two existing paths.
+///
+/// Returns `Ok(false)` when the platform, kernel, or filesystem cannot
+/// exchange, so the caller can fall back to plain renames.
+#[cfg(target_os = "linux")]
+fn exchange(a: &Path, b: &Path) -> std::io::Result<bool> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let a = CString::new(a.as_os_str().as_bytes())?;
+    let b = CString::new(b.as_os_str().as_bytes())?;
+    // SAFETY: both paths are NUL-terminated and outlive the call.
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_renameat2,
+            libc::AT_FDCWD,
+            a.as_ptr(),
+            libc::AT_FDCWD,
+            b.as_ptr(),
+            libc::RENAME_EXCHANGE,
+        )
+    };
+    if rc == 0 {
+        return Ok(true);
+    }
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ENOSYS | libc::EINVAL | libc::EOPNOTSUPP) => Ok(false),
+        _ => Err(err),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn exchange(_a: &Path, _b: &Path) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+/// Lock serializing runtime swaps against access-log writes.
+///
+/// [`install`] holds it exclusively while it moves `logs.db` into the new
+/// build and swaps the build in; loggers hold it shared while they open or
+/// write the log. The lock file lives in the runtime's builds directory, so
+/// it survives the swap.
+pub(crate) fn lock_runtime_state(runtime_dir: &Path, exclusive: bool) -> Result<fs::File> {
+    let builds_dir = runtime_builds_dir(runtime_dir);
+    ensure_dir(&builds_dir)?;
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(builds_dir.join(".lock"))?;
+    if exclusive {
+        lock.lock()?;
+    } else {
+        lock.lock_shared()?;
+    }
+    Ok(lock)
+}
+
+/// Move runtime state from one build's `.skillc-meta` to another's.
+fn carry_state(from: &Path, to: &Path) -> Result<()> {
+    let Ok(entries) = fs::read_dir(from.join(".skillc-meta")) else {
+        return Ok(());
+    };
+    let meta_dir = to.join(".skillc-meta");
+    ensure_dir(&meta_dir)?;
+    for entry in entries {
+        let entry = entry?;
+        if is_carried_state(&entry.file_name().to_string_lossy()) {
+            fs::rename(entry.path(), meta_dir.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Delete all but the newest `keep` retained builds.
+fn prune(builds_dir: &Path, keep: usize) -> Result<()> {
+    let mut ids = retained_ids(builds_dir);
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    for id in ids.into_iter().skip(keep) {
+        fs::remove_dir_all(builds_dir.join(id.to_string()))?;
+        verbose!("build: pruned retained build {}", id);
+    }
+    Ok(())
+}
+
+/// Numbers of the retained builds in a builds directory, in no order.
+fn retained_ids(builds_dir: &Path) -> Vec<u64> {
+    fs::read_dir(builds_dir)
+        .map(|rd| {
+            rd.flatten()
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Retained builds of a runtime, newest first.
+pub fn retained_builds(runtime_dir: &Path) -> Vec<BuildInfo> {
+    let builds_dir = runtime_builds_dir(runtime_dir);
+    let mut ids = retained_ids(&builds_dir);
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids.into_iter()
+        .map(|id| BuildInfo::read(Some(id), builds_dir.join(id.to_string())))
+        .collect()
+}
+
+/// List a skill's live runtime and its retained builds.
+pub fn history(skill: &str) -> Result<BuildHistory> {
+    let resolved = resolve_skill(skill)?;
+    let runtime_dir = resolved.runtime_dir;
+    Ok(BuildHistory {
+        skill: resolved.name,
+        current: read_manifest(&runtime_dir)
+            .is_some()
+            .then(|| BuildInfo::read(None, runtime_dir.clone())),
+        retained: retained_builds(&runtime_dir),
+    })
+}
+
+/// Swap a retained build back in and repoint the skill's deployments.
+///
+/// The live runtime is retained in turn, so a rollback can itself be undone
+/// with `--to`. Links into the runtime follow the swap; they are re-created
+/// only where the restored build has a different target variant. Copies and
+/// native instruction files are refreshed from the restored build.
+pub fn rollback(skill: &str, options: &RollbackOptions) -> Result<RollbackResult> {
+    let resolved = resolve_skill(skill)?;
+    let runtime_dir = resolved.runtime_dir;
+
+    let retained = retained_builds(&runtime_dir);
+    let chosen = match options.to {
+        Some(id) => retained.into_iter().find(|b| b.id == Some(id)),
+        None => retained.into_iter().next(),
+    }
+    .ok_or_else(|| SkillcError::NoRetainedBuild(resolved.name.clone(), options.to))?;
+    verbose!(
+        "rollback: restoring build {:?} from {}",
+        chosen.id,
+        chosen.path.display()
+    );
+
+    let retired = install(&chosen.path, &runtime_dir, get_keep_builds())?;
+
+    let source_canonical = resolved.source_dir.canonicalize().ok();
+    let mut targets = Vec::new();
+    let mut deployments = Vec::new();
+    let registry = Registry::load().unwrap_or_default();
+    for (target, dest) in deployment_candidates(&runtime_dir, &resolved.name, &[]) {
+        if dest.symlink_metadata().is_err() || dest.canonicalize().ok() == source_canonical {
+            continue;
+        }
+        let Some(method) = deploy::owned_deployment(&dest, &runtime_dir, &resolved.name, &registry)
+        else {
+            continue;
+        };
+
+        if method == DeployMethod::Native {
+            let Some(source) = native_output(&runtime_dir, &target, &resolved.name)
+                .filter(|source| source.is_file())
+            else {
+                verbose!("rollback: restored build has no native file for {}", target);
+                continue;
+            };
+            let unchanged = fs::read(&source).ok() == fs::read(&dest).ok();
+            if !unchanged {
+                deploy::place_file(&source, &dest)?;
+            }
+            verbose!("rollback: restored {} ({})", dest.display(), method);
+            deployments.push(Deployment {
+                target: target.to_string(),
+                path: dest,
+                method,
+                unchanged,
+                refreshed: false,
+            });
+            targets.push(target);
+            continue;
+        }
+
+        let source = deploy_source(&runtime_dir, &target);
+        let unchanged =
+            method != DeployMethod::Copy && dest.canonicalize().ok() == source.canonicalize().ok();
+        let method = if unchanged {
+            method
+        } else {
+            deploy::place_entry(&source, &dest, method == DeployMethod::Copy)?
+        };
+        verbose!("rollback: repointed {} ({})", dest.display(), method);
+        deployments.push(Deployment {
+            target: target.to_string(),
+            path: dest,
+            method,
+            unchanged,
+            refreshed: false,
+        });
+        targets.push(target);
+    }
+    deploy::record_deployments(&runtime_dir, &targets, &resolved.name, &deployments);
+
+    Ok(RollbackResult {
+        skill: resolved.name,
+        restored: BuildInfo {
+            path: runtime_dir,
+            ..chosen
+        },
+        retired,
+        deployments,
+    })
+}
+
+/// Format a build history.
+pub fn format_history(history: &BuildHistory, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(history)?);
+    }
+
+    let line = |label: String, build: &BuildInfo| {
+        let hash = build.source_hash.get(..12).unwrap_or(&build.source_hash);
+        format!("  {:<9} {}  {}", label, build.built_at, hash)
+    };
+    let mut lines = vec![format!("Builds of {}", history.skill)];
+    match &history.current {
+        Some(build) => lines.push(line("current".to_string(), build)),
+        None => lines.push("  (not built)".to_string()),
+    }
+    for build in &history.retained {
+        lines.push(line(build.id.unwrap_or_default().to_string(), build));
+    }
+    if history.retained.is_empty() {
+        lines.push("No earlier builds retained".to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Format a rollback result.
+pub fn format_rollback(result: &RollbackResult, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(result)?);
+    }
+
+    let mut lines = vec![format!(
+        "Rolled back {} to build {} (built {})",
+        result.skill,
+        result.restored.id.unwrap_or_default(),
+        result.restored.built_at
+    )];
+    if let Some(id) = result.retired {
+        lines.push(format!("Retained the replaced build as {}", id));
+    }
+    for deployment in &result.deployments {
+        let verb = if deployment.unchanged {
+            "Kept"
+        } else {
+            "Repointed"
+        };
+        lines.push(format!(
+            "{} {} ({})",
+            verb,
+            deployment.path.display(),
+            deployment.method
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_build(dir: &Path, stub: &str) {
+        fs::create_dir_all(dir.join(".skillc-meta")).expect("create build");
+        fs::write(dir.join("SKILL.md"), stub).expect("write stub");
+    }
+
+    #[test]
+    fn test_install_retains_and_prunes() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path().join("runtime").join("my-skill");
+
+        for n in 1..=4 {
+            let staged = stage(&runtime).expect("stage");
+            write_build(&staged, &format!("build {}", n));
+            install(&staged, &runtime, 2).expect("install");
+        }
+
+        assert_eq!(
+            fs::read_to_string(runtime.join("SKILL.md")).expect("read stub"),
+            "build 4"
+        );
+        let retained: Vec<Option<u64>> = retained_builds(&runtime)
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        assert_eq!(retained, vec![Some(3), Some(2)]);
+        assert_eq!(
+            fs::read_to_string(runtime_builds_dir(&runtime).join("3").join("SKILL.md"))
+                .expect("read retained stub"),
+            "build 3"
+        );
+    }
+
+    #[test]
+    fn test_install_carries_runtime_state() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path().join("runtime").join("my-skill");
+        write_build(&runtime, "old");
+        let meta = runtime.join(".skillc-meta");
+        fs::write(meta.join("logs.db"), "log").expect("write log");
+        fs::create_dir_all(meta.join("native")).expect("create native dir");
+        fs::write(meta.join("search-0123.db"), "index").expect("write index");
+
+        let staged = stage(&runtime).expect("stage");
+        write_build(&staged, "new");
+        assert_eq!(install(&staged, &runtime, 1).expect("install"), Some(1));
+
+        assert!(meta.join("logs.db").exists());
+        assert!(!meta.join("native").exists());
+        assert!(!meta.join("search-0123.db").exists());
+        let retired = runtime_builds_dir(&runtime).join("1").join(".skillc-meta");
+        assert!(retired.join("native").is_dir());
+        assert!(retired.join("search-0123.db").exists());
+        assert!(!retired.join("logs.db").exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_exchange_swaps_directories() {
+        let temp = TempDir::new().expect("create temp dir");
+        let a = temp.path().join("a");
+        let b = temp.path().join("b");
+        write_build(&a, "a");
+        write_build(&b, "b");
+
+        if exchange(&a, &b).expect("exchange") {
+            assert_eq!(fs::read_to_string(a.join("SKILL.md")).expect("read a"), "b");
+            assert_eq!(fs::read_to_string(b.join("SKILL.md")).expect("read b"), "a");
+        }
+    }
+
+    #[test]
+    fn test_runtime_state_lock_excludes_loggers() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path().join("runtime").join("my-skill");
+
+        let swap = lock_runtime_state(&runtime, true).expect("exclusive lock");
+        let lock_path = runtime_builds_dir(&runtime).join(".lock");
+        let logger = fs::File::open(&lock_path).expect("open lock");
+        assert!(logger.try_lock_shared().is_err(), "swap blocks loggers");
+        drop(swap);
+        assert!(logger.try_lock_shared().is_ok(), "released after the swap");
+    }
+}
@@ -0,0 +1,164 @@
+//! Build hooks (`[build] pre` / `post` in config) per [[RFC-0009:C-FILES]].
+//!
+//! Pre-hooks run before the source is read, so they can generate content
+//! (e.g. API references from an OpenAPI file). Post-hooks run on the staged
+//! build before it is swapped in, e.g. to validate scripts, so a failing one
+//! rejects the build. An up-to-date build runs neither. Each command runs
+//! through the platform shell in the source directory, with its output
+//! captured so it never interleaves with command output (or the MCP
+//! transport).
+
+use crate::error::{Result, SkillcError};
+use crate::verbose;
+use std::fmt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Environment variable holding the skill name.
+pub const ENV_SKILL: &str = "SKILLC_SKILL";
+
+/// Environment variable holding the absolute source directory.
+pub const ENV_SOURCE_DIR: &str = "SKILLC_SOURCE_DIR";
+
+/// Environment variable holding the absolute runtime directory (for post-hooks,
+/// the staged build that replaces it).
+pub const ENV_RUNTIME_DIR: &str = "SKILLC_RUNTIME_DIR";
+
+/// When a hook runs relative to the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookPhase {
+    Pre,
+    Post,
+}
+
+impl fmt::Display for HookPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookPhase::Pre => write!(f, "pre"),
+            HookPhase::Post => write!(f, "post"),
+        }
+    }
+}
+
+/// Run hook commands in order, stopping at the first failure (E019).
+pub fn run_hooks(
+    phase: HookPhase,
+    commands: &[String],
+    skill: &str,
+    source_dir: &Path,
+    runtime_dir: &Path,
+) -> Result<()> {
+    let source_dir = std::path::absolute(source_dir)?;
+    let runtime_dir = std::path::absolute(runtime_dir)?;
+    for command in commands {
+        verbose!("build: running {} hook: {}", phase, command);
+        let output = shell(command)
+            .current_dir(&source_dir)
+            .env(ENV_SKILL, skill)
+            .env(ENV_SOURCE_DIR, &source_dir)
+            .env(ENV_RUNTIME_DIR, &runtime_dir)
+            .output()
+            .map_err(|e| {
+                SkillcError::BuildHookFailed(format!(
+                    "{} hook '{}' could not start: {}",
+                    phase, command, e
+                ))
+            })?;
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            verbose!("  {}", line);
+        }
+        if !output.status.success() {
+            return Err(SkillcError::BuildHookFailed(failure_message(
+                phase, command, &output,
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Platform shell invocation for a hook command line.
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// Describe a failed hook, including the last line it wrote to stderr.
+fn failure_message(phase: HookPhase, command: &str, output: &Output) -> String {
+    let status = match output.status.code() {
+        Some(code) => format!("exited with status {}", code),
+        None => "was terminated by a signal".to_string(),
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+        Some(line) => format!("{} hook '{}' {}: {}", phase, command, status, line.trim()),
+        None => format!("{} hook '{}' {}", phase, command, status),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hooks_receive_environment() {
+        let temp = TempDir::new().expect("create temp dir");
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).expect("create source");
+
+        let commands = vec![
+            "echo \"$SKILLC_SKILL $SKILLC_RUNTIME_DIR\" > generated.md".to_string(),
+            "echo \"$SKILLC_SOURCE_DIR\" > source.txt".to_string(),
+        ];
+        run_hooks(
+            HookPhase::Pre,
+            &commands,
+            "my-skill",
+            &source,
+            Path::new("/rt"),
+        )
+        .expect("hooks succeed");
+        assert_eq!(
+            fs::read_to_string(source.join("generated.md")).expect("read output"),
+            "my-skill /rt\n"
+        );
+        assert_eq!(
+            fs::read_to_string(source.join("source.txt")).expect("read output"),
+            format!("{}\n", source.display())
+        );
+    }
+
+    #[test]
+    fn test_failing_hook_stops_the_chain() {
+        let temp = TempDir::new().expect("create temp dir");
+        let commands = vec![
+            "echo boom >&2; exit 3".to_string(),
+            "touch never".to_string(),
+        ];
+        let err = run_hooks(
+            HookPhase::Pre,
+            &commands,
+            "my-skill",
+            temp.path(),
+            temp.path(),
+        )
+        .expect_err("hook fails");
+        let msg = err.to_string();
+        assert!(msg.contains("error[E019]"), "got: {}", msg);
+        assert!(
+            msg.contains("pre hook 'echo boom >&2; exit 3' exited with status 3: boom"),
+            "got: {}",
+            msg
+        );
+        assert!(!temp.path().join("never").exists());
+    }
+}
@@ -0,0 +1,302 @@
+//! Build-time transclusion per [[RFC-0001:C-INPUT]].
+//!
+//! A markdown line of the form `<!-- skc:include path[#Section] -->` is
+//! replaced by the referenced file, or by a single heading-delimited section
+//! of it. Paths are relative to the including file and may reach sibling
+//! directories in the same source store (e.g. `../shared/conventions.md`),
+//! but nothing outside it. Included content is expanded recursively and
+//! cycles are rejected. Directives inside fenced code blocks are left as-is.
+
+use crate::compiler::{FileHash, sha256_hex};
+use crate::error::{Result, SkillcError};
+use crate::markdown;
+use lazy_regex::{Lazy, Regex, lazy_regex};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Regex for an include directive occupying a whole line.
+static INCLUDE_RE: Lazy<Regex> = lazy_regex!(r"^\s*<!--\s*skc:include\s+(\S.*?)\s*-->\s*$");
+
+/// Result of expanding includes across a skill's markdown files.
+#[derive(Debug, Default)]
+pub struct Expansion {
+    /// Expanded content of each file that contained directives, keyed by
+    /// path relative to the skill root
+    pub files: BTreeMap<PathBuf, String>,
+    /// Included files outside the skill root, with paths relative to it
+    pub dependencies: Vec<FileHash>,
+}
+
+impl Expansion {
+    /// True if no file contained an include directive.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Expand include directives in the given markdown files.
+///
+/// `md_files` are relative to `source_dir`. Returns E012 if an include
+/// resolves outside the source store, and E018 for missing targets,
+/// missing sections, and cycles.
+pub fn expand_includes(source_dir: &Path, md_files: &[PathBuf]) -> Result<Expansion> {
+    let skill_root = source_dir
+        .canonicalize()
+        .map_err(|e| SkillcError::Internal(format!("Failed to canonicalize source dir: {}", e)))?;
+    let include_root = skill_root
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| skill_root.clone());
+
+    let mut expander = Expander {
+        skill_root,
+        include_root,
+        dependencies: BTreeMap::new(),
+    };
+    let mut files = BTreeMap::new();
+
+    for file in md_files {
+        let path = expander.skill_root.join(file);
+        let content = fs::read_to_string(&path)?;
+        if !has_directive(&content) {
+            continue;
+        }
+        let mut stack = vec![path.clone()];
+        files.insert(file.clone(), expander.expand(&path, &content, &mut stack)?);
+    }
+
+    Ok(Expansion {
+        files,
+        dependencies: expander.dependencies.into_values().collect(),
+    })
+}
+
+/// Quick check for a directive outside code fences.
+fn has_directive(content: &str) -> bool {
+    content.contains("skc:include") && outside_fences(content).any(|l| INCLUDE_RE.is_match(l))
+}
+
+/// Lines of `content` that are not inside fenced code blocks.
+fn outside_fences(content: &str) -> impl Iterator<Item = &str> {
+    let mut in_fence = false;
+    content.lines().filter(move |line| {
+        if markdown::is_code_fence(line) {
+            in_fence = !in_fence;
+            return false;
+        }
+        !in_fence
+    })
+}
+
+struct Expander {
+    skill_root: PathBuf,
+    include_root: PathBuf,
+    /// External dependencies keyed by canonical path
+    dependencies: BTreeMap<PathBuf, FileHash>,
+}
+
+impl Expander {
+    /// Expand directives in `content`, which was read from canonical `path`.
+    fn expand(&mut self, path: &Path, content: &str, stack: &mut Vec<PathBuf>) -> Result<String> {
+        let mut out: Vec<String> = Vec::new();
+        let mut in_fence = false;
+
+        for line in content.lines() {
+            if markdown::is_code_fence(line) {
+                in_fence = !in_fence;
+            }
+            let spec = match INCLUDE_RE.captures(line) {
+                Some(caps) if !in_fence => caps[1].to_string(),
+                _ => {
+                    out.push(line.to_string());
+                    continue;
+                }
+            };
+
+            let (rel, section) = match spec.split_once('#') {
+                Some((rel, section)) => (rel, Some(section)),
+                None => (spec.as_str(), None),
+            };
+            let base = path.parent().unwrap_or(&self.include_root);
+            let target = base
+                .join(rel)
+                .canonicalize()
+                .map_err(|_| SkillcError::InvalidDirective(format!("'{}' not found", spec)))?;
+
+            // Same containment rule as symlinks, widened to the source store
+            if !target.starts_with(&self.include_root) {
+                return Err(SkillcError::PathEscapesRoot(spec.clone()));
+            }
+            if stack.contains(&target) {
+                let chain: Vec<String> = stack
+                    .iter()
+                    .chain(std::iter::once(&target))
+                    .map(|p| self.display(p))
+                    .collect();
+                return Err(SkillcError::InvalidDirective(format!(
+                    "cycle {}",
+                    chain.join(" -> ")
+                )));
+            }
+
+            let included = fs::read_to_string(&target)?;
+            if !target.starts_with(&self.skill_root) {
+                self.dependencies.insert(
+                    target.clone(),
+                    FileHash {
+                        path: Path::new("..")
+                            .join(target.strip_prefix(&self.include_root).unwrap_or(&target))
+                            .to_string_lossy()
+                            .to_string(),
+                        sha256: sha256_hex(included.as_bytes()),
+                    },
+                );
+            }
+
+            let body = match section {
+                Some(section) => select_section(&included, section).ok_or_else(|| {
+                    SkillcError::InvalidDirective(format!(
+                        "section '{}' not found in '{}'",
+                        section, rel
+                    ))
+                })?,
+                None => markdown::strip_frontmatter(&included).to_string(),
+            };
+
+            stack.push(target.clone());
+            let expanded = self.expand(&target, &body, stack)?;
+            stack.pop();
+
+            out.push(expanded.trim_end_matches('\n').to_string());
+        }
+
+        let mut result = out.join("\n");
+        if content.ends_with('\n') {
+            result.push('\n');
+        }
+        Ok(result)
+    }
+
+    /// Path for error messages, relative to the source store.
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.include_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// Extract a section (heading through its subsections) by heading text.
+///
+/// Matching is case-insensitive and ignores surrounding whitespace.
+fn select_section(content: &str, section: &str) -> Option<String> {
+    let wanted = section.trim().to_lowercase();
+    let lines: Vec<&str> = content.lines().collect();
+    let headings = markdown::extract_headings(content);
+
+    let idx = headings
+        .iter()
+        .position(|h| h.text.trim().to_lowercase() == wanted)?;
+    let heading = &headings[idx];
+    let start = heading.line.saturating_sub(1);
+    let end = headings
+        .iter()
+        .skip(idx + 1)
+        .find(|h| h.level <= heading.level)
+        .map(|h| h.line.saturating_sub(1))
+        .unwrap_or(lines.len());
+
+    Some(lines[start..end].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Create a store with a skill and a shared directory next to it.
+    fn store(temp: &TempDir, skill_md: &str) -> PathBuf {
+        let skill = temp.path().join("store").join("my-skill");
+        fs::create_dir_all(&skill).expect("create skill");
+        fs::create_dir_all(temp.path().join("store").join("shared")).expect("create shared");
+        fs::write(skill.join("SKILL.md"), skill_md).expect("write SKILL.md");
+        skill
+    }
+
+    fn expand_skill_md(skill: &Path) -> Result<Expansion> {
+        expand_includes(skill, &[PathBuf::from("SKILL.md")])
+    }
+
+    #[test]
+    fn test_expand_whole_file_and_section() {
+        let temp = TempDir::new().expect("create temp dir");
+        let skill = store(
+            &temp,
+            "# Skill\n\n<!-- skc:include ../shared/conv.md#Safety Rules -->\n\nEnd\n",
+        );
+        fs::write(
+            skill.join("../shared/conv.md"),
+            "---\ndescription: shared\n---\n# Conventions\n\n## Style\n\nTabs.\n\n## Safety Rules\n\nBe careful.\n\n### Detail\n\nMore.\n",
+        )
+        .expect("write shared");
+
+        let expansion = expand_skill_md(&skill).expect("expand");
+        let content = &expansion.files[Path::new("SKILL.md")];
+        assert_eq!(
+            content,
+            "# Skill\n\n## Safety Rules\n\nBe careful.\n\n### Detail\n\nMore.\n\nEnd\n"
+        );
+        assert_eq!(expansion.dependencies.len(), 1);
+        assert_eq!(
+            expansion.dependencies[0].path,
+            Path::new("..")
+                .join("shared")
+                .join("conv.md")
+                .to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn test_expand_ignores_fenced_directives() {
+        let temp = TempDir::new().expect("create temp dir");
+        let skill = store(
+            &temp,
+            "# Skill\n\n```\n<!-- skc:include missing.md -->\n```\n",
+        );
+
+        let expansion = expand_skill_md(&skill).expect("expand");
+        assert!(expansion.is_empty());
+    }
+
+    #[test]
+    fn test_expand_rejects_escape_and_cycles() {
+        let temp = TempDir::new().expect("create temp dir");
+        fs::write(temp.path().join("outside.md"), "# Outside\n").expect("write outside");
+
+        let skill = store(&temp, "<!-- skc:include ../../outside.md -->\n");
+        let err = expand_skill_md(&skill).expect_err("escape");
+        assert!(err.to_string().contains("error[E012]"));
+
+        fs::write(skill.join("SKILL.md"), "<!-- skc:include a.md -->\n").expect("write");
+        fs::write(skill.join("a.md"), "<!-- skc:include b.md -->\n").expect("write a");
+        fs::write(skill.join("b.md"), "<!-- skc:include a.md -->\n").expect("write b");
+        let err = expand_skill_md(&skill).expect_err("cycle");
+        let msg = err.to_string();
+        assert!(msg.contains("error[E018]"), "got: {}", msg);
+        assert!(msg.contains("cycle"), "got: {}", msg);
+    }
+
+    #[test]
+    fn test_expand_reports_missing_targets() {
+        let temp = TempDir::new().expect("create temp dir");
+        let skill = store(&temp, "<!-- skc:include nope.md -->\n");
+        let err = expand_skill_md(&skill).expect_err("missing file");
+        assert!(err.to_string().contains("'nope.md' not found"));
+
+        fs::write(skill.join("ref.md"), "# Ref\n").expect("write ref");
+        fs::write(skill.join("SKILL.md"), "<!-- skc:include ref.md#Nope -->\n").expect("write");
+        let err = expand_skill_md(&skill).expect_err("missing section");
+        assert!(err.to_string().contains("section 'Nope' not found"));
+    }
+}
@@ -8,7 +8,8 @@ use std::path::{Path, PathBuf};
 
 /// Current schema version for the search index per [[RFC-0004:C-INDEX]].
 /// v2: Added headings table for index-based section lookup.
-pub const SCHEMA_VERSION: i32 = 2;
+/// v3: Index `.csv` files row by row.
+pub const SCHEMA_VERSION: i32 = 3;
 
 /// A heading entry from the index per [[RFC-0004:C-INDEX]].
 #[derive(Debug, Clone, Serialize)]
@@ -131,6 +132,42 @@ pub fn get_all_headings(conn: &Connection) -> Result<Vec<HeadingEntry>> {
     Ok(results)
 }
 
+/// Summary of a search index, as reported by `skc build -o json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    /// Index database file
+    pub path: PathBuf,
+    /// Indexed sections (full-text rows)
+    pub sections: usize,
+    /// Headings available to `skc show`
+    pub headings: usize,
+    /// Tokenizer recorded in the index metadata
+    pub tokenizer: String,
+}
+
+/// Count the rows of a skill's search index per [[RFC-0004:C-INDEX]].
+pub fn index_stats(runtime_dir: &Path, source_dir: &Path, skill_name: &str) -> Result<IndexStats> {
+    let conn = open_index(runtime_dir, source_dir, skill_name)?;
+    let count = |sql: &str| -> Result<usize> {
+        let n: i64 = conn.query_row(sql, [], |row| row.get(0))?;
+        Ok(n as usize)
+    };
+    let tokenizer: String = conn
+        .query_row(
+            "SELECT value FROM index_meta WHERE key = 'tokenizer'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|_| SkillcError::IndexUnusable(skill_name.to_string()))?;
+
+    Ok(IndexStats {
+        path: get_index_path(runtime_dir, source_dir),
+        sections: count("SELECT COUNT(*) FROM sections")?,
+        headings: count("SELECT COUNT(*) FROM headings")?,
+        tokenizer,
+    })
+}
+
 fn row_to_heading(row: &rusqlite::Row) -> rusqlite::Result<HeadingEntry> {
     Ok(HeadingEntry {
         file: row.get(0)?,
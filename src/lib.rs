@@ -25,12 +25,21 @@ pub struct Heading {
 }
 
 pub mod analytics;
+pub mod batch;
 pub mod compiler;
+pub mod conditional;
 pub mod config;
+pub mod csv;
 pub mod deploy;
+pub mod diff;
+pub mod doctor;
+pub mod emit;
 pub mod error;
 pub mod frontmatter;
 pub mod gateway;
+pub mod history;
+pub mod hooks;
+pub mod include;
 pub mod index;
 pub mod init;
 pub mod lint;
@@ -38,21 +47,39 @@ pub mod list;
 pub mod logging;
 pub mod markdown;
 pub mod mcp;
+pub mod pack;
+pub mod registry;
+pub mod remove;
 pub mod resolver;
 pub mod search;
+pub mod skillignore;
 pub mod sync;
+pub mod tokens;
 pub mod util;
+pub mod verify;
+pub mod watch;
 
 pub use analytics::{QueryType, StatsOptions, stats};
-pub use compiler::compile;
+pub use compiler::{
+    BuildReport, CompileOptions, CompileStatus, compile, compile_with_options, compile_with_report,
+};
+pub use diff::{DiffReport, diff, format_diff};
+pub use doctor::{DoctorOptions, DoctorReport, doctor, format_doctor};
 pub use error::{Result, SkillcError, SkillcWarning};
 pub use gateway::{open, outline, show, sources};
+pub use history::{RollbackOptions, history, rollback};
 pub use init::{InitOptions, init};
 pub use lint::{Diagnostic, LintOptions, LintResult, Severity, lint};
-pub use list::{ListOptions, ListResult, SkillScope, SkillStatus, format_list, list};
+pub use list::{
+    ListOptions, ListResult, SkillScope, SkillStatus, TargetInfo, format_list, format_target_list,
+    list, list_targets,
+};
+pub use pack::{ArchiveFormat, PackOptions, PackResult, pack, unpack};
+pub use remove::{RemoveOptions, UndeployOptions, remove, undeploy};
 pub use resolver::{ResolvedSkill, resolve_skill};
-pub use search::search;
+pub use search::{QueryMode, search, search_all};
 pub use sync::{SyncOptions, sync};
+pub use verify::{VerifyReport, format_verify, verify};
 
 // Global verbose flag
 static VERBOSE: AtomicBool = AtomicBool::new(false);
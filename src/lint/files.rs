@@ -3,6 +3,7 @@
 use super::{Diagnostic, LintContext, LintResult, progress_bar};
 use crate::error::Result;
 use crate::markdown::ExtractedLink;
+use crate::skillignore::SkillIgnore;
 use indicatif::ProgressIterator;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -13,6 +14,8 @@ const EXCEPTION_FILES: &[&str] = &["LICENSE.md", "CHANGELOG.md", "CONTRIBUTING.m
 /// Lint file rules (SKL401).
 ///
 /// Uses shared LintContext to avoid repeated file reads and parsing.
+/// Files matched by `.skillcignore` are not part of the skill and are never
+/// reported as orphans.
 pub fn lint_files(skill_path: &Path, ctx: &LintContext, result: &mut LintResult) -> Result<()> {
     // Build reachability graph starting from SKILL.md
     let skill_md = skill_path.join("SKILL.md");
@@ -20,6 +23,7 @@ pub fn lint_files(skill_path: &Path, ctx: &LintContext, result: &mut LintResult)
 
     let pb = progress_bar("Checking orphans", ctx.md_files.len());
     let exception_set: HashSet<&str> = EXCEPTION_FILES.iter().copied().collect();
+    let ignore = SkillIgnore::load(skill_path);
 
     for file_path in ctx.md_files.iter().progress_with(pb) {
         let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
@@ -29,6 +33,11 @@ pub fn lint_files(skill_path: &Path, ctx: &LintContext, result: &mut LintResult)
             continue;
         }
 
+        // Skip files excluded by .skillcignore
+        if ignore.is_ignored(file_path, false) {
+            continue;
+        }
+
         // Check if reachable
         if let Ok(canonical) = file_path.canonicalize()
             && !reachable.contains(&canonical)
@@ -240,6 +249,25 @@ mod tests {
         assert!(result.diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_skillcignore_files_not_orphaned() {
+        let dir = TempDir::new().expect("create temp dir");
+        let skill_path = dir.path();
+
+        fs::write(skill_path.join("SKILL.md"), "# Test\n").expect("write test file");
+        fs::write(skill_path.join(".skillcignore"), "drafts/\n").expect("write ignore file");
+
+        let drafts = skill_path.join("drafts");
+        fs::create_dir_all(&drafts).expect("create test dir");
+        fs::write(drafts.join("idea.md"), "# Idea\n").expect("write test file");
+
+        let mut result = make_result();
+        let ctx = make_context(skill_path);
+        lint_files(skill_path, &ctx, &mut result).expect("lint files");
+
+        assert!(result.diagnostics.is_empty());
+    }
+
     #[test]
     fn test_nested_orphan() {
         let dir = TempDir::new().expect("create temp dir");
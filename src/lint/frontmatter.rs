@@ -15,7 +15,7 @@ static TRIGGER_RE: Lazy<Regex> =
     lazy_regex!(r"(?i)(use when|when to use|use for|triggers on|triggers:|activate when)");
 
 /// Known frontmatter fields per [[RFC-0008:C-REGISTRY]] SKL109
-const KNOWN_FIELDS: &[&str] = &["name", "description", "allowed-tools"];
+const KNOWN_FIELDS: &[&str] = &["name", "description", "allowed-tools", "globs"];
 
 /// Re-export for backward compatibility
 pub fn parse_frontmatter(content: &str) -> Result<(Option<RawFrontmatter>, bool)> {
@@ -333,10 +333,11 @@ pub fn lint(skill_path: &Path, options: LintOptions) -> Result<LintResult> {
     // Build shared context (collects and parses all files in parallel)
     let ctx = LintContext::new(skill_path)?;
 
-    // Run heading hierarchy rules (SKL204-SKL205) for all markdown files
+    // Run heading hierarchy and conditional rules (SKL204-SKL206) for all markdown files
     for file_path in &ctx.md_files {
         if let Some(cached) = ctx.get(file_path) {
             structure::lint_heading_hierarchy(&cached.content, file_path, skill_path, &mut result);
+            structure::lint_conditionals(&cached.content, file_path, skill_path, &mut result);
         }
     }
 
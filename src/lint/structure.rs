@@ -1,6 +1,7 @@
-//! Structure lint rules (SKL201-SKL205) per [[RFC-0008:C-REGISTRY]]
+//! Structure lint rules (SKL201-SKL206) per [[RFC-0008:C-REGISTRY]]
 
 use super::{Diagnostic, LintResult};
+use crate::conditional;
 use crate::markdown::extract_headings;
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use std::path::Path;
@@ -154,6 +155,27 @@ pub fn lint_heading_hierarchy(
     }
 }
 
+/// Lint target-conditional blocks (SKL206) for a single file.
+///
+/// Per [[RFC-0008:C-REGISTRY]], every `skc:if` must be closed by an
+/// `skc:endif` and name only known targets.
+pub fn lint_conditionals(
+    content: &str,
+    file_path: &Path,
+    skill_path: &Path,
+    result: &mut LintResult,
+) {
+    let relative_path = file_path.strip_prefix(skill_path).unwrap_or(file_path);
+
+    for problem in conditional::check(content) {
+        result.add(
+            Diagnostic::error("SKL206", "conditional-balanced", problem.message)
+                .with_file(relative_path)
+                .with_line(problem.line),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +358,25 @@ mod tests {
             "Going up levels should not trigger warnings"
         );
     }
+
+    #[test]
+    fn test_lint_conditionals_balanced() {
+        let content = "# Title\n\n<!-- skc:if target=claude -->\nClaude.\n<!-- skc:endif -->\n";
+        let mut result = make_result();
+        lint_conditionals(content, Path::new("test.md"), Path::new("."), &mut result);
+
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_lint_conditionals_skl206_unclosed() {
+        let content = "# Title\n\n<!-- skc:if target=claude -->\nClaude.\n";
+        let mut result = make_result();
+        lint_conditionals(content, Path::new("test.md"), Path::new("."), &mut result);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule_id, "SKL206");
+        assert_eq!(result.diagnostics[0].line, Some(3));
+        assert!(result.diagnostics[0].message.contains("never closed"));
+    }
 }
@@ -1,16 +1,19 @@
 //! Skill listing functionality per [[RFC-0007:C-LIST]]
 
 use crate::OutputFormat;
-use crate::config::{find_project_root, global_skillc_dir, global_source_store};
+use crate::compiler::{FileHash, current_source_hash, read_manifest};
+use crate::config::{
+    TargetSpec, all_targets, find_project_root, global_skillc_dir, global_source_store,
+};
+use crate::deploy::{copy_deployments, deploy_source};
 use crate::error::{Result, SkillcError};
+use crate::registry::{Registry, RegistryEntry};
 use crate::util::{project_skill_runtime_dir, project_skills_dir};
 use comfy_table::{Cell, Color, ContentArrangement, Table};
 use glob::Pattern;
 use serde::Serialize;
-use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 /// Skill status per [[RFC-0007:C-LIST]]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -60,6 +63,12 @@ pub struct SkillInfo {
     pub source_path: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub runtime_path: Option<PathBuf>,
+    /// Registered deployments of the runtime per [[RFC-0001:C-DEPLOYMENT]]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deployments: Vec<RegistryEntry>,
+    /// Copy deployments whose manifest source hash is behind the runtime's
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stale_copies: Vec<PathBuf>,
 }
 
 /// Options for the list command
@@ -85,9 +94,94 @@ pub struct ListResult {
     pub total: usize,
 }
 
+/// Where a deployment target comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetSource {
+    /// Built into skillc
+    Builtin,
+    /// Defined under `[targets.<name>]` in config
+    Config,
+}
+
+/// A named deployment target, as listed by `skc list --targets`
+#[derive(Debug, Serialize)]
+pub struct TargetInfo {
+    pub name: String,
+    pub source: TargetSource,
+    /// Native instruction format for `skc build --native`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_path: Option<PathBuf>,
+    /// Skills directory in the current project, if inside one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<PathBuf>,
+}
+
+/// List built-in and configured deployment targets
+pub fn list_targets() -> Vec<TargetInfo> {
+    let project_root = find_project_root();
+    all_targets()
+        .into_iter()
+        .map(|target| TargetInfo {
+            name: target.to_string(),
+            source: match target {
+                TargetSpec::Configured(..) => TargetSource::Config,
+                _ => TargetSource::Builtin,
+            },
+            layout: target.native_format().map(|f| f.as_str()),
+            global_path: target.skills_path(None).ok(),
+            project_path: project_root
+                .as_deref()
+                .and_then(|root| target.skills_path(Some(root)).ok()),
+        })
+        .collect()
+}
+
+/// Format a target listing for output
+pub fn format_target_list(targets: &[TargetInfo], format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return serde_json::to_string_pretty(targets)
+            .map_err(|e| SkillcError::Internal(format!("JSON serialization failed: {}", e)));
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(comfy_table::presets::NOTHING)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    if table.width().unwrap_or(0) < 80 {
+        table.set_width(120);
+    }
+    table.set_header(vec!["TARGET", "SOURCE", "LAYOUT", "GLOBAL", "PROJECT"]);
+
+    let path_cell = |path: &Option<PathBuf>| {
+        Cell::new(
+            path.as_ref()
+                .map_or_else(|| "-".to_string(), |p| p.display().to_string()),
+        )
+    };
+    for target in targets {
+        let source_cell = match target.source {
+            TargetSource::Builtin => Cell::new("builtin").fg(Color::DarkGrey),
+            TargetSource::Config => Cell::new("config").fg(Color::Cyan),
+        };
+        table.add_row(vec![
+            Cell::new(&target.name),
+            source_cell,
+            Cell::new(target.layout.unwrap_or("-")),
+            path_cell(&target.global_path),
+            path_cell(&target.project_path),
+        ]);
+    }
+
+    Ok(table.to_string())
+}
+
 /// List all skillc-managed skills per [[RFC-0007:C-LIST]]
 pub fn list(options: &ListOptions) -> Result<ListResult> {
     let mut skills = Vec::new();
+    let registry = Registry::load().unwrap_or_default();
 
     // 1. Discover project-local skills (recursive-up search)
     if let Some(project_root) = find_project_root() {
@@ -98,6 +192,7 @@ pub fn list(options: &ListOptions) -> Result<ListResult> {
                 SkillScope::Project,
                 &project_root,
                 options.check_obsolete,
+                &registry,
                 &mut skills,
             )?;
         }
@@ -112,6 +207,7 @@ pub fn list(options: &ListOptions) -> Result<ListResult> {
             SkillScope::Global,
             &global_skillc_dir()?,
             options.check_obsolete,
+            &registry,
             &mut skills,
         )?;
     }
@@ -189,9 +285,9 @@ fn format_text(result: &ListResult, verbose: bool) -> Result<String> {
 
     // Header row
     if verbose {
-        table.set_header(vec!["SKILL", "SCOPE", "STATUS", "SOURCE"]);
+        table.set_header(vec!["SKILL", "SCOPE", "STATUS", "TARGETS", "SOURCE"]);
     } else {
-        table.set_header(vec!["SKILL", "SCOPE", "STATUS"]);
+        table.set_header(vec!["SKILL", "SCOPE", "STATUS", "TARGETS"]);
     }
 
     // Data rows with colored status/scope
@@ -207,27 +303,60 @@ fn format_text(result: &ListResult, verbose: bool) -> Result<String> {
             SkillStatus::Obsolete => Cell::new("obsolete").fg(Color::Red),
         };
 
+        let targets_cell = if skill.stale_copies.is_empty() {
+            Cell::new(deployment_targets(&skill.deployments))
+        } else {
+            Cell::new(format!(
+                "{} ({} stale copy)",
+                deployment_targets(&skill.deployments),
+                skill.stale_copies.len()
+            ))
+            .fg(Color::Yellow)
+        };
+
         if verbose {
             table.add_row(vec![
                 Cell::new(&skill.name),
                 scope_cell,
                 status_cell,
+                targets_cell,
                 Cell::new(skill.source_path.display().to_string()),
             ]);
         } else {
-            table.add_row(vec![Cell::new(&skill.name), scope_cell, status_cell]);
+            table.add_row(vec![
+                Cell::new(&skill.name),
+                scope_cell,
+                status_cell,
+                targets_cell,
+            ]);
         }
     }
 
     Ok(table.to_string())
 }
 
+/// Distinct deployment targets, or "-" when the skill is not deployed.
+fn deployment_targets(deployments: &[RegistryEntry]) -> String {
+    let mut targets: Vec<&str> = Vec::new();
+    for entry in deployments {
+        if !targets.contains(&entry.target.as_str()) {
+            targets.push(&entry.target);
+        }
+    }
+    if targets.is_empty() {
+        "-".to_string()
+    } else {
+        targets.join(", ")
+    }
+}
+
 /// Discover skills in a directory
 fn discover_skills_in_dir(
     skills_dir: &Path,
     scope: SkillScope,
     context_root: &Path,
     check_obsolete: bool,
+    registry: &Registry,
     skills: &mut Vec<SkillInfo>,
 ) -> Result<()> {
     for entry in fs::read_dir(skills_dir)? {
@@ -257,6 +386,12 @@ fn discover_skills_in_dir(
 
         // Determine status based on check_obsolete flag
         let (status, has_valid_runtime) = determine_status(&path, &runtime_path, check_obsolete)?;
+        let deployments = registry.for_runtime(&runtime_path).cloned().collect();
+        let stale_copies = if has_valid_runtime {
+            stale_copies(&runtime_path, &name, registry)
+        } else {
+            Vec::new()
+        };
 
         skills.push(SkillInfo {
             name,
@@ -268,12 +403,31 @@ fn discover_skills_in_dir(
             } else {
                 None
             },
+            deployments,
+            stale_copies,
         });
     }
 
     Ok(())
 }
 
+/// Copy deployments of a runtime whose manifest source hash differs from
+/// the runtime (or target variant) they were copied from.
+fn stale_copies(runtime_path: &Path, skill_name: &str, registry: &Registry) -> Vec<PathBuf> {
+    copy_deployments(runtime_path, skill_name, registry)
+        .into_iter()
+        .filter(|(target, dest)| {
+            let expected = read_manifest(&deploy_source(runtime_path, target));
+            let deployed = read_manifest(dest);
+            match (expected, deployed) {
+                (Some(expected), Some(deployed)) => deployed.source_hash != expected.source_hash,
+                _ => false,
+            }
+        })
+        .map(|(_, dest)| dest)
+        .collect()
+}
+
 /// Determine the status of a skill
 ///
 /// Returns (status, has_valid_runtime) tuple.
@@ -306,8 +460,13 @@ fn determine_status(
         None => return Ok((SkillStatus::Obsolete, true)), // No hash in manifest = obsolete
     };
 
-    // Compute current source hash (expensive operation)
-    let current_hash = compute_source_hash(source_dir)?;
+    // Compute current source hash, including shared files pulled in by
+    // include directives (expensive operation)
+    let includes: Vec<FileHash> = manifest
+        .get("includes")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let current_hash = current_source_hash(source_dir, &includes)?;
 
     if current_hash == stored_hash {
         Ok((SkillStatus::Normal, true))
@@ -316,48 +475,10 @@ fn determine_status(
     }
 }
 
-/// Compute SHA-256 hash of source files (same logic as compiler)
-fn compute_source_hash(source_dir: &Path) -> Result<String> {
-    let mut file_hashes: Vec<(String, String)> = Vec::new();
-
-    for entry in WalkDir::new(source_dir)
-        .into_iter()
-        .filter_entry(|e| {
-            // Exclude hidden directories
-            !e.file_type().is_dir() || !e.file_name().to_string_lossy().starts_with('.')
-        })
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let relative_path = entry
-            .path()
-            .strip_prefix(source_dir)
-            .map_err(|_| SkillcError::Internal("path does not start with source_dir".into()))?
-            .to_string_lossy()
-            .to_string();
-        let content = fs::read(entry.path())?;
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        let file_hash = format!("{:x}", hasher.finalize());
-        file_hashes.push((relative_path, file_hash));
-    }
-
-    // Sort by path for deterministic hash
-    file_hashes.sort_by(|a, b| a.0.cmp(&b.0));
-
-    // Hash the combined (path, hash) pairs
-    let mut hasher = Sha256::new();
-    for (path, hash) in &file_hashes {
-        hasher.update(path.as_bytes());
-        hasher.update(hash.as_bytes());
-    }
-
-    Ok(format!("{:x}", hasher.finalize()))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::compute_source_hash;
     use tempfile::TempDir;
 
     #[test]
@@ -508,6 +629,16 @@ mod tests {
                     status: SkillStatus::Normal,
                     source_path: PathBuf::from("/path/to/skill"),
                     runtime_path: Some(PathBuf::from("/runtime/path")),
+                    deployments: vec![RegistryEntry {
+                        skill: "test-skill".to_string(),
+                        ssot: PathBuf::from("/runtime/path"),
+                        target: "claude".to_string(),
+                        path: PathBuf::from("/agent/test-skill"),
+                        method: crate::deploy::DeployMethod::Symlink,
+                        deployed_at: "2026-01-01T00:00:00+00:00".to_string(),
+                        source_hash: "abc".to_string(),
+                    }],
+                    stale_copies: Vec::new(),
                 },
                 SkillInfo {
                     name: "global-skill".to_string(),
@@ -515,6 +646,8 @@ mod tests {
                     status: SkillStatus::NotBuilt,
                     source_path: PathBuf::from("/global/skill"),
                     runtime_path: None,
+                    deployments: Vec::new(),
+                    stale_copies: Vec::new(),
                 },
             ],
             total: 2,
@@ -536,6 +669,11 @@ mod tests {
             "should contain status, got: {}",
             output
         );
+        assert!(
+            output.contains("TARGETS") && output.contains("claude"),
+            "should contain deployment targets, got: {}",
+            output
+        );
     }
 
     #[test]
@@ -547,6 +685,8 @@ mod tests {
                 status: SkillStatus::Obsolete,
                 source_path: PathBuf::from("/path/to/source"),
                 runtime_path: Some(PathBuf::from("/path/to/runtime")),
+                deployments: Vec::new(),
+                stale_copies: Vec::new(),
             }],
             total: 1,
         };
@@ -578,6 +718,8 @@ mod tests {
                 status: SkillStatus::Normal,
                 source_path: PathBuf::from("/path"),
                 runtime_path: None,
+                deployments: Vec::new(),
+                stale_copies: Vec::new(),
             }],
             total: 1,
         };
@@ -26,7 +26,11 @@ pub struct LogEntry {
 ///
 /// Note: This does not test writability. Use `log_access_with_fallback` which handles
 /// readonly errors at write time (EAFP pattern).
+///
+/// Holds the shared runtime state lock while opening, so the database is not
+/// created in a runtime that a concurrent build is swapping out.
 pub fn init_log_db(runtime_dir: &Path) -> Option<Connection> {
+    let _lock = crate::history::lock_runtime_state(runtime_dir, false);
     try_init_db_at(runtime_dir)
 }
 
@@ -119,8 +123,12 @@ pub fn log_access_with_fallback(conn: Option<&Connection>, entry: &LogEntry) {
     // Check for stale fallback logs first (for all commands)
     check_stale_fallback_logs(&entry.skill);
 
-    // Try primary connection first
+    // Try primary connection first, under the shared lock of its runtime
     if let Some(c) = conn {
+        let _lock = c
+            .path()
+            .and_then(|db| Path::new(db).parent()?.parent())
+            .and_then(|runtime_dir| crate::history::lock_runtime_state(runtime_dir, false).ok());
         match try_log_access(c, entry) {
             Ok(()) => return,
             Err(e) if is_readonly_error(&e) => {
@@ -1,12 +1,16 @@
 //! skillc CLI - skc command
 
 use clap::{Parser, Subcommand};
+use skillc::compiler::compute_source_hash;
 use skillc::config::{
     TargetSpec, find_project_root, find_project_skill, global_runtime_store, global_source_store,
     resolve_source_store,
 };
-use skillc::deploy::{self, DeployMethod};
-use skillc::{InitOptions, LintOptions, OutputFormat, QueryType, StatsOptions, SyncOptions};
+use skillc::deploy;
+use skillc::{
+    ArchiveFormat, CompileStatus, InitOptions, LintOptions, OutputFormat, PackOptions, QueryMode,
+    QueryType, StatsOptions, SyncOptions,
+};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
@@ -72,6 +76,10 @@ enum Commands {
         #[arg(long)]
         check_obsolete: bool,
 
+        /// List deployment targets (built-in and configured) instead of skills
+        #[arg(long, conflicts_with_all = ["limit", "pattern", "check_obsolete"])]
+        targets: bool,
+
         /// Output format
         #[arg(short = 'o', long, value_enum, default_value = "text")]
         format: OutputFormat,
@@ -80,12 +88,22 @@ enum Commands {
     /// Compile a skill to runtime format
     Build {
         /// Skill name (looks in source store) or path to skill directory
-        skill: String,
+        #[arg(required_unless_present = "all")]
+        skill: Option<String>,
 
         /// Force SSOT to global (~/.skillc/runtime/) regardless of source location
+        /// (with --all: build only the global source store)
         #[arg(short, long)]
         global: bool,
 
+        /// Build every skill in the project and global source stores
+        #[arg(long, conflicts_with_all = ["skill", "check", "watch"])]
+        all: bool,
+
+        /// Filter skills by name (glob pattern, with --all)
+        #[arg(short, long, requires = "all")]
+        pattern: Option<String>,
+
         /// Target agents to deploy to (comma-separated, or custom path)
         #[arg(short, long, value_delimiter = ',', default_value = "claude")]
         target: Vec<TargetSpec>,
@@ -94,9 +112,37 @@ enum Commands {
         #[arg(long)]
         copy: bool,
 
-        /// Force overwrite when importing from a path
+        /// Rebuild and redeploy even if up to date (also overwrites on import)
         #[arg(short, long)]
         force: bool,
+
+        /// Exit nonzero if a rebuild is needed, without writing anything
+        #[arg(long, conflicts_with = "force")]
+        check: bool,
+
+        /// Rebuild and redeploy whenever the source changes
+        #[arg(short, long, conflicts_with = "check")]
+        watch: bool,
+
+        /// Also render the stub in each target's native format and deploy it
+        /// where the agent reads it (Cursor rules, AGENTS.md,
+        /// copilot-instructions.md, GEMINI.md)
+        #[arg(long, conflicts_with = "check")]
+        native: bool,
+
+        /// Build twice into scratch directories and fail if the outputs differ
+        #[arg(long, conflicts_with_all = ["all", "check", "watch"])]
+        reproducible_check: bool,
+
+        /// Output format
+        #[arg(
+            short = 'o',
+            long,
+            value_enum,
+            default_value = "text",
+            conflicts_with_all = ["check", "watch", "reproducible_check"]
+        )]
+        format: OutputFormat,
     },
 
     /// List all sections in a skill
@@ -132,7 +178,7 @@ enum Commands {
         /// Skill name or path to skill directory
         skill: String,
 
-        /// Relative path to file within the skill
+        /// Relative path to file within the skill (`<file>.csv#<row>` for one CSV row)
         path: String,
 
         /// Maximum lines to return
@@ -168,11 +214,25 @@ enum Commands {
 
     /// Search skill content
     Search {
-        /// Skill name or path to skill directory
-        skill: String,
+        /// Skill name or path to skill directory (with --all: the query)
+        #[arg(required_unless_present = "all")]
+        skill: Option<String>,
 
         /// Search query (bag-of-words, implicit AND)
-        query: String,
+        #[arg(required_unless_present = "all")]
+        query: Option<String>,
+
+        /// Search every built skill in the project and global stores
+        #[arg(long)]
+        all: bool,
+
+        /// Pass the query through as FTS5 syntax (OR, NOT, prefix*, NEAR(), column:term)
+        #[arg(long, conflicts_with = "phrase")]
+        raw: bool,
+
+        /// Match the words as an exact phrase (adjacent, in order)
+        #[arg(long)]
+        phrase: bool,
 
         /// Maximum number of results
         #[arg(short, long, default_value = "10")]
@@ -223,6 +283,143 @@ enum Commands {
         dry_run: bool,
     },
 
+    /// Show what changed in a skill since its last build
+    Diff {
+        /// Skill name
+        skill: String,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Check source, runtime, and deployments against the last build
+    Verify {
+        /// Skill name
+        skill: String,
+
+        /// Additional agent directories to check (comma-separated, or custom path)
+        #[arg(short, long, value_delimiter = ',')]
+        target: Vec<TargetSpec>,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Check stores, deployments, indexes, logs, and config for problems
+    Doctor {
+        /// Apply safe repairs
+        #[arg(long)]
+        fix: bool,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Remove a skill's deployments from agent directories
+    Undeploy {
+        /// Skill name
+        skill: String,
+
+        /// Use the global stores even inside a project
+        #[arg(short, long)]
+        global: bool,
+
+        /// Only these targets (comma-separated, or custom path; default: all known)
+        #[arg(short, long, value_delimiter = ',')]
+        target: Vec<TargetSpec>,
+
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Undeploy a skill and delete its runtime, index, and source
+    Remove {
+        /// Skill name
+        skill: String,
+
+        /// Use the global stores even inside a project
+        #[arg(short, long)]
+        global: bool,
+
+        /// Also delete access logs
+        #[arg(long)]
+        logs: bool,
+
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Restore a retained earlier build and repoint its deployments
+    Rollback {
+        /// Skill name
+        skill: String,
+
+        /// Retained build to restore (default: the most recently retained)
+        #[arg(long)]
+        to: Option<u64>,
+
+        /// List the current and retained builds instead of rolling back
+        #[arg(long, conflicts_with = "to")]
+        list: bool,
+
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Pack a skill's sources into a checksummed archive
+    Pack {
+        /// Skill name
+        skill: String,
+
+        /// Archive path (default: ./<skill>.tar.gz or ./<skill>.zip)
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Archive format (default: inferred from --output, else tar-gz)
+        #[arg(long, value_enum)]
+        format: Option<ArchiveFormat>,
+    },
+
+    /// Verify a skill archive and import it into a source store
+    Install {
+        /// Path to an archive created by `skc pack`
+        archive: PathBuf,
+
+        /// Install into the global source store (~/.skillc/skills/)
+        #[arg(short, long)]
+        global: bool,
+
+        /// Overwrite an existing skill with the same name
+        #[arg(short, long)]
+        force: bool,
+
+        /// Build and deploy after installing
+        #[arg(long)]
+        build: bool,
+
+        /// Target agents to deploy to (comma-separated, or custom path; with --build)
+        #[arg(short, long, value_delimiter = ',', default_value = "claude")]
+        target: Vec<TargetSpec>,
+
+        /// Force copy instead of symlink/junction for deployment (with --build)
+        #[arg(long)]
+        copy: bool,
+    },
+
     /// Start MCP server for agent integration
     Mcp,
 
@@ -304,6 +501,263 @@ fn is_inside_skillc(path: &Path) -> bool {
     path.components().any(|c| c.as_os_str() == ".skillc")
 }
 
+/// A skill copied (or found already copied) into a source store.
+struct ImportedSkill {
+    /// Skill directory in the source store
+    source: PathBuf,
+    project_root: Option<PathBuf>,
+    is_local: bool,
+    /// False if the store already held an identical copy
+    copied: bool,
+}
+
+impl ImportedSkill {
+    fn scope(&self) -> &'static str {
+        if self.is_local { "project" } else { "global" }
+    }
+}
+
+/// Import a skill directory into the project or global source store.
+///
+/// Respects `--global`, else uses the CWD project or global store. An
+/// identical copy is left alone unless forced. With `check`, nothing is
+/// written and a pending import is reported as out of date.
+fn import_skill(
+    path: &Path,
+    name: &str,
+    global: bool,
+    force: bool,
+    check: bool,
+) -> skillc::Result<ImportedSkill> {
+    // 1. Determine destination
+    let (dest_store, is_local) = if global {
+        (global_source_store()?, false)
+    } else {
+        resolve_source_store()?
+    };
+    let dest = dest_store.join(name);
+
+    // 2. Skip re-import if the source store copy is identical
+    let already_imported =
+        dest.exists() && compute_source_hash(&dest)? == compute_source_hash(path)?;
+
+    let mut copied = false;
+    if check {
+        if !already_imported {
+            return Err(skillc::SkillcError::BuildOutdated(name.to_string()));
+        }
+    } else if !already_imported || force {
+        // 3. Check for conflicts
+        if dest.exists() && !force {
+            return Err(skillc::SkillcError::Internal(format!(
+                "Skill '{}' already exists at {}. Use --force to overwrite.",
+                name,
+                dest.display()
+            )));
+        }
+
+        // 4. Copy skill to source store
+        if dest.exists() {
+            fs::remove_dir_all(&dest).map_err(|e| {
+                skillc::SkillcError::Io(std::io::Error::new(
+                    e.kind(),
+                    format!("Failed to remove existing skill: {}", e),
+                ))
+            })?;
+        }
+        skillc::util::copy_dir_filtered(path, &dest).map_err(|e| {
+            skillc::SkillcError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to copy skill: {}", e),
+            ))
+        })?;
+        copied = true;
+    }
+
+    let project_root = if is_local { find_project_root() } else { None };
+    Ok(ImportedSkill {
+        source: dest,
+        project_root,
+        is_local,
+        copied,
+    })
+}
+
+/// Determine the SSOT location per [[RFC-0001:C-DEPLOYMENT]].
+fn ssot_dir(
+    skill_name: &str,
+    global: bool,
+    is_local: bool,
+    project_root: Option<&Path>,
+) -> skillc::Result<PathBuf> {
+    if global || !is_local {
+        // Global SSOT: ~/.skillc/runtime/<skill>/
+        return Ok(global_runtime_store()?.join(skill_name));
+    }
+    // Local SSOT: {project}/.skillc/runtime/{skill}/
+    match project_root {
+        Some(r) => Ok(skillc::util::project_skill_runtime_dir(r, skill_name)),
+        None => Ok(global_runtime_store()?.join(skill_name)),
+    }
+}
+
+/// Deploy the SSOT to each target, leaving current deployments untouched unless forced.
+///
+/// Native instruction files are deployed for `native` (see [`deploy::native_targets`]).
+fn deploy_targets(
+    ssot: &Path,
+    targets: &[TargetSpec],
+    native: &[TargetSpec],
+    skill_name: &str,
+    copy: bool,
+    deploy_root: Option<&Path>,
+    force: bool,
+) -> skillc::Result<()> {
+    let mut deployments =
+        deploy::deploy_targets(ssot, targets, skill_name, copy, deploy_root, force)?;
+    deployments.extend(deploy::deploy_native(
+        ssot,
+        native,
+        skill_name,
+        deploy_root,
+    )?);
+    print_deployments(&deployments);
+    Ok(())
+}
+
+/// Print one line per deployment.
+fn print_deployments(deployments: &[deploy::Deployment]) {
+    for deployment in deployments {
+        if deployment.unchanged {
+            println!(
+                "  Deploy:  {} ({}, unchanged)",
+                deployment.path.display(),
+                deployment.method
+            );
+        } else if deployment.refreshed {
+            println!(
+                "  Refresh: {} ({})",
+                deployment.path.display(),
+                deployment.method
+            );
+        } else {
+            println!(
+                "  Deploy:  {} ({})",
+                deployment.path.display(),
+                deployment.method
+            );
+        }
+    }
+}
+
+/// Print lint diagnostics to stderr and a summary line to stdout.
+fn print_lint_result(result: &skillc::LintResult) {
+    // Print diagnostics to stderr per [[RFC-0005:C-CODES]]
+    for diag in &result.diagnostics {
+        eprintln!("{}", diag);
+    }
+
+    // Print summary
+    if result.error_count == 0 && result.warning_count == 0 {
+        println!("Lint passed: no issues found");
+    } else {
+        println!(
+            "Lint complete: {} error(s), {} warning(s)",
+            result.error_count, result.warning_count
+        );
+    }
+}
+
+/// Report whether two builds of `source` are byte-identical.
+fn check_reproducible(source: &Path, skill_name: &str) -> skillc::Result<()> {
+    let changes = skillc::compiler::check_reproducible(source)?;
+    if changes.is_empty() {
+        println!("{} builds reproducibly", skill_name);
+        return Ok(());
+    }
+    for change in &changes {
+        println!("  {:<9} {}", change.change.to_string(), change.path);
+    }
+    Err(skillc::SkillcError::Internal(format!(
+        "build of '{}' is not reproducible: {} file(s) differ",
+        skill_name,
+        changes.len()
+    )))
+}
+
+/// Where watch mode redeploys after each rebuild.
+struct WatchDeploy<'a> {
+    targets: &'a [TargetSpec],
+    /// Targets that also get native instruction files
+    native: &'a [TargetSpec],
+    copy: bool,
+    /// Project root for project-local agent directories
+    root: Option<&'a Path>,
+}
+
+/// Watch the source directory and rebuild on settled changes. Runs until interrupted.
+///
+/// For an imported skill, `import` is the original directory and `--global`:
+/// that directory is watched and re-imported before each rebuild.
+///
+/// Build and lint failures are reported and the watch continues, so a
+/// half-edited skill does not end the session.
+fn watch_and_rebuild(
+    source: &Path,
+    ssot: &Path,
+    skill_name: &str,
+    deploy: &WatchDeploy,
+    import: Option<(&Path, bool)>,
+) {
+    use skillc::watch::{DEBOUNCE, POLL_INTERVAL, SourceWatcher};
+
+    let watched = import.map_or(source, |(original, _)| original);
+    println!("Watching {} (Ctrl-C to stop)", watched.display());
+    let mut watcher = SourceWatcher::new(watched, source, ssot, DEBOUNCE);
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if !watcher.poll() {
+            continue;
+        }
+
+        let rebuild = || -> skillc::Result<()> {
+            if let Some((original, global)) = import {
+                import_skill(original, skill_name, global, true, false)?;
+            }
+            skillc::compile_with_options(
+                source,
+                ssot,
+                &skillc::CompileOptions {
+                    native: deploy.native.to_vec(),
+                    ..Default::default()
+                },
+            )?;
+            println!("Rebuilt {}", skill_name);
+            deploy_targets(
+                ssot,
+                deploy.targets,
+                deploy.native,
+                skill_name,
+                deploy.copy,
+                deploy.root,
+                false,
+            )
+        };
+        let rebuilt = rebuild();
+        watcher.rebuilt();
+        if let Err(e) = rebuilt {
+            eprintln!("{}", e);
+            continue;
+        }
+
+        match skillc::lint(source, LintOptions { force: false }) {
+            Ok(result) => print_lint_result(&result),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
@@ -333,8 +787,15 @@ fn run(cli: Cli) -> skillc::Result<()> {
             limit,
             pattern,
             check_obsolete,
+            targets,
             format,
         } => {
+            if targets {
+                let targets = skillc::list_targets();
+                println!("{}", skillc::format_target_list(&targets, format)?);
+                return Ok(());
+            }
+
             // Convert CLI filters to library types per [[RFC-0007:C-LIST]]
             let scope_filter = match scope {
                 ScopeFilter::Project => Some(skillc::SkillScope::Project),
@@ -364,11 +825,47 @@ fn run(cli: Cli) -> skillc::Result<()> {
         Commands::Build {
             skill,
             global,
+            all,
+            pattern,
             target,
             copy,
             force,
+            check,
+            watch,
+            native,
+            reproducible_check,
+            format,
         } => {
+            let json = matches!(format, OutputFormat::Json);
+            if all {
+                let result = skillc::batch::build_all(&skillc::batch::BatchOptions {
+                    scope: global.then_some(skillc::SkillScope::Global),
+                    pattern,
+                    targets: target,
+                    copy,
+                    force,
+                    native,
+                })?;
+                if json {
+                    println!("{}", skillc::batch::format_batch_json(&result)?);
+                } else {
+                    println!("{}", skillc::batch::format_batch(&result));
+                }
+
+                let failed = result.failed_count();
+                if failed > 0 {
+                    return Err(skillc::SkillcError::Internal(format!(
+                        "{} skill(s) failed to build",
+                        failed
+                    )));
+                }
+                return Ok(());
+            }
+
             // Per [[RFC-0001:C-DEPLOYMENT]] - simplified build with import flow
+            let skill = skill.ok_or_else(|| {
+                skillc::SkillcError::InvalidOption("missing skill name".to_string())
+            })?;
 
             let path = PathBuf::from(&skill);
             let is_direct_path = path.exists() && path.is_dir() && !is_inside_skillc(&path);
@@ -380,50 +877,26 @@ fn run(cli: Cli) -> skillc::Result<()> {
 
                 // 1. Extract skill name from frontmatter
                 let name = extract_skill_name(&path)?;
-
-                // 2. Determine destination (respect --global flag, else use CWD project or global)
-                let (dest_store, is_local) = if global {
-                    (global_source_store()?, false)
-                } else {
-                    resolve_source_store()?
-                };
-                let dest = dest_store.join(&name);
-
-                // 3. Check for conflicts
-                if dest.exists() && !force {
-                    return Err(skillc::SkillcError::Internal(format!(
-                        "Skill '{}' already exists at {}. Use --force to overwrite.",
-                        name,
-                        dest.display()
-                    )));
+                if reproducible_check {
+                    return check_reproducible(&path, &name);
                 }
 
-                // 4. Copy skill to source store
-                if dest.exists() {
-                    fs::remove_dir_all(&dest).map_err(|e| {
-                        skillc::SkillcError::Io(std::io::Error::new(
-                            e.kind(),
-                            format!("Failed to remove existing skill: {}", e),
-                        ))
-                    })?;
+                // 2. Copy to the project or global source store
+                let imported = import_skill(&path, &name, global, force, check)?;
+                if imported.copied && !json {
+                    println!(
+                        "Imported {} → {} ({})",
+                        path.display(),
+                        imported.source.display(),
+                        imported.scope()
+                    );
                 }
-                skillc::util::copy_dir_recursive(&path, &dest).map_err(|e| {
-                    skillc::SkillcError::Io(std::io::Error::new(
-                        e.kind(),
-                        format!("Failed to copy skill: {}", e),
-                    ))
-                })?;
-
-                let scope = if is_local { "project" } else { "global" };
-                println!(
-                    "Imported {} → {} ({})",
-                    path.display(),
-                    dest.display(),
-                    scope
-                );
-
-                let project_root = if is_local { find_project_root() } else { None };
-                (dest, name, project_root, is_local)
+                (
+                    imported.source,
+                    name,
+                    imported.project_root,
+                    imported.is_local,
+                )
             } else {
                 // === LOOKUP FLOW ===
                 // Skill name → find in project source store or global
@@ -447,45 +920,95 @@ fn run(cli: Cli) -> skillc::Result<()> {
                 }
             };
 
-            // Determine SSOT location per [[RFC-0001:C-DEPLOYMENT]]
-            let ssot = if global || !is_local {
-                // Global SSOT: ~/.skillc/runtime/<skill>/
-                global_runtime_store()?.join(&skill_name)
+            if reproducible_check {
+                return check_reproducible(&source, &skill_name);
+            }
+
+            let ssot = ssot_dir(&skill_name, global, is_local, project_root.as_deref())?;
+
+            // Deploy to agent directories (project-local if applicable)
+            let deploy_root = if is_local {
+                project_root.as_deref()
             } else {
-                // Local SSOT: {project}/.skillc/runtime/{skill}/
-                match project_root.as_ref() {
-                    Some(r) => skillc::util::project_skill_runtime_dir(r, &skill_name),
-                    None => global_runtime_store()?.join(&skill_name),
-                }
+                None
             };
 
-            // Compile to SSOT
-            skillc::compile(&source, &ssot)?;
-
-            // Build output summary
             let scope = if is_local && project_root.is_some() {
                 "project"
             } else {
                 "global"
             };
-            println!("Built {} ({})", skill_name, scope);
+
+            if check {
+                // Report staleness without touching runtime or agent dirs
+                let mut outdated = skillc::compiler::needs_rebuild(&source, &ssot)?;
+                for t in &target {
+                    if deploy::current_deployment(&ssot, t, &skill_name, copy, deploy_root)?
+                        .is_none()
+                    {
+                        outdated = true;
+                    }
+                }
+                if outdated {
+                    return Err(skillc::SkillcError::BuildOutdated(skill_name));
+                }
+                println!("{} is up to date ({})", skill_name, scope);
+                return Ok(());
+            }
+
+            // Compile to SSOT, rendering native files into the staged build
+            let native_targets = deploy::native_targets(&ssot, &target, native);
+            let mut report = skillc::compile_with_report(
+                &source,
+                &ssot,
+                &skillc::CompileOptions {
+                    force,
+                    native: native_targets.clone(),
+                    ..Default::default()
+                },
+            )?;
+
+            report.deployments =
+                deploy::deploy_targets(&ssot, &target, &skill_name, copy, deploy_root, force)?;
+            report.deployments.extend(deploy::deploy_native(
+                &ssot,
+                &native_targets,
+                &skill_name,
+                deploy_root,
+            )?);
+
+            if json {
+                let output = serde_json::to_string_pretty(&report).map_err(|e| {
+                    skillc::SkillcError::Internal(format!("failed to serialize report: {}", e))
+                })?;
+                println!("{}", output);
+                return Ok(());
+            }
+
+            // Build output summary
+            match report.status {
+                CompileStatus::Built => println!("Built {} ({})", skill_name, scope),
+                CompileStatus::UpToDate => {
+                    println!("{} is up to date ({})", skill_name, scope)
+                }
+            }
             println!("  Source:  {}", source.display());
             println!("  Runtime: {}", ssot.display());
-
-            // Deploy to agent directories (project-local if applicable)
-            let deploy_root = if is_local {
-                project_root.as_deref()
-            } else {
-                None
-            };
-            for t in &target {
-                let result = deploy::deploy_to_agent(&ssot, t, &skill_name, copy, deploy_root)?;
-                let method_str = match result.method {
-                    DeployMethod::Symlink => "symlink",
-                    DeployMethod::Junction => "junction",
-                    DeployMethod::Copy => "copy",
-                };
-                println!("  Deploy:  {} ({})", result.target.display(), method_str);
+            print_deployments(&report.deployments);
+
+            if watch {
+                watch_and_rebuild(
+                    &source,
+                    &ssot,
+                    &skill_name,
+                    &WatchDeploy {
+                        targets: &target,
+                        native: &native_targets,
+                        copy,
+                        root: deploy_root,
+                    },
+                    is_direct_path.then_some((path.as_path(), global)),
+                );
             }
         }
 
@@ -543,6 +1066,9 @@ fn run(cli: Cli) -> skillc::Result<()> {
         Commands::Search {
             skill,
             query,
+            all,
+            raw,
+            phrase,
             limit,
             format,
         } => {
@@ -550,7 +1076,26 @@ fn run(cli: Cli) -> skillc::Result<()> {
                 SearchFormat::Json => OutputFormat::Json,
                 SearchFormat::Text => OutputFormat::Text,
             };
-            let output = skillc::search(&skill, &query, limit, output_format)?;
+            let mode = if raw {
+                QueryMode::Raw
+            } else if phrase {
+                QueryMode::Phrase
+            } else {
+                QueryMode::Words
+            };
+            let output = match (all, skill, query) {
+                (true, Some(query), None) => {
+                    skillc::search_all(&query, mode, limit, output_format)?
+                }
+                (false, Some(skill), Some(query)) => {
+                    skillc::search(&skill, &query, mode, limit, output_format)?
+                }
+                _ => {
+                    return Err(skillc::SkillcError::InvalidOption(
+                        "--all with a skill name".to_string(),
+                    ));
+                }
+            };
             println!("{}", output);
         }
 
@@ -585,6 +1130,167 @@ fn run(cli: Cli) -> skillc::Result<()> {
             })?;
         }
 
+        Commands::Diff { skill, format } => {
+            let report = skillc::diff(&skill)?;
+            println!("{}", skillc::format_diff(&report, format)?);
+        }
+
+        Commands::Verify {
+            skill,
+            target,
+            format,
+        } => {
+            let report = skillc::verify(&skill, &target)?;
+            println!("{}", skillc::format_verify(&report, format)?);
+
+            if report.has_drift() {
+                return Err(skillc::SkillcError::VerifyDrift(report.skill));
+            }
+        }
+
+        Commands::Doctor { fix, format } => {
+            let report = skillc::doctor(&skillc::DoctorOptions { fix })?;
+            println!("{}", skillc::format_doctor(&report, format)?);
+
+            if report.unfixed() > 0 {
+                return Err(skillc::SkillcError::DoctorFoundProblems(report.unfixed()));
+            }
+        }
+
+        Commands::Undeploy {
+            skill,
+            global,
+            target,
+            dry_run,
+            format,
+        } => {
+            let result = skillc::undeploy(
+                &skill,
+                &skillc::UndeployOptions {
+                    global,
+                    targets: target,
+                    dry_run,
+                },
+            )?;
+            println!("{}", skillc::remove::format_undeploy(&result, format)?);
+        }
+
+        Commands::Remove {
+            skill,
+            global,
+            logs,
+            dry_run,
+            format,
+        } => {
+            let result = skillc::remove(
+                &skill,
+                &skillc::RemoveOptions {
+                    global,
+                    logs,
+                    dry_run,
+                },
+            )?;
+            println!("{}", skillc::remove::format_remove(&result, format)?);
+        }
+
+        Commands::Rollback {
+            skill,
+            to,
+            list,
+            format,
+        } => {
+            if list {
+                let history = skillc::history(&skill)?;
+                println!("{}", skillc::history::format_history(&history, format)?);
+            } else {
+                let result = skillc::rollback(&skill, &skillc::RollbackOptions { to })?;
+                println!("{}", skillc::history::format_rollback(&result, format)?);
+            }
+        }
+
+        Commands::Pack {
+            skill,
+            output,
+            format,
+        } => {
+            let result = skillc::pack(&skill, &PackOptions { output, format })?;
+            println!(
+                "Packed {} ({} files) → {}",
+                result.skill,
+                result.manifest.files.len(),
+                result.archive.display()
+            );
+        }
+
+        Commands::Install {
+            archive,
+            global,
+            force,
+            build,
+            target,
+            copy,
+        } => {
+            // Verify into a scratch directory, then reuse the build import flow
+            let staging = std::env::temp_dir().join(format!(
+                "skillc-install-{}-{:016x}",
+                std::process::id(),
+                rand::random::<u64>()
+            ));
+            let imported = skillc::unpack(&archive, &staging).and_then(|manifest| {
+                let path = staging.join(&manifest.skill);
+                let name = extract_skill_name(&path)?;
+                let imported = import_skill(&path, &name, global, force, false)?;
+                Ok((name, manifest.files.len(), imported))
+            });
+            let _ = fs::remove_dir_all(&staging);
+            let (skill_name, file_count, imported) = imported?;
+
+            if imported.copied {
+                println!(
+                    "Installed {} ({} files) → {} ({})",
+                    skill_name,
+                    file_count,
+                    imported.source.display(),
+                    imported.scope()
+                );
+            } else {
+                println!("{} is already installed ({})", skill_name, imported.scope());
+            }
+
+            if build {
+                let ssot = ssot_dir(
+                    &skill_name,
+                    global,
+                    imported.is_local,
+                    imported.project_root.as_deref(),
+                )?;
+                let deploy_root = if imported.is_local {
+                    imported.project_root.as_deref()
+                } else {
+                    None
+                };
+                let native = deploy::native_targets(&ssot, &target, false);
+                skillc::compile_with_options(
+                    &imported.source,
+                    &ssot,
+                    &skillc::CompileOptions {
+                        native: native.clone(),
+                        ..Default::default()
+                    },
+                )?;
+                println!("  Runtime: {}", ssot.display());
+                deploy_targets(
+                    &ssot,
+                    &target,
+                    &native,
+                    &skill_name,
+                    copy,
+                    deploy_root,
+                    force,
+                )?;
+            }
+        }
+
         Commands::Mcp => {
             // MCP server requires async runtime
             let rt = tokio::runtime::Runtime::new().map_err(|e| {
@@ -610,21 +1316,7 @@ fn run(cli: Cli) -> skillc::Result<()> {
             };
 
             let result = skillc::lint(&skill_path, LintOptions { force })?;
-
-            // Print diagnostics to stderr per [[RFC-0005:C-CODES]]
-            for diag in &result.diagnostics {
-                eprintln!("{}", diag);
-            }
-
-            // Print summary
-            if result.error_count == 0 && result.warning_count == 0 {
-                println!("Lint passed: no issues found");
-            } else {
-                println!(
-                    "Lint complete: {} error(s), {} warning(s)",
-                    result.error_count, result.warning_count
-                );
-            }
+            print_lint_result(&result);
 
             // Exit with error if any errors per [[RFC-0008:C-DIAGNOSTICS]]
             if result.error_count > 0 {
@@ -9,7 +9,7 @@ use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 ///
 /// Returns the content after the closing `---` delimiter, or the original
 /// content if no valid frontmatter is present.
-fn strip_frontmatter(content: &str) -> &str {
+pub(crate) fn strip_frontmatter(content: &str) -> &str {
     // Check for opening delimiter
     let after_open = content
         .strip_prefix("---\r\n")
@@ -29,6 +29,12 @@ fn strip_frontmatter(content: &str) -> &str {
     }
 }
 
+/// Check if a line opens or closes a fenced code block (` ``` ` or `~~~`).
+pub(crate) fn is_code_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
 /// Represents a link extracted from markdown content.
 #[derive(Debug, Clone)]
 pub struct ExtractedLink {
@@ -3,9 +3,11 @@
 //! Provides structured agent interface via Model Context Protocol.
 //! Uses the official Rust SDK from <https://github.com/modelcontextprotocol/rust-sdk>
 
-use crate::config::get_target_path;
+use crate::config::{TargetSpec, find_project_skill};
 use crate::resolver::resolve_skill;
-use crate::{InitOptions, LintOptions, OutputFormat, QueryType, StatsOptions};
+use crate::{
+    CompileOptions, InitOptions, LintOptions, OutputFormat, QueryMode, QueryType, StatsOptions,
+};
 use rmcp::ErrorData as McpError;
 use rmcp::handler::server::tool::ToolRouter;
 use rmcp::handler::server::wrapper::Parameters;
@@ -53,7 +55,7 @@ pub struct ShowParams {
 pub struct OpenParams {
     /// Name of the skill
     pub skill: String,
-    /// Path to the file within the skill
+    /// Path to the file within the skill (`<file>.csv#<row>` for one CSV row)
     pub path: String,
     /// Maximum lines to return (optional)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -86,11 +88,33 @@ pub struct SearchParams {
     pub skill: String,
     /// Search query
     pub query: String,
+    /// Treat the query as FTS5 syntax: OR, NOT, prefix*, NEAR(), column:term (default: false)
+    #[serde(default)]
+    pub raw: bool,
+    /// Require the words to appear adjacent and in order (default: false)
+    #[serde(default)]
+    pub phrase: bool,
     /// Maximum results (optional, default: 10)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
 }
 
+/// Parameters for skc_search_all tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchAllParams {
+    /// Search query
+    pub query: String,
+    /// Treat the query as FTS5 syntax: OR, NOT, prefix*, NEAR(), column:term (default: false)
+    #[serde(default)]
+    pub raw: bool,
+    /// Require the words to appear adjacent and in order (default: false)
+    #[serde(default)]
+    pub phrase: bool,
+    /// Maximum results across all skills (optional, default: 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
 /// Parameters for skc_stats tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct StatsParams {
@@ -267,9 +291,43 @@ impl SkillcServer {
         annotations(read_only_hint = true)
     )]
     async fn skc_search(&self, params: Parameters<SearchParams>) -> McpResult<CallToolResult> {
+        let Some(mode) = query_mode(params.0.raw, params.0.phrase) else {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "error: raw and phrase cannot be used together",
+            )]));
+        };
         match crate::search(
             &params.0.skill,
             &params.0.query,
+            mode,
+            params.0.limit.unwrap_or(10),
+            OutputFormat::Json,
+        ) {
+            Ok(json) => Ok(CallToolResult::success(vec![Content::text(json)])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "error: {}",
+                e
+            ))])),
+        }
+    }
+
+    /// Search across every built skill
+    #[tool(
+        description = "Full-text search across all built skills in the project and global stores. Returns JSON array of {skill, file, section, snippet, score} with scores normalized to the best match overall.",
+        annotations(read_only_hint = true)
+    )]
+    async fn skc_search_all(
+        &self,
+        params: Parameters<SearchAllParams>,
+    ) -> McpResult<CallToolResult> {
+        let Some(mode) = query_mode(params.0.raw, params.0.phrase) else {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "error: raw and phrase cannot be used together",
+            )]));
+        };
+        match crate::search_all(
+            &params.0.query,
+            mode,
             params.0.limit.unwrap_or(10),
             OutputFormat::Json,
         ) {
@@ -314,34 +372,58 @@ impl SkillcServer {
         }
     }
 
-    /// Compile a skill
+    /// Compile a skill and deploy it, as `skc build -o json` does
     #[tool(
-        description = "Compile skill to target platform (claude, cursor). Returns {success, output_path}."
+        description = "Compile a skill to its runtime and deploy it to a target agent (default: claude). Build hooks run only when the global config sets build.mcp_hooks. Returns {skill, status, source, runtime, source_hash, headings, files, index, stub_lines, sections_omitted, references_omitted, deployments}."
     )]
     async fn skc_build(&self, params: Parameters<BuildParams>) -> McpResult<CallToolResult> {
         // Resolve source using proper resolver (checks project store first)
         let resolved = resolve_skill(&params.0.skill).map_err(to_mcp_err)?;
-        let source = resolved.source_dir;
-
-        // Resolve runtime (use target param or default to claude)
-        let target = params.0.target.as_deref().unwrap_or("claude");
-        let skill_name = source
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&params.0.skill);
-        let runtime = get_target_path(target)
-            .map_err(to_mcp_err)?
-            .join(skill_name);
-
-        match crate::compile(&source, &runtime) {
-            Ok(()) => {
-                let result = serde_json::json!({
-                    "success": true,
-                    "output_path": runtime.to_string_lossy()
-                });
-                Ok(CallToolResult::success(vec![Content::text(
-                    result.to_string(),
-                )]))
+        // The server outlives config edits, so read the targets afresh
+        let target = TargetSpec::resolve(
+            params.0.target.as_deref().unwrap_or("claude"),
+            &crate::config::configured_targets(),
+        );
+
+        // Project skills deploy to the project's agent directories
+        let deploy_root = find_project_skill(&resolved.name)
+            .filter(|(path, _)| path.canonicalize().ok().as_ref() == Some(&resolved.source_dir))
+            .map(|(_, root)| root);
+
+        // Native files already deployed from this runtime are kept current
+        let native = crate::deploy::native_targets(&resolved.runtime_dir, &[], false);
+        let result = crate::compile_with_report(
+            &resolved.source_dir,
+            &resolved.runtime_dir,
+            &CompileOptions {
+                skip_hooks: !crate::config::get_mcp_hooks(),
+                native: native.clone(),
+                ..Default::default()
+            },
+        )
+        .and_then(|mut report| {
+            report.deployments = crate::deploy::deploy_targets(
+                &resolved.runtime_dir,
+                &[target],
+                &resolved.name,
+                false,
+                deploy_root.as_deref(),
+                false,
+            )?;
+            report.deployments.extend(crate::deploy::deploy_native(
+                &resolved.runtime_dir,
+                &native,
+                &resolved.name,
+                deploy_root.as_deref(),
+            )?);
+            Ok(report)
+        });
+
+        match result {
+            Ok(report) => {
+                let json = serde_json::to_string(&report)
+                    .unwrap_or_else(|e| format!(r#"{{"error": "serialization failed: {}"}}"#, e));
+                Ok(CallToolResult::success(vec![Content::text(json)]))
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "error: {}",
@@ -455,6 +537,16 @@ impl SkillcServer {
     }
 }
 
+/// Query mode for the `raw` and `phrase` search flags, or `None` if both are set.
+fn query_mode(raw: bool, phrase: bool) -> Option<QueryMode> {
+    match (raw, phrase) {
+        (true, true) => None,
+        (true, false) => Some(QueryMode::Raw),
+        (false, true) => Some(QueryMode::Phrase),
+        (false, false) => Some(QueryMode::Words),
+    }
+}
+
 // Implement the server handler
 #[tool_handler]
 impl ServerHandler for SkillcServer {
@@ -0,0 +1,472 @@
+//! Skill archives (`skc pack`, `skc install`).
+//!
+//! An archive holds one skill's sources under `<skill>/` plus a
+//! [`PACK_MANIFEST`] at the root listing every file with its SHA-256. It is
+//! a transfer format between machines without a shared repository, not a
+//! publishing format. Files whose includes reach outside the skill are
+//! packed with those includes expanded, so the archive is self-contained.
+
+use crate::compiler::{
+    FileHash, build_timestamp, combine_file_hashes, compute_file_hashes, list_md_files, sha256_hex,
+};
+use crate::error::{Result, SkillcError};
+use crate::include;
+use crate::resolver::resolve_skill;
+use crate::verbose;
+use clap::ValueEnum;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Name of the manifest at the archive root.
+pub const PACK_MANIFEST: &str = "skillc-pack.json";
+
+/// Current pack manifest version.
+pub const PACK_VERSION: u32 = 1;
+
+/// Archive container format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// Gzip-compressed tarball (`.tar.gz`)
+    TarGz,
+    /// Zip archive (`.zip`)
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// File extension, without a leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+
+    /// Infer the format from a file name, if it has a known extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Manifest stored at the root of a skill archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub skill: String,
+    pub version: u32,
+    pub packed_at: String,
+    /// Aggregate hash of `files`, computed as for the build manifest
+    pub source_hash: String,
+    /// Every packed file, relative to the skill root, sorted by path
+    pub files: Vec<FileHash>,
+}
+
+/// Options for `skc pack`.
+#[derive(Debug, Clone, Default)]
+pub struct PackOptions {
+    /// Archive path (default: `<skill>.<ext>` in the current directory)
+    pub output: Option<PathBuf>,
+    /// Container format (default: inferred from `output`, else tar.gz)
+    pub format: Option<ArchiveFormat>,
+}
+
+/// Result of packing a skill.
+#[derive(Debug)]
+pub struct PackResult {
+    pub skill: String,
+    pub archive: PathBuf,
+    pub format: ArchiveFormat,
+    pub manifest: PackManifest,
+}
+
+/// Pack a skill's sources into a single archive.
+pub fn pack(skill: &str, options: &PackOptions) -> Result<PackResult> {
+    let resolved = resolve_skill(skill)?;
+    let format = options
+        .format
+        .or_else(|| options.output.as_deref().and_then(ArchiveFormat::from_path))
+        .unwrap_or(ArchiveFormat::TarGz);
+    let archive = options
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("{}.{}", resolved.name, format.extension())));
+
+    let files = collect_files(&resolved.source_dir)?;
+    let hashes: Vec<FileHash> = files
+        .iter()
+        .map(|(path, content)| FileHash {
+            path: path.clone(),
+            sha256: sha256_hex(content),
+        })
+        .collect();
+    let manifest = PackManifest {
+        skill: resolved.name.clone(),
+        version: PACK_VERSION,
+        packed_at: build_timestamp()?,
+        source_hash: combine_file_hashes(&hashes),
+        files: hashes,
+    };
+
+    let mut entries: Vec<(String, Vec<u8>)> = vec![(
+        PACK_MANIFEST.to_string(),
+        serde_json::to_vec_pretty(&manifest)?,
+    )];
+    for (path, content) in files {
+        entries.push((format!("{}/{}", manifest.skill, path), content));
+    }
+
+    verbose!(
+        "pack: writing {} entries to {}",
+        entries.len(),
+        archive.display()
+    );
+    if let Some(parent) = archive.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    match format {
+        ArchiveFormat::TarGz => write_tar_gz(&archive, &entries)?,
+        ArchiveFormat::Zip => write_zip(&archive, &entries)?,
+    }
+
+    Ok(PackResult {
+        skill: resolved.name,
+        archive,
+        format,
+        manifest,
+    })
+}
+
+/// Read the files to pack, keyed by `/`-separated path relative to the skill.
+fn collect_files(source_dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let expansion = include::expand_includes(source_dir, &list_md_files(source_dir)?)?;
+    let self_contained = expansion.dependencies.is_empty();
+
+    let mut files = BTreeMap::new();
+    for file in compute_file_hashes(source_dir)? {
+        let relative = PathBuf::from(&file.path);
+        let content = match expansion.files.get(&relative) {
+            Some(expanded) if !self_contained => expanded.clone().into_bytes(),
+            _ => fs::read(source_dir.join(&relative))?,
+        };
+        files.insert(file.path.replace('\\', "/"), content);
+    }
+    Ok(files)
+}
+
+fn write_tar_gz(path: &Path, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let encoder = GzEncoder::new(File::create(path)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for (name, content) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_cksum();
+        builder.append_data(&mut header, name, content.as_slice())?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn write_zip(path: &Path, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let mut writer = zip::ZipWriter::new(File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    for (name, content) in entries {
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(|e| SkillcError::Internal(format!("Failed to write zip entry: {}", e)))?;
+        writer.write_all(content)?;
+    }
+    writer
+        .finish()
+        .map_err(|e| SkillcError::Internal(format!("Failed to finish zip archive: {}", e)))?;
+    Ok(())
+}
+
+/// Verify an archive and extract the skill into `dest_dir/<skill>`.
+///
+/// Every file listed in the pack manifest must be present with a matching
+/// checksum, and the archive must contain nothing else. Nothing is written
+/// unless verification passes. Returns E060 on any mismatch.
+pub fn unpack(archive: &Path, dest_dir: &Path) -> Result<PackManifest> {
+    let mut entries = read_entries(archive)?;
+
+    let manifest_bytes = entries
+        .remove(PACK_MANIFEST)
+        .ok_or_else(|| SkillcError::InvalidArchive(format!("missing {}", PACK_MANIFEST)))?;
+    let manifest: PackManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| SkillcError::InvalidArchive(format!("malformed {}: {}", PACK_MANIFEST, e)))?;
+    if manifest.version > PACK_VERSION {
+        return Err(SkillcError::InvalidArchive(format!(
+            "unsupported pack version {}",
+            manifest.version
+        )));
+    }
+    if !is_safe_path(&manifest.skill) || manifest.skill.contains('/') {
+        return Err(SkillcError::InvalidArchive(format!(
+            "invalid skill name '{}'",
+            manifest.skill
+        )));
+    }
+
+    let prefix = format!("{}/", manifest.skill);
+    let mut files = BTreeMap::new();
+    for (name, content) in entries {
+        let relative = name
+            .strip_prefix(&prefix)
+            .ok_or_else(|| SkillcError::InvalidArchive(format!("unexpected entry '{}'", name)))?;
+        files.insert(relative.to_string(), content);
+    }
+
+    for file in &manifest.files {
+        let content = files
+            .get(&file.path)
+            .ok_or_else(|| SkillcError::InvalidArchive(format!("missing file '{}'", file.path)))?;
+        if sha256_hex(content) != file.sha256 {
+            return Err(SkillcError::InvalidArchive(format!(
+                "checksum mismatch for '{}'",
+                file.path
+            )));
+        }
+    }
+    if let Some(extra) = files
+        .keys()
+        .find(|path| !manifest.files.iter().any(|f| &f.path == *path))
+    {
+        return Err(SkillcError::InvalidArchive(format!(
+            "file '{}' is not in the manifest",
+            extra
+        )));
+    }
+    if combine_file_hashes(&manifest.files) != manifest.source_hash {
+        return Err(SkillcError::InvalidArchive(
+            "source hash does not match the file list".to_string(),
+        ));
+    }
+    if !files.contains_key("SKILL.md") {
+        return Err(SkillcError::InvalidArchive("missing SKILL.md".to_string()));
+    }
+
+    let skill_dir = dest_dir.join(&manifest.skill);
+    for (path, content) in &files {
+        let target = skill_dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, content)?;
+    }
+    verbose!(
+        "install: verified {} file(s) into {}",
+        files.len(),
+        skill_dir.display()
+    );
+
+    Ok(manifest)
+}
+
+/// Read every regular file in an archive, keyed by entry name.
+///
+/// The format is detected from the file's magic bytes. Entries with absolute
+/// paths or `..` components are rejected.
+fn read_entries(archive: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut magic = [0u8; 4];
+    let read = File::open(archive)?.read(&mut magic)?;
+    let entries = match &magic[..read] {
+        [0x1f, 0x8b, ..] => read_tar_gz(archive)?,
+        [b'P', b'K', ..] => read_zip(archive)?,
+        _ => {
+            return Err(SkillcError::InvalidArchive(format!(
+                "'{}' is not a .tar.gz or .zip file",
+                archive.display()
+            )));
+        }
+    };
+
+    let mut files = BTreeMap::new();
+    for (name, content) in entries {
+        let name = name.replace('\\', "/");
+        if !is_safe_path(&name) {
+            return Err(SkillcError::InvalidArchive(format!(
+                "unsafe entry path '{}'",
+                name
+            )));
+        }
+        if files.insert(name.clone(), content).is_some() {
+            return Err(SkillcError::InvalidArchive(format!(
+                "duplicate entry '{}'",
+                name
+            )));
+        }
+    }
+    Ok(files)
+}
+
+fn read_tar_gz(archive: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    let invalid = |e: std::io::Error| SkillcError::InvalidArchive(e.to_string());
+    let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+    let mut entries = Vec::new();
+    for entry in tar.entries().map_err(invalid)? {
+        let mut entry = entry.map_err(invalid)?;
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            continue;
+        }
+        let name = entry.path().map_err(invalid)?.to_string_lossy().to_string();
+        if !kind.is_file() {
+            return Err(SkillcError::InvalidArchive(format!(
+                "'{}' is not a regular file",
+                name
+            )));
+        }
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).map_err(invalid)?;
+        entries.push((name, content));
+    }
+    Ok(entries)
+}
+
+fn read_zip(archive: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    let invalid = |e: zip::result::ZipError| SkillcError::InvalidArchive(e.to_string());
+    let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(invalid)?;
+    let mut entries = Vec::new();
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i).map_err(invalid)?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        entries.push((name, content));
+    }
+    Ok(entries)
+}
+
+/// True if `path` is relative and has no `.`, `..`, or root components.
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_entries(path: &Path, format: ArchiveFormat, entries: &[(String, Vec<u8>)]) {
+        match format {
+            ArchiveFormat::TarGz => write_tar_gz(path, entries).expect("write tar.gz"),
+            ArchiveFormat::Zip => write_zip(path, entries).expect("write zip"),
+        }
+    }
+
+    fn manifest_for(files: &[(&str, &[u8])]) -> PackManifest {
+        let files: Vec<FileHash> = files
+            .iter()
+            .map(|(path, content)| FileHash {
+                path: path.to_string(),
+                sha256: sha256_hex(content),
+            })
+            .collect();
+        PackManifest {
+            skill: "my-skill".to_string(),
+            version: PACK_VERSION,
+            packed_at: "2024-01-01T00:00:00+00:00".to_string(),
+            source_hash: combine_file_hashes(&files),
+            files,
+        }
+    }
+
+    fn entries_for(manifest: &PackManifest, files: &[(&str, &[u8])]) -> Vec<(String, Vec<u8>)> {
+        let mut entries = vec![(
+            PACK_MANIFEST.to_string(),
+            serde_json::to_vec(manifest).expect("serialize"),
+        )];
+        for (path, content) in files {
+            entries.push((format!("my-skill/{}", path), content.to_vec()));
+        }
+        entries
+    }
+
+    #[test]
+    fn test_unpack_round_trip() {
+        let temp = TempDir::new().expect("create temp dir");
+        let files: &[(&str, &[u8])] = &[
+            ("SKILL.md", b"---\nname: my-skill\n---\n"),
+            ("refs/guide.md", b"# Guide\n"),
+        ];
+        let manifest = manifest_for(files);
+
+        for format in [ArchiveFormat::TarGz, ArchiveFormat::Zip] {
+            let archive = temp.path().join(format!("my-skill.{}", format.extension()));
+            write_entries(&archive, format, &entries_for(&manifest, files));
+            assert_eq!(ArchiveFormat::from_path(&archive), Some(format));
+
+            let dest = temp.path().join(format!("dest-{:?}", format));
+            let unpacked = unpack(&archive, &dest).expect("unpack");
+            assert_eq!(unpacked.skill, "my-skill");
+            assert_eq!(
+                fs::read(dest.join("my-skill/refs/guide.md")).expect("read guide"),
+                b"# Guide\n"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpack_rejects_tampered_archives() {
+        let temp = TempDir::new().expect("create temp dir");
+        let files: &[(&str, &[u8])] = &[("SKILL.md", b"---\nname: my-skill\n---\n")];
+        let manifest = manifest_for(files);
+        let archive = temp.path().join("my-skill.tar.gz");
+        let dest = temp.path().join("dest");
+
+        let tampered: &[(&str, &[u8])] = &[("SKILL.md", b"changed")];
+        write_entries(
+            &archive,
+            ArchiveFormat::TarGz,
+            &entries_for(&manifest, tampered),
+        );
+        let err = unpack(&archive, &dest).expect_err("checksum mismatch");
+        assert!(err.to_string().contains("error[E060]"));
+        assert!(err.to_string().contains("checksum mismatch for 'SKILL.md'"));
+
+        let mut entries = entries_for(&manifest, files);
+        entries.push(("my-skill/extra.md".to_string(), b"# Extra\n".to_vec()));
+        write_entries(&archive, ArchiveFormat::TarGz, &entries);
+        let err = unpack(&archive, &dest).expect_err("extra file");
+        assert!(
+            err.to_string()
+                .contains("'extra.md' is not in the manifest")
+        );
+
+        write_entries(&archive, ArchiveFormat::TarGz, &entries[1..]);
+        let err = unpack(&archive, &dest).expect_err("missing manifest");
+        assert!(err.to_string().contains("missing skillc-pack.json"));
+
+        assert!(!dest.exists(), "nothing is written on failure");
+    }
+
+    #[test]
+    fn test_safe_paths() {
+        assert!(is_safe_path("my-skill/refs/guide.md"));
+        assert!(!is_safe_path("../outside.md"));
+        assert!(!is_safe_path("my-skill/../../outside.md"));
+        assert!(!is_safe_path("/etc/passwd"));
+        assert!(!is_safe_path(""));
+    }
+}
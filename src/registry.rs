@@ -0,0 +1,266 @@
+//! Deployment registry (`~/.skillc/registry.json`) per [[RFC-0001:C-DEPLOYMENT]].
+//!
+//! Every deploy records where a skill went, so `skc list`, `skc undeploy`,
+//! and `skc verify` also find deployments outside the standard agent
+//! directories (custom `--target` paths). Writers hold an exclusive lock on
+//! a sidecar `registry.json.lock` for the whole read-modify-write and replace
+//! the registry by rename, so concurrent builds neither drop each other's
+//! entries nor expose a half-written file to readers.
+
+use crate::config::{ensure_dir, global_registry_path};
+use crate::deploy::DeployMethod;
+use crate::error::{Result, SkillcError};
+use crate::verbose;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// Current registry format version.
+pub const REGISTRY_VERSION: u32 = 1;
+
+/// One agent directory entry created by a deploy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub skill: String,
+    /// Runtime (SSOT) the entry was deployed from
+    pub ssot: PathBuf,
+    /// Target name or custom path
+    pub target: String,
+    /// Skill entry in the agent directory
+    pub path: PathBuf,
+    pub method: DeployMethod,
+    /// When this content was deployed (RFC 3339)
+    pub deployed_at: String,
+    /// Source hash of the runtime that was deployed
+    pub source_hash: String,
+}
+
+/// Contents of the registry file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Registry {
+    pub version: u32,
+    #[serde(default)]
+    pub deployments: Vec<RegistryEntry>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            version: REGISTRY_VERSION,
+            deployments: Vec::new(),
+        }
+    }
+}
+
+impl Registry {
+    /// Read the registry. A missing file is an empty registry.
+    pub fn load() -> Result<Self> {
+        read_registry(&global_registry_path()?)
+    }
+
+    /// Deployments made from the runtime at `ssot`.
+    pub fn for_runtime<'a>(&'a self, ssot: &'a Path) -> impl Iterator<Item = &'a RegistryEntry> {
+        self.deployments
+            .iter()
+            .filter(move |entry| same_path(&entry.ssot, ssot))
+    }
+
+    /// The entry recorded for an agent directory path, if any.
+    pub fn entry_at(&self, path: &Path) -> Option<&RegistryEntry> {
+        self.deployments
+            .iter()
+            .find(|entry| same_path(&entry.path, path))
+    }
+}
+
+/// Record deployments, replacing any earlier entry for the same agent path.
+///
+/// An entry whose runtime, method, and source hash are unchanged keeps its
+/// original `deployed_at`.
+pub fn record(entries: Vec<RegistryEntry>) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    update(|registry| {
+        for mut entry in entries {
+            if let Some(old) = registry
+                .deployments
+                .iter()
+                .find(|old| same_path(&old.path, &entry.path))
+                && same_path(&old.ssot, &entry.ssot)
+                && old.method == entry.method
+                && old.source_hash == entry.source_hash
+            {
+                entry.deployed_at = old.deployed_at.clone();
+            }
+            registry
+                .deployments
+                .retain(|old| !same_path(&old.path, &entry.path));
+            registry.deployments.push(entry);
+        }
+    })
+}
+
+/// Drop the entries for these agent paths.
+pub fn forget(paths: &[PathBuf]) -> Result<()> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    update(|registry| {
+        registry
+            .deployments
+            .retain(|entry| !paths.iter().any(|p| same_path(&entry.path, p)));
+    })
+}
+
+/// Apply `change` to the registry under an exclusive lock and write it back.
+fn update<F: FnOnce(&mut Registry)>(change: F) -> Result<()> {
+    let path = global_registry_path()?;
+    if let Some(parent) = path.parent() {
+        ensure_dir(parent)?;
+    }
+
+    let lock_path = path.with_extension("json.lock");
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)?;
+    lock.lock()?;
+
+    let mut registry = read_registry(&path)?;
+    change(&mut registry);
+    registry.version = REGISTRY_VERSION;
+    registry
+        .deployments
+        .sort_by(|a, b| a.skill.cmp(&b.skill).then_with(|| a.path.cmp(&b.path)));
+
+    let json = serde_json::to_string_pretty(&registry)?;
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&tmp, json + "\n")?;
+    fs::rename(&tmp, &path)?;
+    verbose!("registry: wrote {}", path.display());
+    Ok(())
+}
+
+fn read_registry(path: &Path) -> Result<Registry> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            SkillcError::Internal(format!("invalid registry {}: {}", path.display(), e))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Registry::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Compare paths by location.
+///
+/// See [`location`]: the last component is never resolved, so two links
+/// into the same runtime are different paths.
+pub(crate) fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || location(a) == location(b)
+}
+
+/// Absolute form of `path` with its parent directory's symlinks resolved.
+///
+/// The last component is kept as is, so a deployment link is identified by
+/// where it lives rather than by the runtime it points to.
+pub(crate) fn location(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    match (path.parent().map(Path::canonicalize), path.file_name()) {
+        (Some(Ok(parent)), Some(name)) => parent.join(name),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, hash: &str, deployed_at: &str) -> RegistryEntry {
+        RegistryEntry {
+            skill: "my-skill".to_string(),
+            ssot: PathBuf::from("/runtime/my-skill"),
+            target: "claude".to_string(),
+            path: PathBuf::from(path),
+            method: DeployMethod::Symlink,
+            deployed_at: deployed_at.to_string(),
+            source_hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_forget() {
+        let temp = TempDir::new().expect("create temp dir");
+        temp_env::with_var("SKILLC_HOME", Some(temp.path()), || {
+            record(vec![entry("/a/my-skill", "h1", "t1")]).expect("record");
+            record(vec![
+                entry("/a/my-skill", "h1", "t2"),
+                entry("/b/my-skill", "h1", "t2"),
+            ])
+            .expect("record again");
+
+            let registry = Registry::load().expect("load");
+            assert_eq!(registry.deployments.len(), 2);
+            // Unchanged content keeps its deploy time
+            assert_eq!(registry.deployments[0].deployed_at, "t1");
+            assert_eq!(
+                registry.for_runtime(Path::new("/runtime/my-skill")).count(),
+                2
+            );
+
+            record(vec![entry("/a/my-skill", "h2", "t3")]).expect("record new content");
+            forget(&[PathBuf::from("/b/my-skill")]).expect("forget");
+            let registry = Registry::load().expect("load");
+            assert_eq!(registry.deployments, vec![entry("/a/my-skill", "h2", "t3")]);
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_record_keeps_links_to_the_same_runtime() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path().join("runtime").join("my-skill");
+        fs::create_dir_all(&runtime).expect("create runtime");
+        let claude = temp.path().join("claude").join("my-skill");
+        let codex = temp.path().join("codex").join("my-skill");
+        for link in [&claude, &codex] {
+            fs::create_dir_all(link.parent().expect("parent")).expect("create agent dir");
+            std::os::unix::fs::symlink(&runtime, link).expect("create link");
+        }
+        assert!(!same_path(&claude, &codex));
+        assert!(same_path(
+            &claude,
+            &temp.path().join("claude/../claude/my-skill")
+        ));
+
+        temp_env::with_var("SKILLC_HOME", Some(temp.path()), || {
+            record(vec![
+                entry(&claude.to_string_lossy(), "h", "t"),
+                entry(&codex.to_string_lossy(), "h", "t"),
+            ])
+            .expect("record");
+            let registry = Registry::load().expect("load");
+            assert_eq!(registry.deployments.len(), 2);
+            assert!(registry.entry_at(&claude).is_some());
+            assert!(registry.entry_at(&codex).is_some());
+        });
+    }
+
+    #[test]
+    fn test_concurrent_writers_keep_every_entry() {
+        let temp = TempDir::new().expect("create temp dir");
+        temp_env::with_var("SKILLC_HOME", Some(temp.path()), || {
+            std::thread::scope(|scope| {
+                for i in 0..8 {
+                    scope.spawn(move || {
+                        record(vec![entry(&format!("/agent{}/my-skill", i), "h", "t")])
+                            .expect("record");
+                    });
+                }
+            });
+            assert_eq!(Registry::load().expect("load").deployments.len(), 8);
+        });
+    }
+}
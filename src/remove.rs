@@ -0,0 +1,361 @@
+//! Undeploying and removing skills (`skc undeploy`, `skc remove`).
+//!
+//! Only agent directory entries that skillc created are touched: links into
+//! the skill's runtime, or copies whose manifest names the skill (see
+//! [`deploy::owned_deployment`]). Anything else at the same path is reported
+//! and left alone. Removed entries are dropped from the deployment registry.
+
+use crate::OutputFormat;
+use crate::config::{TargetSpec, find_project_root, global_runtime_store, global_source_store};
+use crate::deploy::{self, DeployMethod};
+use crate::error::{Result, SkillcError};
+use crate::logging::get_fallback_log_dir;
+use crate::registry::{self, Registry};
+use crate::util::{project_skill_runtime_dir, project_skills_dir, runtime_builds_dir};
+use crate::verbose;
+use crate::verify::deployment_candidates;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for [`undeploy`].
+#[derive(Debug, Default)]
+pub struct UndeployOptions {
+    /// Use the global stores even inside a project
+    pub global: bool,
+    /// Only these targets; empty means every known target
+    pub targets: Vec<TargetSpec>,
+    /// Report what would be removed without removing it
+    pub dry_run: bool,
+}
+
+/// Options for [`remove`].
+#[derive(Debug, Default)]
+pub struct RemoveOptions {
+    /// Use the global stores even inside a project
+    pub global: bool,
+    /// Also delete access logs, including fallback logs in the current directory
+    pub logs: bool,
+    /// Report what would be removed without removing it
+    pub dry_run: bool,
+}
+
+/// An agent directory entry removed (or, in a dry run, to be removed).
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovedDeployment {
+    pub target: String,
+    pub path: PathBuf,
+    pub method: DeployMethod,
+}
+
+/// Result of [`undeploy`].
+#[derive(Debug, Clone, Serialize)]
+pub struct UndeployResult {
+    pub skill: String,
+    pub dry_run: bool,
+    pub removed: Vec<RemovedDeployment>,
+    /// Entries at a deployment path that skillc did not create
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Result of [`remove`].
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoveResult {
+    pub skill: String,
+    pub dry_run: bool,
+    pub undeploy: UndeployResult,
+    /// Source, runtime (with its search index), retained build, and log directories deleted
+    pub deleted: Vec<PathBuf>,
+    /// Access log left in place because `logs` was not requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kept_logs: Option<PathBuf>,
+}
+
+/// Where a skill's source and runtime live. Either may already be gone.
+struct Located {
+    name: String,
+    source_dir: PathBuf,
+    runtime_dir: PathBuf,
+}
+
+/// Find a skill in the project stores (unless `global`), then the global stores.
+fn locate(skill: &str, global: bool) -> Result<Located> {
+    if skill.contains('/') || skill.contains('\\') {
+        return Err(SkillcError::SkillNotFound(format!(
+            "{} (use skill name, not path)",
+            skill
+        )));
+    }
+
+    let mut candidates = Vec::new();
+    if !global && let Some(root) = find_project_root() {
+        candidates.push((
+            project_skills_dir(&root).join(skill),
+            project_skill_runtime_dir(&root, skill),
+        ));
+    }
+    candidates.push((
+        global_source_store()?.join(skill),
+        global_runtime_store()?.join(skill),
+    ));
+
+    candidates
+        .into_iter()
+        .find(|(source, runtime)| source.is_dir() || runtime.is_dir())
+        .map(|(source_dir, runtime_dir)| Located {
+            name: skill.to_string(),
+            source_dir,
+            runtime_dir,
+        })
+        .ok_or_else(|| SkillcError::SkillNotFound(skill.to_string()))
+}
+
+/// Remove the agent directory entries skillc created for a skill.
+pub fn undeploy(skill: &str, options: &UndeployOptions) -> Result<UndeployResult> {
+    let located = locate(skill, options.global)?;
+    undeploy_located(&located, &options.targets, options.dry_run)
+}
+
+fn undeploy_located(
+    located: &Located,
+    targets: &[TargetSpec],
+    dry_run: bool,
+) -> Result<UndeployResult> {
+    let mut result = UndeployResult {
+        skill: located.name.clone(),
+        dry_run,
+        removed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    let registry = Registry::load().unwrap_or_default();
+    for (target, dest) in deployment_candidates(&located.runtime_dir, &located.name, targets) {
+        if !targets.is_empty() && !targets.contains(&target) {
+            continue;
+        }
+        if dest.symlink_metadata().is_err() {
+            continue;
+        }
+        // The skill itself may live in an agent directory (runtime-store fallback)
+        if dest.canonicalize().ok() == located.source_dir.canonicalize().ok() {
+            continue;
+        }
+
+        match deploy::owned_deployment(&dest, &located.runtime_dir, &located.name, &registry) {
+            Some(method) => {
+                if !dry_run {
+                    deploy::remove_deployment(&dest, method)?;
+                }
+                verbose!("undeploy: {} ({})", dest.display(), method);
+                result.removed.push(RemovedDeployment {
+                    target: target.to_string(),
+                    path: dest,
+                    method,
+                });
+            }
+            None => {
+                verbose!(
+                    "undeploy: skipping {} (not created by skillc)",
+                    dest.display()
+                );
+                result.skipped.push(dest);
+            }
+        }
+    }
+
+    if !dry_run {
+        forget_removed(&located.runtime_dir, &result.removed);
+    }
+    Ok(result)
+}
+
+/// Drop registry entries for removed deployments and for ones already gone.
+fn forget_removed(runtime_dir: &Path, removed: &[RemovedDeployment]) {
+    let mut paths: Vec<PathBuf> = removed.iter().map(|r| r.path.clone()).collect();
+    if let Ok(registry) = Registry::load() {
+        paths.extend(
+            registry
+                .for_runtime(runtime_dir)
+                .filter(|entry| entry.path.symlink_metadata().is_err())
+                .map(|entry| entry.path.clone()),
+        );
+    }
+    if let Err(e) = registry::forget(&paths) {
+        eprintln!("warning: failed to update deployment registry: {}", e);
+    }
+}
+
+/// Undeploy a skill everywhere, then delete its runtime, retained builds, and source.
+///
+/// The search index lives in the runtime and goes with it. The access log
+/// also lives there; unless `logs` is set it is kept so that `skc stats`
+/// history survives a reinstall.
+pub fn remove(skill: &str, options: &RemoveOptions) -> Result<RemoveResult> {
+    let located = locate(skill, options.global)?;
+    let undeploy = undeploy_located(&located, &[], options.dry_run)?;
+
+    let mut result = RemoveResult {
+        skill: located.name.clone(),
+        dry_run: options.dry_run,
+        undeploy,
+        deleted: Vec::new(),
+        kept_logs: None,
+    };
+
+    let meta_dir = located.runtime_dir.join(".skillc-meta");
+    let log_db = meta_dir.join("logs.db");
+    if located.runtime_dir.is_dir() {
+        if !options.logs && log_db.exists() {
+            if !options.dry_run {
+                clear_runtime_except_logs(&located.runtime_dir)?;
+            }
+            result.kept_logs = Some(log_db);
+        } else if !options.dry_run {
+            fs::remove_dir_all(&located.runtime_dir)?;
+        }
+        result.deleted.push(located.runtime_dir.clone());
+    }
+
+    let builds_dir = runtime_builds_dir(&located.runtime_dir);
+    if builds_dir.is_dir() {
+        if !options.dry_run {
+            fs::remove_dir_all(&builds_dir)?;
+        }
+        result.deleted.push(builds_dir);
+    }
+
+    if options.logs
+        && let Some(fallback) = get_fallback_log_dir(&located.name)
+        && fallback.is_dir()
+    {
+        if !options.dry_run {
+            fs::remove_dir_all(&fallback)?;
+        }
+        result.deleted.push(fallback);
+    }
+
+    if located.source_dir.is_dir() {
+        if !options.dry_run {
+            fs::remove_dir_all(&located.source_dir)?;
+        }
+        result.deleted.push(located.source_dir.clone());
+    }
+
+    Ok(result)
+}
+
+/// Delete everything in a runtime except the access log database.
+fn clear_runtime_except_logs(runtime_dir: &Path) -> Result<()> {
+    let is_log = |path: &Path| {
+        path.file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("logs.db"))
+    };
+    for entry in fs::read_dir(runtime_dir)? {
+        let path = entry?.path();
+        if path.file_name().is_some_and(|n| n == ".skillc-meta") {
+            for meta in fs::read_dir(&path)? {
+                let meta = meta?.path();
+                if !is_log(&meta) {
+                    remove_path(&meta)?;
+                }
+            }
+        } else {
+            remove_path(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() && !deploy::is_link(path) {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Format an undeploy result.
+pub fn format_undeploy(result: &UndeployResult, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(result)?);
+    }
+
+    let mut lines = Vec::new();
+    push_undeploy_lines(&mut lines, result);
+    if result.removed.is_empty() {
+        lines.push(format!("No deployments of {} found", result.skill));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Format a remove result.
+pub fn format_remove(result: &RemoveResult, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(result)?);
+    }
+
+    let verb = if result.dry_run {
+        "Would delete"
+    } else {
+        "Deleted"
+    };
+    let mut lines = Vec::new();
+    push_undeploy_lines(&mut lines, &result.undeploy);
+    for path in &result.deleted {
+        lines.push(format!("{} {}", verb, path.display()));
+    }
+    if let Some(log) = &result.kept_logs {
+        lines.push(format!("Kept {} (use --logs to delete)", log.display()));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn push_undeploy_lines(lines: &mut Vec<String>, result: &UndeployResult) {
+    let verb = if result.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    for removed in &result.removed {
+        lines.push(format!(
+            "{} {} ({})",
+            verb,
+            removed.path.display(),
+            removed.method
+        ));
+    }
+    for path in &result.skipped {
+        lines.push(format!(
+            "Skipped {} (not created by skillc)",
+            path.display()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clear_runtime_except_logs() {
+        let temp = TempDir::new().expect("create temp dir");
+        let runtime = temp.path();
+        let meta = runtime.join(".skillc-meta");
+        fs::create_dir_all(meta.join("targets")).expect("create meta");
+        fs::write(runtime.join("SKILL.md"), "stub").expect("write stub");
+        fs::write(meta.join("logs.db"), "").expect("write log");
+        fs::write(meta.join("logs.db-wal"), "").expect("write wal");
+        fs::write(meta.join("search-0123.db"), "").expect("write index");
+
+        clear_runtime_except_logs(runtime).expect("clear runtime");
+        let mut left: Vec<String> = fs::read_dir(&meta)
+            .expect("read meta")
+            .map(|e| e.expect("entry").file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["logs.db", "logs.db-wal"]);
+        assert!(!runtime.join("SKILL.md").exists());
+    }
+}
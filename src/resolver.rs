@@ -1,10 +1,11 @@
 //! Skill resolution per [[RFC-0007:C-RESOLUTION]]
 
-use crate::config::{find_project_skill, global_runtime_store, global_source_store};
+use crate::config::{Target, find_project_skill, global_runtime_store, global_source_store};
 use crate::error::{Result, SkillcError};
 use crate::verbose;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Resolved skill paths
 #[derive(Debug)]
@@ -15,6 +16,12 @@ pub struct ResolvedSkill {
     pub source_dir: PathBuf,
     /// Absolute path to runtime directory (for logging)
     pub runtime_dir: PathBuf,
+    /// Runtime holding the search index and manifest: the target variant
+    /// for `skill@target` when the last build produced one, else `runtime_dir`
+    pub index_dir: PathBuf,
+    /// Directory to read skill content from: the resolved copy in
+    /// `index_dir` when the last build produced one, else `source_dir`
+    pub content_dir: PathBuf,
 }
 
 /// Resolve a skill argument to source and runtime directories.
@@ -28,6 +35,10 @@ pub struct ResolvedSkill {
 /// Note: Direct paths are NOT supported for query commands. Use `skc build <path>`
 /// to import a skill first, then query by name.
 ///
+/// A `@target` suffix (e.g. `my-skill@claude`) selects the content built for
+/// that target, as written into the stub of a target variant. Targets without
+/// a variant see the shared runtime.
+///
 /// Per [[RFC-0005:C-CODES]] resolution error hierarchy:
 /// - E010: A directory was found but it lacks SKILL.md
 /// - E001: No directory was found at all
@@ -42,10 +53,12 @@ pub fn resolve_skill(skill: &str) -> Result<ResolvedSkill> {
         )));
     }
 
+    let (skill, target) = split_target(skill)?;
+
     // Try project source store first
     if let Some(project_source) = try_project_source_store(skill)? {
         verbose!("  resolved via project store: {}", project_source.display());
-        return finish_resolve(skill, project_source);
+        return finish_resolve(project_source, target);
     }
 
     // Try global source store
@@ -57,7 +70,7 @@ pub fn resolve_skill(skill: &str) -> Result<ResolvedSkill> {
             "  resolved via global source store: {}",
             source_dir.display()
         );
-        return finish_resolve(skill, source_dir);
+        return finish_resolve(source_dir, target);
     } else if global_path.exists() {
         // E010: Directory exists but lacks SKILL.md
         return Err(SkillcError::NotAValidSkill(
@@ -79,7 +92,7 @@ pub fn resolve_skill(skill: &str) -> Result<ResolvedSkill> {
             "  resolved via global runtime store: {}",
             source_dir.display()
         );
-        return finish_resolve(skill, source_dir);
+        return finish_resolve(source_dir, target);
     } else if runtime_path.exists() {
         // E010: Directory exists but lacks SKILL.md
         return Err(SkillcError::NotAValidSkill(
@@ -91,8 +104,29 @@ pub fn resolve_skill(skill: &str) -> Result<ResolvedSkill> {
     Err(SkillcError::SkillNotFound(skill.to_string()))
 }
 
+/// Resolve a skill from its source directory, as found by `skc list`.
+///
+/// Unlike [`resolve_skill`], a project skill does not shadow a global skill
+/// of the same name, so each source store entry resolves to its own runtime.
+pub fn resolve_source(source_dir: &Path) -> Result<ResolvedSkill> {
+    finish_resolve(source_dir.canonicalize()?, None)
+}
+
+/// Split a `skill@target` argument into the skill name and target.
+fn split_target(skill: &str) -> Result<(&str, Option<Target>)> {
+    match skill.rsplit_once('@') {
+        Some((name, target)) => {
+            let target = Target::from_str(target).map_err(|_| {
+                SkillcError::InvalidOption(format!("unknown target '{}' in '{}'", target, skill))
+            })?;
+            Ok((name, Some(target)))
+        }
+        None => Ok((skill, None)),
+    }
+}
+
 /// Complete resolution with source_dir already determined.
-fn finish_resolve(_skill: &str, source_dir: PathBuf) -> Result<ResolvedSkill> {
+fn finish_resolve(source_dir: PathBuf, target: Option<Target>) -> Result<ResolvedSkill> {
     // Extract skill name from source directory basename
     let name = source_dir
         .file_name()
@@ -103,10 +137,22 @@ fn finish_resolve(_skill: &str, source_dir: PathBuf) -> Result<ResolvedSkill> {
     // Resolve runtime directory based on where the source was found
     let runtime_dir = resolve_runtime_dir_for_source(&name, &source_dir)?;
 
+    let index_dir = target
+        .map(|t| crate::util::target_variant_dir(&runtime_dir, t))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| runtime_dir.clone());
+    verbose!("  index_dir: {}", index_dir.display());
+
+    let content_dir = crate::util::expanded_source_dir(&index_dir)
+        .canonicalize()
+        .unwrap_or_else(|_| source_dir.clone());
+
     Ok(ResolvedSkill {
         name,
         source_dir,
         runtime_dir,
+        index_dir,
+        content_dir,
     })
 }
 
@@ -254,4 +300,16 @@ mod tests {
             assert!(result.is_none());
         });
     }
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(split_target("my-skill").expect("plain"), ("my-skill", None));
+        assert_eq!(
+            split_target("my-skill@claude").expect("with target"),
+            ("my-skill", Some(Target::Claude))
+        );
+
+        let err = split_target("my-skill@nope").expect_err("unknown target");
+        assert!(err.to_string().contains("error[E100]"));
+    }
 }
@@ -3,13 +3,14 @@
 //! Provides full-text search over skill content using SQLite FTS5.
 
 use crate::config::{ensure_dir, get_cwd};
+use crate::csv::CsvTable;
 use crate::error::{Result, SkillcError};
 use crate::index::{self, SCHEMA_VERSION};
 use crate::logging::{LogEntry, get_run_id, init_log_db, log_access_with_fallback};
 use crate::markdown;
-use crate::resolver::{ResolvedSkill, resolve_skill};
+use crate::resolver::{ResolvedSkill, resolve_skill, resolve_source};
+use crate::skillignore::SkillIgnore;
 use crate::{OutputFormat, verbose};
-use chrono::Utc;
 use crossterm::style::Stylize;
 use rusqlite::{Connection, params};
 use serde::Serialize;
@@ -22,6 +23,9 @@ use walkdir::WalkDir;
 /// Search result entry.
 #[derive(Debug, Serialize)]
 pub struct SearchResult {
+    /// Skill the result came from (cross-skill search only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
     pub file: String,
     pub section: String,
     pub snippet: String,
@@ -35,6 +39,29 @@ pub struct SearchResponse {
     pub results: Vec<SearchResult>,
 }
 
+/// How a search query is turned into an FTS5 MATCH expression per [[RFC-0004:C-QUERY-SYNTAX]].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryMode {
+    /// Bag-of-words with implicit AND (default)
+    #[default]
+    Words,
+    /// All words adjacent and in order
+    Phrase,
+    /// FTS5 query syntax passed through after validation
+    Raw,
+}
+
+impl QueryMode {
+    /// Mode name as recorded in the access log.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryMode::Words => "words",
+            QueryMode::Phrase => "phrase",
+            QueryMode::Raw => "raw",
+        }
+    }
+}
+
 // Re-export HeadingEntry from index module for backward compatibility
 pub use crate::index::HeadingEntry;
 
@@ -88,6 +115,15 @@ fn tokenizer_short_name(tokenizer: &str) -> &str {
     }
 }
 
+/// Tokenizer short name a new index would record per [[RFC-0004:C-INDEX]].
+pub(crate) fn current_tokenizer() -> String {
+    let tokenizer_pref = match Connection::open_in_memory() {
+        Ok(conn) => get_tokenizer_preference(&conn),
+        Err(_) => return "unicode61".to_string(),
+    };
+    tokenizer_short_name(&tokenizer_pref).to_string()
+}
+
 /// Read required metadata key from index.
 fn read_meta(conn: &Connection, key: &str) -> Result<String> {
     conn.query_row(
@@ -191,8 +227,34 @@ fn check_index_state(
     IndexState::UpToDate
 }
 
+/// Check whether the search index is up to date per [[RFC-0004:C-INDEX]].
+///
+/// Returns false for missing, corrupt, colliding, or stale indexes.
+pub fn index_is_current(source_dir: &Path, runtime_dir: &Path, source_hash: &str) -> bool {
+    let index_path = get_index_path(runtime_dir, source_dir);
+    let tokenizer_pref = match Connection::open_in_memory() {
+        Ok(conn) => get_tokenizer_preference(&conn),
+        Err(_) => return false,
+    };
+
+    matches!(
+        check_index_state(&index_path, source_dir, source_hash, &tokenizer_pref),
+        IndexState::UpToDate
+    )
+}
+
 /// Build the search index for a skill per [[RFC-0004:C-INDEX]].
-pub fn build_index(source_dir: &Path, runtime_dir: &Path, source_hash: &str) -> Result<()> {
+///
+/// The index is keyed by `source_dir` but built from the files in
+/// `content_dir`, which differs when includes were expanded. `indexed_at` is
+/// recorded as-is so that identical inputs produce an identical index file.
+pub fn build_index(
+    source_dir: &Path,
+    content_dir: &Path,
+    runtime_dir: &Path,
+    source_hash: &str,
+    indexed_at: &str,
+) -> Result<()> {
     let start = Instant::now();
     let index_path = get_index_path(runtime_dir, source_dir);
 
@@ -238,7 +300,14 @@ pub fn build_index(source_dir: &Path, runtime_dir: &Path, source_hash: &str) ->
     }
 
     // Create new index
-    create_index(&index_path, source_dir, source_hash, &tokenizer_pref)?;
+    create_index(
+        &index_path,
+        source_dir,
+        content_dir,
+        source_hash,
+        &tokenizer_pref,
+        indexed_at,
+    )?;
 
     verbose!("build_index: completed in {:?}", start.elapsed());
 
@@ -249,8 +318,10 @@ pub fn build_index(source_dir: &Path, runtime_dir: &Path, source_hash: &str) ->
 fn create_index(
     index_path: &Path,
     source_dir: &Path,
+    content_dir: &Path,
     source_hash: &str,
     tokenizer: &str,
+    indexed_at: &str,
 ) -> Result<()> {
     let conn = Connection::open(index_path)?;
 
@@ -285,7 +356,7 @@ fn create_index(
     )?;
 
     // Index files
-    index_files(&conn, source_dir)?;
+    index_files(&conn, content_dir)?;
 
     // Write metadata
     let canonical_path = source_dir
@@ -294,7 +365,6 @@ fn create_index(
         .to_string_lossy()
         .to_string();
     let tokenizer_short = tokenizer_short_name(tokenizer);
-    let indexed_at = Utc::now().to_rfc3339();
 
     conn.execute(
         "INSERT INTO index_meta (key, value) VALUES (?1, ?2)",
@@ -320,10 +390,16 @@ fn create_index(
     Ok(())
 }
 
-/// Index all supported files in source directory.
+/// Index all supported files in source directory, honoring `.skillcignore`.
+///
+/// Files are visited in name order so the index is byte-reproducible.
 fn index_files(conn: &Connection, source_dir: &Path) -> Result<()> {
+    let ignore = SkillIgnore::load(source_dir);
+
     for entry in WalkDir::new(source_dir)
+        .sort_by_file_name()
         .into_iter()
+        .filter_entry(|e| ignore.allows(e))
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
     {
@@ -333,6 +409,7 @@ fn index_files(conn: &Connection, source_dir: &Path) -> Result<()> {
         match ext {
             Some("md") => index_markdown(conn, source_dir, path)?,
             Some("txt") => index_text(conn, source_dir, path)?,
+            Some("csv") => index_csv(conn, source_dir, path)?,
             _ => {
                 // Silently skip unsupported formats per [[RFC-0004:C-FORMATS]]
             }
@@ -424,27 +501,62 @@ fn index_text(conn: &Connection, source_dir: &Path, file_path: &Path) -> Result<
     Ok(())
 }
 
+/// Index a CSV file one document per row per [[RFC-0004:C-FORMATS]].
+///
+/// Content is the row rendered as `column: value` lines using the header;
+/// the section is the row's first column, or its row number if empty.
+fn index_csv(conn: &Connection, source_dir: &Path, file_path: &Path) -> Result<()> {
+    let content = fs::read_to_string(file_path)?;
+    let relative_path = file_path
+        .strip_prefix(source_dir)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .to_string();
+
+    let table = CsvTable::parse(&content);
+    for row in &table.rows {
+        conn.execute(
+            "INSERT INTO sections (file, section, content) VALUES (?1, ?2, ?3)",
+            params![relative_path, row.section(), row.render(&table.header)],
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Execute search command per [[RFC-0004:C-SEARCH]].
 ///
 /// Returns formatted output as a string.
-pub fn search(skill: &str, query: &str, limit: usize, format: OutputFormat) -> Result<String> {
+pub fn search(
+    skill: &str,
+    query: &str,
+    mode: QueryMode,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<String> {
     let start = Instant::now();
 
     // Validate query per [[RFC-0004:C-QUERY-SYNTAX]]
     if query.trim().is_empty() {
         return Err(SkillcError::EmptyQuery);
     }
+    let fts_query = build_fts_query(query, mode)?;
 
     let resolved = resolve_skill(skill)?;
     let run_id = get_run_id();
 
-    verbose!("search: query=\"{}\" limit={}", query, limit);
+    verbose!(
+        "search: query=\"{}\" mode={} limit={}",
+        query,
+        mode.as_str(),
+        limit
+    );
     verbose!("search: source_dir={}", resolved.source_dir.display());
 
     // Initialize logging
     let log_conn = init_log_db(&resolved.runtime_dir);
 
-    let result = do_search(&resolved, query, limit, &format);
+    let result = do_search(&resolved, query, &fts_query, limit, &format);
 
     verbose!("search: completed in {:?}", start.elapsed());
 
@@ -456,6 +568,7 @@ pub fn search(skill: &str, query: &str, limit: usize, format: OutputFormat) -> R
 
     let args = serde_json::json!({
         "query": query,
+        "mode": mode.as_str(),
         "result_count": result_count,
     });
 
@@ -476,25 +589,131 @@ pub fn search(skill: &str, query: &str, limit: usize, format: OutputFormat) -> R
     result.map(|(output, _)| output)
 }
 
+/// Search every built skill in the project and global stores per [[RFC-0004:C-SEARCH]].
+///
+/// Scores are normalized to the best match across all skills (1.0), so a
+/// skill's weak hits still rank below another skill's strong ones. Skills whose
+/// index is unusable are skipped with a warning. One access is logged for
+/// each skill that contributes a result.
+pub fn search_all(
+    query: &str,
+    mode: QueryMode,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<String> {
+    let start = Instant::now();
+
+    if query.trim().is_empty() {
+        return Err(SkillcError::EmptyQuery);
+    }
+    let fts_query = build_fts_query(query, mode)?;
+
+    let skills = crate::list::list(&crate::list::ListOptions {
+        status: Some(crate::list::SkillStatus::Normal),
+        ..Default::default()
+    })?;
+
+    let mut searched = Vec::new();
+    let mut results = Vec::new();
+    for skill in &skills.skills {
+        let resolved = match resolve_source(&skill.source_path) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                verbose!("search: skipping {}: {}", skill.name, e);
+                continue;
+            }
+        };
+        let skill_results =
+            index::open_index(&resolved.index_dir, &resolved.source_dir, &resolved.name).and_then(
+                |conn| {
+                    check_index(&conn, &resolved)?;
+                    query_index(&conn, &fts_query, limit)
+                },
+            );
+        let mut skill_results = match skill_results {
+            Ok(skill_results) => skill_results,
+            Err(e) => {
+                eprintln!("warning: skipping '{}': {}", resolved.name, e);
+                continue;
+            }
+        };
+
+        for result in &mut skill_results {
+            result.skill = Some(resolved.name.clone());
+        }
+        results.extend(skill_results.into_iter().map(|r| (searched.len(), r)));
+        searched.push(resolved);
+    }
+
+    let best = results.iter().map(|(_, r)| r.score).fold(0.0, f64::max);
+    let scale = if best > 0.0 { best } else { 1.0 };
+    for (_, result) in &mut results {
+        result.score /= scale;
+    }
+
+    results.sort_by(|(_, a), (_, b)| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.skill.cmp(&b.skill))
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.section.cmp(&b.section))
+    });
+    results.truncate(limit);
+    verbose!(
+        "search: {} result(s) from {} skill(s) in {:?}",
+        results.len(),
+        searched.len(),
+        start.elapsed()
+    );
+
+    let run_id = get_run_id();
+    for (index, resolved) in searched.iter().enumerate() {
+        let result_count = results.iter().filter(|(i, _)| *i == index).count();
+        if result_count == 0 {
+            continue;
+        }
+        let args = serde_json::json!({
+            "query": query,
+            "mode": mode.as_str(),
+            "result_count": result_count,
+            "all": true,
+        });
+        log_access_with_fallback(
+            init_log_db(&resolved.runtime_dir).as_ref(),
+            &LogEntry {
+                run_id: run_id.clone(),
+                command: "search".to_string(),
+                skill: resolved.name.clone(),
+                skill_path: resolved.source_dir.to_string_lossy().to_string(),
+                cwd: get_cwd(),
+                args: args.to_string(),
+                error: None,
+            },
+        );
+    }
+
+    let results = results.into_iter().map(|(_, r)| r).collect();
+    format_results(query, results, &format)
+}
+
 /// Perform the actual search.
 /// Returns (output_string, result_count).
 fn do_search(
     resolved: &ResolvedSkill,
     query: &str,
+    fts_query: &str,
     limit: usize,
     format: &OutputFormat,
 ) -> Result<(String, usize)> {
-    let index_path = get_index_path(&resolved.runtime_dir, &resolved.source_dir);
-
-    // Check if index exists
-    if !index_path.exists() {
-        return Err(SkillcError::IndexUnusable(resolved.name.clone()));
-    }
-
-    // Open and validate index
-    let conn = Connection::open(&index_path)
-        .map_err(|_| SkillcError::IndexUnusable(resolved.name.clone()))?;
+    let conn = index::open_index(&resolved.index_dir, &resolved.source_dir, &resolved.name)?;
+    check_index(&conn, resolved)?;
+    let results = query_index(&conn, fts_query, limit)?;
+    let result_count = results.len();
+    Ok((format_results(query, results, format)?, result_count))
+}
 
+/// Check an open index for collision and staleness per [[RFC-0004:C-INDEX]].
+fn check_index(conn: &Connection, resolved: &ResolvedSkill) -> Result<()> {
     // Check index state (simplified - just check skill_path for collision)
     let table_exists: bool = conn
         .query_row(
@@ -573,7 +792,7 @@ fn do_search(
 
     // Read manifest to get current source_hash
     let manifest_path = resolved
-        .runtime_dir
+        .index_dir
         .join(".skillc-meta")
         .join("manifest.json");
     if manifest_path.exists() {
@@ -586,10 +805,11 @@ fn do_search(
         }
     }
 
-    // Build FTS5 query per [[RFC-0004:C-QUERY-SYNTAX]]
-    let fts_query = build_fts_query(query);
+    Ok(())
+}
 
-    // Execute search
+/// Run an FTS5 query against an index, best match first.
+fn query_index(conn: &Connection, fts_query: &str, limit: usize) -> Result<Vec<SearchResult>> {
     let mut stmt = conn.prepare(
         "SELECT file, section, snippet(sections, 2, '[MATCH]', '[/MATCH]', '...', 32), bm25(sections)
          FROM sections
@@ -601,6 +821,7 @@ fn do_search(
     let results: Vec<SearchResult> = stmt
         .query_map(params![fts_query, limit as i64], |row| {
             Ok(SearchResult {
+                skill: None,
                 file: row.get(0)?,
                 section: row.get(1)?,
                 snippet: row.get(2)?,
@@ -610,9 +831,15 @@ fn do_search(
         .filter_map(|r| r.ok())
         .collect();
 
-    let result_count = results.len();
+    Ok(results)
+}
 
-    // Format output
+/// Format search results per [[RFC-0004:C-SEARCH]].
+fn format_results(
+    query: &str,
+    results: Vec<SearchResult>,
+    format: &OutputFormat,
+) -> Result<String> {
     let output = match format {
         OutputFormat::Json => {
             let response = SearchResponse {
@@ -625,14 +852,15 @@ fn do_search(
             let is_tty = std::io::stdout().is_terminal();
             let mut lines = Vec::new();
             for result in &results {
-                if result.section.is_empty() {
-                    lines.push(format!("{} (score: {:.4})", result.file, result.score));
+                let mut location = if result.section.is_empty() {
+                    result.file.clone()
                 } else {
-                    lines.push(format!(
-                        "{}#{} (score: {:.4})",
-                        result.file, result.section, result.score
-                    ));
+                    format!("{}#{}", result.file, result.section)
+                };
+                if let Some(skill) = &result.skill {
+                    location = format!("{}: {}", skill, location);
                 }
+                lines.push(format!("{} (score: {:.4})", location, result.score));
                 // Render [MATCH]...[/MATCH] as colored text when outputting to TTY
                 let snippet = if is_tty {
                     render_match_highlights(&result.snippet)
@@ -649,7 +877,7 @@ fn do_search(
         }
     };
 
-    Ok((output, result_count))
+    Ok(output)
 }
 
 /// Render [MATCH]...[/MATCH] markers as colored text.
@@ -681,23 +909,46 @@ fn render_match_highlights(text: &str) -> String {
 }
 
 /// Build FTS5 query from user input per [[RFC-0004:C-QUERY-SYNTAX]].
-fn build_fts_query(query: &str) -> String {
+///
+/// Raw queries are validated against an empty table with the index's
+/// columns, so syntax errors surface as E005 rather than as an empty result.
+fn build_fts_query(query: &str, mode: QueryMode) -> Result<String> {
     // Split on ASCII whitespace only
     let tokens: Vec<&str> = query
         .split([' ', '\t', '\n', '\r'])
         .filter(|s| !s.is_empty())
         .collect();
 
-    // Quote each token (escape internal quotes)
-    let quoted: Vec<String> = tokens
-        .iter()
-        .map(|t| {
-            let escaped = t.replace('"', "\"\"");
-            format!("\"{}\"", escaped)
-        })
-        .collect();
+    match mode {
+        QueryMode::Words => {
+            // Quote each token (escape internal quotes)
+            let quoted: Vec<String> = tokens.iter().map(|t| quote_fts_string(t)).collect();
+            Ok(quoted.join(" "))
+        }
+        QueryMode::Phrase => Ok(quote_fts_string(&tokens.join(" "))),
+        QueryMode::Raw => {
+            validate_raw_query(query)?;
+            Ok(query.to_string())
+        }
+    }
+}
 
-    quoted.join(" ")
+/// Wrap text in double quotes as an FTS5 string, doubling internal quotes.
+fn quote_fts_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Check that a raw query parses as an FTS5 MATCH expression over the index columns.
+fn validate_raw_query(query: &str) -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    conn.execute_batch("CREATE VIRTUAL TABLE sections USING fts5(file, section, content)")?;
+    conn.query_row(
+        "SELECT COUNT(*) FROM sections WHERE sections MATCH ?1",
+        params![query],
+        |row| row.get::<_, i64>(0),
+    )
+    .map_err(|e| SkillcError::InvalidQuery(e.to_string()))?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -708,7 +959,7 @@ mod tests {
     #[test]
     fn test_build_fts_query_simple() {
         assert_eq!(
-            build_fts_query("configure authentication"),
+            build_fts_query("configure authentication", QueryMode::Words).expect("query"),
             "\"configure\" \"authentication\""
         );
     }
@@ -716,14 +967,46 @@ mod tests {
     #[test]
     fn test_build_fts_query_with_quotes() {
         assert_eq!(
-            build_fts_query("my \"special\" app"),
+            build_fts_query("my \"special\" app", QueryMode::Words).expect("query"),
             "\"my\" \"\"\"special\"\"\" \"app\""
         );
     }
 
     #[test]
     fn test_build_fts_query_extra_whitespace() {
-        assert_eq!(build_fts_query("  hello   world  "), "\"hello\" \"world\"");
+        assert_eq!(
+            build_fts_query("  hello   world  ", QueryMode::Words).expect("query"),
+            "\"hello\" \"world\""
+        );
+    }
+
+    #[test]
+    fn test_build_fts_query_phrase() {
+        assert_eq!(
+            build_fts_query("  configure   the \"auth\" ", QueryMode::Phrase).expect("query"),
+            "\"configure the \"\"auth\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_build_fts_query_raw() {
+        for query in [
+            "install OR setup",
+            "deploy NOT docker",
+            "config*",
+            "NEAR(token refresh, 5)",
+            "section:install",
+        ] {
+            assert_eq!(
+                build_fts_query(query, QueryMode::Raw).expect("valid raw query"),
+                query
+            );
+        }
+
+        for query in ["install OR", "\"unterminated", "heading:install"] {
+            let err = build_fts_query(query, QueryMode::Raw).expect_err("invalid raw query");
+            assert_eq!(err.code(), crate::error::ErrorCode::E005, "{}", query);
+        }
     }
 
     #[test]
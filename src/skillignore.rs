@@ -0,0 +1,116 @@
+//! `.skillcignore` support per [[RFC-0001:C-INPUT]].
+//!
+//! A gitignore-syntax file at the skill root excludes build artifacts,
+//! drafts, and fixtures from everything that walks a skill: source hashing,
+//! search indexing, import copies, `skc sources`, and the SKL401 orphan check.
+//! The ignore file itself is an ordinary source file and is hashed.
+
+use crate::verbose;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use walkdir::DirEntry;
+
+/// Name of the ignore file at the skill root.
+pub const IGNORE_FILE: &str = ".skillcignore";
+
+/// Compiled `.skillcignore` patterns for one skill root.
+#[derive(Debug, Clone)]
+pub struct SkillIgnore {
+    root: PathBuf,
+    matcher: Option<Gitignore>,
+}
+
+impl SkillIgnore {
+    /// Load `.skillcignore` from `root`. A missing file ignores nothing.
+    ///
+    /// As with gitignore, malformed patterns are skipped rather than failing
+    /// the command.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(IGNORE_FILE);
+        if !path.is_file() {
+            return Self::none(root);
+        }
+
+        let mut builder = GitignoreBuilder::new(root);
+        if let Some(err) = builder.add(&path) {
+            verbose!(
+                "ignore: skipping invalid patterns in {}: {}",
+                path.display(),
+                err
+            );
+        }
+        match builder.build() {
+            Ok(matcher) => Self {
+                root: root.to_path_buf(),
+                matcher: Some(matcher),
+            },
+            Err(err) => {
+                verbose!("ignore: failed to load {}: {}", path.display(), err);
+                Self::none(root)
+            }
+        }
+    }
+
+    /// A matcher that ignores nothing.
+    pub fn none(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matcher: None,
+        }
+    }
+
+    /// True if `path` (under the root, or relative to it) is ignored, either
+    /// directly or through an ignored parent directory.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(matcher) = &self.matcher else {
+            return false;
+        };
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if relative.as_os_str().is_empty() || relative.has_root() {
+            return false;
+        }
+        matcher
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+
+    /// Predicate for `WalkDir::filter_entry`; the walk root is always kept.
+    pub fn allows(&self, entry: &DirEntry) -> bool {
+        entry.depth() == 0 || !self.is_ignored(entry.path(), entry.file_type().is_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gitignore_syntax() {
+        let temp = TempDir::new().expect("create temp dir");
+        let root = temp.path();
+        fs::write(
+            root.join(IGNORE_FILE),
+            "# comment\n*.log\ndrafts/\n/build\n!keep.log\n",
+        )
+        .expect("write ignore file");
+        let ignore = SkillIgnore::load(root);
+
+        assert!(ignore.is_ignored(&root.join("debug.log"), false));
+        assert!(ignore.is_ignored(Path::new("nested/debug.log"), false));
+        assert!(!ignore.is_ignored(Path::new("keep.log"), false));
+        assert!(ignore.is_ignored(Path::new("drafts"), true));
+        assert!(ignore.is_ignored(Path::new("drafts/idea.md"), false));
+        assert!(ignore.is_ignored(Path::new("build/out.md"), false));
+        assert!(!ignore.is_ignored(Path::new("docs/build/out.md"), false));
+        assert!(!ignore.is_ignored(Path::new("SKILL.md"), false));
+    }
+
+    #[test]
+    fn test_missing_file_ignores_nothing() {
+        let temp = TempDir::new().expect("create temp dir");
+        let ignore = SkillIgnore::load(temp.path());
+        assert!(!ignore.is_ignored(Path::new("anything.log"), false));
+    }
+}
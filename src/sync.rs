@@ -126,7 +126,7 @@ pub fn sync(options: SyncOptions) -> Result<()> {
 }
 
 /// List all skills with logs in the given logs directory.
-fn list_skills_in_logs_dir(logs_dir: &Path) -> Vec<String> {
+pub(crate) fn list_skills_in_logs_dir(logs_dir: &Path) -> Vec<String> {
     let mut skills = Vec::new();
 
     if let Ok(entries) = fs::read_dir(logs_dir) {
@@ -146,6 +146,13 @@ fn list_skills_in_logs_dir(logs_dir: &Path) -> Vec<String> {
     skills
 }
 
+/// Sync one skill's fallback logs and remove them, returning the entries synced.
+pub(crate) fn sync_and_purge(logs_dir: &Path, skill: &str) -> Result<usize> {
+    let result = sync_skill(logs_dir, skill, false)?;
+    purge_local_logs(logs_dir, skill)?;
+    Ok(result.entries_synced)
+}
+
 /// Sync a single skill's logs from fallback to primary.
 fn sync_skill(logs_dir: &Path, skill: &str, dry_run: bool) -> Result<SyncResult> {
     let fallback_dir = logs_dir.join(skill);
@@ -0,0 +1,104 @@
+//! Local token estimation for stub budgets and manifest accounting.
+//!
+//! This is a tokenizer-free approximation, not an exact count for any model:
+//! - runs of ASCII letters/digits cost one token per 4 characters (rounded up)
+//! - every other non-whitespace character (punctuation, CJK, emoji) costs one token
+//!
+//! It tracks real BPE tokenizers closely enough for budgeting and for
+//! comparing sections against each other.
+
+use crate::markdown;
+use serde::{Deserialize, Serialize};
+
+/// Characters per token for ASCII word runs.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimate the token count of a text.
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut run: usize = 0;
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            run += 1;
+            continue;
+        }
+        tokens += run.div_ceil(CHARS_PER_TOKEN);
+        run = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+
+    tokens + run.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Token estimate for one section of a markdown file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionTokens {
+    pub file: String,
+    pub heading: String,
+    pub level: usize,
+    /// Tokens from the heading line up to the next heading of equal or higher level
+    pub tokens: usize,
+}
+
+/// Estimate tokens for each heading-delimited section of a markdown file.
+///
+/// Section boundaries match the search index per [[RFC-0004:C-FORMATS]]: a
+/// section includes its subsections, so counts nest rather than sum.
+pub fn section_tokens(file: &str, content: &str) -> Vec<SectionTokens> {
+    let lines: Vec<&str> = content.lines().collect();
+    let headings = markdown::extract_headings(content);
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(idx, heading)| {
+            let start = heading.line.saturating_sub(1);
+            let end = headings
+                .iter()
+                .skip(idx + 1)
+                .find(|h| h.level <= heading.level)
+                .map(|h| h.line.saturating_sub(1))
+                .unwrap_or(lines.len());
+            SectionTokens {
+                file: file.to_string(),
+                heading: heading.text.clone(),
+                level: heading.level,
+                tokens: estimate_tokens(&lines[start..end].join("\n")),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("   \n\t"), 0);
+        // "hello" = 5 chars -> 2 tokens, "world" -> 2 tokens
+        assert_eq!(estimate_tokens("hello world"), 4);
+        // "a" -> 1, "," -> 1, "b" -> 1
+        assert_eq!(estimate_tokens("a, b"), 3);
+        // Each CJK character counts as one token
+        assert_eq!(estimate_tokens("中文"), 2);
+    }
+
+    #[test]
+    fn test_section_tokens_nest_subsections() {
+        let content = "# Top\n\nintro\n\n## Child\n\nbody text\n\n# Next\n\nend\n";
+        let sections = section_tokens("SKILL.md", content);
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, "Top");
+        assert_eq!(sections[1].heading, "Child");
+        assert_eq!(sections[2].heading, "Next");
+        // Parent section includes its child
+        assert!(sections[0].tokens > sections[1].tokens);
+        assert_eq!(sections[2].file, "SKILL.md");
+    }
+}
@@ -1,6 +1,7 @@
 //! General utilities for skillc.
 
 use crate::error::{Result, SkillcError};
+use crate::skillignore::SkillIgnore;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -41,6 +42,19 @@ pub fn is_valid_skill(path: &Path) -> bool {
 /// Copies all files and subdirectories from `src` to `dst`.
 /// Symlinks and other special file types are skipped.
 pub fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
+    copy_dir(src, dst, None)
+}
+
+/// Recursively copy a skill source directory, honoring its `.skillcignore`.
+///
+/// Like [`copy_dir_recursive`], but skips paths matched by a `.skillcignore`
+/// at `src` per [[RFC-0001:C-INPUT]]. Used where sources enter skillc
+/// (import and build); deploy copies mirror the runtime unfiltered.
+pub fn copy_dir_filtered(src: &Path, dst: &Path) -> io::Result<()> {
+    copy_dir(src, dst, Some(&SkillIgnore::load(src)))
+}
+
+fn copy_dir(src: &Path, dst: &Path, ignore: Option<&SkillIgnore>) -> io::Result<()> {
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
@@ -49,8 +63,11 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
+        if ignore.is_some_and(|ignore| ignore.is_ignored(&src_path, file_type.is_dir())) {
+            continue;
+        }
         if file_type.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir(&src_path, &dst_path, ignore)?;
         } else if file_type.is_file() {
             fs::copy(&src_path, &dst_path)?;
         }
@@ -64,6 +81,37 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
 // Path helpers for skillc's directory structure
 // =============================================================================
 
+/// Get the include-expanded copy of the source inside a runtime directory.
+///
+/// Only present when the last build expanded `skc:include` or `skc:if` directives.
+pub fn expanded_source_dir(runtime_dir: &Path) -> PathBuf {
+    runtime_dir.join(".skillc-meta").join("source")
+}
+
+/// Get the directory holding all per-target runtime variants.
+pub fn target_variants_dir(runtime_dir: &Path) -> PathBuf {
+    runtime_dir.join(".skillc-meta").join("targets")
+}
+
+/// Get the runtime variant built for one target.
+///
+/// Only present when the skill has `skc:if` blocks naming that target. The
+/// variant is a complete runtime (stub, manifest, content, index) that is
+/// deployed to the target in place of the shared runtime.
+pub fn target_variant_dir(runtime_dir: &Path, target: crate::config::Target) -> PathBuf {
+    target_variants_dir(runtime_dir).join(target.to_string())
+}
+
+/// Get the directory holding a runtime's staged and retained builds.
+///
+/// Lives beside the runtime (`<store>/.skillc-builds/<skill>/`) so that a
+/// build can be swapped in by rename without crossing filesystems.
+pub fn runtime_builds_dir(runtime_dir: &Path) -> PathBuf {
+    let parent = runtime_dir.parent().unwrap_or(Path::new("."));
+    let name = runtime_dir.file_name().unwrap_or_default();
+    parent.join(".skillc-builds").join(name)
+}
+
 /// Get the `.skillc/logs/` directory for a project root.
 pub fn project_logs_dir(root: &Path) -> PathBuf {
     root.join(".skillc").join("logs")
@@ -122,6 +170,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_copy_dir_filtered_honors_skillcignore() {
+        let temp = TempDir::new().expect("create temp dir");
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("drafts")).expect("test operation");
+        fs::write(src.join(".skillcignore"), "drafts/\n").expect("test operation");
+        fs::write(src.join("SKILL.md"), "# Skill").expect("test operation");
+        fs::write(src.join("drafts").join("wip.md"), "wip").expect("test operation");
+
+        let filtered = temp.path().join("filtered");
+        copy_dir_filtered(&src, &filtered).expect("filtered copy");
+        assert!(filtered.join("SKILL.md").exists());
+        assert!(!filtered.join("drafts").exists());
+
+        let mirror = temp.path().join("mirror");
+        copy_dir_recursive(&src, &mirror).expect("plain copy");
+        assert!(mirror.join("drafts").join("wip.md").exists());
+    }
+
     #[test]
     fn test_project_path_helpers() {
         let root = Path::new("/project");
@@ -150,5 +217,9 @@ mod tests {
             project_skill_dir(root, "my-skill"),
             PathBuf::from("/project/.skillc/skills/my-skill")
         );
+        assert_eq!(
+            runtime_builds_dir(&project_skill_runtime_dir(root, "my-skill")),
+            PathBuf::from("/project/.skillc/runtime/.skillc-builds/my-skill")
+        );
     }
 }
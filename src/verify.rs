@@ -0,0 +1,411 @@
+//! Build integrity verification (`skc verify`).
+//!
+//! Re-hashes the source against the per-file inventory in the manifest, the
+//! runtime stub against its recorded hash, and every deployment in the agent
+//! directories against the runtime per [[RFC-0001:C-MANIFEST]].
+
+use crate::OutputFormat;
+use crate::compiler::{FileHash, compute_file_hashes, read_manifest, sha256_hex};
+use crate::config::{TargetSpec, all_targets, global_skillc_dir};
+use crate::deploy::{deploy_source, is_link};
+use crate::emit::native_output;
+use crate::error::Result;
+use crate::registry::{self, Registry};
+use crate::resolver::resolve_skill;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How a file differs from its expected state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// A single file that differs from its expected state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub change: ChangeKind,
+}
+
+/// Verification result for one location (source, runtime, or a deployment).
+#[derive(Debug, Serialize)]
+pub struct LocationReport {
+    /// "source", "runtime", or the deployment target name
+    pub location: String,
+    pub path: PathBuf,
+    /// Problem that prevented a file comparison (missing manifest, foreign link)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    pub changes: Vec<FileChange>,
+}
+
+impl LocationReport {
+    fn new(location: &str, path: &Path) -> Self {
+        Self {
+            location: location.to_string(),
+            path: path.to_path_buf(),
+            issue: None,
+            changes: Vec::new(),
+        }
+    }
+
+    /// True if this location differs from its expected state.
+    pub fn has_drift(&self) -> bool {
+        self.issue.is_some() || !self.changes.is_empty()
+    }
+}
+
+/// Result of verifying a skill.
+#[derive(Debug, Serialize)]
+pub struct VerifyReport {
+    pub skill: String,
+    pub locations: Vec<LocationReport>,
+}
+
+impl VerifyReport {
+    /// True if any location differs from its expected state.
+    pub fn has_drift(&self) -> bool {
+        self.locations.iter().any(LocationReport::has_drift)
+    }
+}
+
+/// Verify a skill's source, runtime, and deployments against its manifest.
+///
+/// Deployments are discovered in the global and project-local directories of
+/// every known target; `extra_targets` adds custom agent directories.
+pub fn verify(skill: &str, extra_targets: &[TargetSpec]) -> Result<VerifyReport> {
+    let resolved = resolve_skill(skill)?;
+    let mut report = VerifyReport {
+        skill: resolved.name.clone(),
+        locations: Vec::new(),
+    };
+
+    let mut runtime = LocationReport::new("runtime", &resolved.runtime_dir);
+    let Some(manifest) = read_manifest(&resolved.runtime_dir) else {
+        runtime.issue = Some(format!("not built; run 'skc build {}'", resolved.name));
+        report.locations.push(runtime);
+        return Ok(report);
+    };
+
+    // Source against the inventory recorded at build time
+    let mut source = LocationReport::new("source", &resolved.source_dir);
+    if manifest.files.is_empty() {
+        source.issue = Some(format!(
+            "manifest has no file inventory; run 'skc build {} --force'",
+            resolved.name
+        ));
+    } else {
+        source.changes =
+            diff_inventories(&manifest.files, &compute_file_hashes(&resolved.source_dir)?);
+    }
+    report.locations.push(source);
+
+    // Runtime stub against its recorded hash
+    if !manifest.stub_sha256.is_empty() {
+        match fs::read(resolved.runtime_dir.join("SKILL.md")) {
+            Ok(stub) if sha256_hex(&stub) != manifest.stub_sha256 => {
+                runtime.changes.push(FileChange {
+                    path: "SKILL.md".to_string(),
+                    change: ChangeKind::Modified,
+                });
+            }
+            Ok(_) => {}
+            Err(_) => runtime.changes.push(FileChange {
+                path: "SKILL.md".to_string(),
+                change: ChangeKind::Removed,
+            }),
+        }
+    }
+    report.locations.push(runtime);
+
+    // Deployments against the runtime (or target variant) they were made from
+    let source_canonical = resolved.source_dir.canonicalize().ok();
+    for (target, dest) in
+        deployment_candidates(&resolved.runtime_dir, &resolved.name, extra_targets)
+    {
+        if dest.symlink_metadata().is_err() {
+            continue;
+        }
+        let mut location = LocationReport::new(&target.to_string(), &dest);
+        let expected = deploy_source(&resolved.runtime_dir, &target);
+        let runtime_canonical = expected.canonicalize().ok();
+
+        if is_link(&dest) {
+            let resolved_link = dest.canonicalize().ok();
+            if resolved_link.is_none() {
+                location.issue = Some("dangling link".to_string());
+            } else if resolved_link != runtime_canonical {
+                location.issue = Some(format!(
+                    "links to {}",
+                    resolved_link.unwrap_or_default().display()
+                ));
+            }
+        } else if dest.is_file() {
+            // Native instruction file, against the runtime's render
+            let expected = native_output(&resolved.runtime_dir, &target, &resolved.name)
+                .and_then(|path| fs::read(path).ok());
+            match expected {
+                Some(expected) if fs::read(&dest).ok().as_ref() == Some(&expected) => {}
+                Some(_) => location.changes.push(FileChange {
+                    path: dest
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    change: ChangeKind::Modified,
+                }),
+                None => location.issue = Some("runtime has no native render".to_string()),
+            }
+        } else {
+            // The skill itself may live in an agent directory (runtime-store fallback)
+            if dest.canonicalize().ok() == source_canonical {
+                continue;
+            }
+            location.changes =
+                diff_inventories(&deployed_inventory(&expected)?, &deployed_inventory(&dest)?);
+        }
+
+        report.locations.push(location);
+    }
+
+    Ok(report)
+}
+
+/// Compare an expected inventory to an actual one.
+///
+/// Both inputs are sorted by path, as returned by [`compute_file_hashes`].
+pub fn diff_inventories(expected: &[FileHash], actual: &[FileHash]) -> Vec<FileChange> {
+    let expected: BTreeMap<&str, &str> = expected
+        .iter()
+        .map(|f| (f.path.as_str(), f.sha256.as_str()))
+        .collect();
+    let actual: BTreeMap<&str, &str> = actual
+        .iter()
+        .map(|f| (f.path.as_str(), f.sha256.as_str()))
+        .collect();
+
+    let mut changes = Vec::new();
+    for (path, hash) in &expected {
+        match actual.get(path) {
+            None => changes.push(FileChange {
+                path: path.to_string(),
+                change: ChangeKind::Removed,
+            }),
+            Some(h) if h != hash => changes.push(FileChange {
+                path: path.to_string(),
+                change: ChangeKind::Modified,
+            }),
+            Some(_) => {}
+        }
+    }
+    for path in actual.keys().filter(|p| !expected.contains_key(*p)) {
+        changes.push(FileChange {
+            path: path.to_string(),
+            change: ChangeKind::Added,
+        });
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Inventory of a runtime or deployed copy: visible files plus the manifest.
+///
+/// Access logs and the search index are excluded since they legitimately
+/// differ between the runtime and a copy taken at deploy time.
+fn deployed_inventory(dir: &Path) -> Result<Vec<FileHash>> {
+    let mut files = compute_file_hashes(dir)?;
+    let manifest_rel = Path::new(".skillc-meta").join("manifest.json");
+    if let Ok(content) = fs::read(dir.join(&manifest_rel)) {
+        files.push(FileHash {
+            path: manifest_rel.to_string_lossy().to_string(),
+            sha256: sha256_hex(&content),
+        });
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    Ok(files)
+}
+
+/// Agent directory entries where the skill may be deployed, with their targets.
+pub(crate) fn deployment_candidates(
+    runtime_dir: &Path,
+    skill_name: &str,
+    extra_targets: &[TargetSpec],
+) -> Vec<(TargetSpec, PathBuf)> {
+    let project_root = project_root_of_runtime(runtime_dir);
+    let mut candidates: Vec<(TargetSpec, PathBuf)> = Vec::new();
+
+    for target in all_targets() {
+        if let Ok(dir) = target.skills_path(None) {
+            candidates.push((target.clone(), dir.join(skill_name)));
+        }
+        if let Some(root) = &project_root
+            && let Ok(dir) = target.skills_path(Some(root))
+        {
+            candidates.push((target, dir.join(skill_name)));
+        }
+    }
+    for target in extra_targets {
+        if let Ok(dir) = target.skills_path(project_root.as_deref()) {
+            candidates.push((target.clone(), dir.join(skill_name)));
+        }
+    }
+
+    // Deployments the registry knows about, including custom paths
+    if let Ok(registry) = Registry::load() {
+        for entry in registry.for_runtime(runtime_dir) {
+            let Ok(target) = entry.target.parse::<TargetSpec>();
+            candidates.push((target, entry.path.clone()));
+        }
+    }
+
+    // Compare entries by location, not by what they link to: two targets'
+    // links into the same runtime are distinct deployments
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|(_, path)| seen.insert(registry::location(path)));
+    candidates
+}
+
+/// Project root for a project runtime dir (`{root}/.skillc/runtime/{skill}`).
+///
+/// The global store (`~/.skillc`, or `$SKILLC_HOME/.skillc`) has the same
+/// shape but is not a project, so its runtimes have no project root.
+pub(crate) fn project_root_of_runtime(runtime_dir: &Path) -> Option<PathBuf> {
+    let runtime_store = runtime_dir.parent()?;
+    let skillc_dir = runtime_store.parent()?;
+    if runtime_store.file_name()? != "runtime" || skillc_dir.file_name()? != ".skillc" {
+        return None;
+    }
+    let is_global = global_skillc_dir().is_ok_and(|global| {
+        global == skillc_dir
+            || matches!(
+                (global.canonicalize(), skillc_dir.canonicalize()),
+                (Ok(a), Ok(b)) if a == b
+            )
+    });
+    if is_global {
+        None
+    } else {
+        skillc_dir.parent().map(Path::to_path_buf)
+    }
+}
+
+/// Format a verification report.
+pub fn format_verify(report: &VerifyReport, format: OutputFormat) -> Result<String> {
+    if matches!(format, OutputFormat::Json) {
+        return Ok(serde_json::to_string_pretty(report)?);
+    }
+
+    let mut lines = vec![format!("Verifying {}", report.skill)];
+    for location in &report.locations {
+        let status = match (&location.issue, location.changes.len()) {
+            (Some(issue), _) => issue.clone(),
+            (None, 0) => "ok".to_string(),
+            (None, n) => format!("{} change(s)", n),
+        };
+        lines.push(format!(
+            "  {:<9} {} ({})",
+            location.location,
+            location.path.display(),
+            status
+        ));
+        for change in &location.changes {
+            lines.push(format!(
+                "    {:<9} {}",
+                change.change.to_string(),
+                change.path
+            ));
+        }
+    }
+
+    let drifted = report.locations.iter().filter(|l| l.has_drift()).count();
+    if drifted == 0 {
+        lines.push("No drift detected".to_string());
+    } else {
+        lines.push(format!("Drift detected in {} location(s)", drifted));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(path: &str, sha256: &str) -> FileHash {
+        FileHash {
+            path: path.to_string(),
+            sha256: sha256.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_inventories() {
+        let expected = vec![
+            hash("SKILL.md", "a"),
+            hash("old.md", "b"),
+            hash("ref.md", "c"),
+        ];
+        let actual = vec![
+            hash("SKILL.md", "a"),
+            hash("new.md", "d"),
+            hash("ref.md", "x"),
+        ];
+
+        let changes = diff_inventories(&expected, &actual);
+        assert_eq!(
+            changes,
+            vec![
+                FileChange {
+                    path: "new.md".to_string(),
+                    change: ChangeKind::Added
+                },
+                FileChange {
+                    path: "old.md".to_string(),
+                    change: ChangeKind::Removed
+                },
+                FileChange {
+                    path: "ref.md".to_string(),
+                    change: ChangeKind::Modified
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_inventories_identical() {
+        let files = vec![hash("SKILL.md", "a")];
+        assert!(diff_inventories(&files, &files).is_empty());
+    }
+
+    #[test]
+    fn test_project_root_of_runtime() {
+        temp_env::with_var("SKILLC_HOME", Some("/home/u"), || {
+            assert_eq!(
+                project_root_of_runtime(Path::new("/p/.skillc/runtime/skill")),
+                Some(PathBuf::from("/p"))
+            );
+            // The global store is not a project
+            assert_eq!(
+                project_root_of_runtime(Path::new("/home/u/.skillc/runtime/skill")),
+                None
+            );
+            assert_eq!(project_root_of_runtime(Path::new("/tmp/out/skill")), None);
+        });
+    }
+}
@@ -0,0 +1,200 @@
+//! Source change detection for `skc build --watch`.
+//!
+//! Polls the skill source directory, and the include dependencies recorded
+//! in the last build's manifest, and reports a change only once the hash has
+//! stopped moving for the debounce window, so a burst of editor writes
+//! triggers a single rebuild.
+
+use crate::compiler::{
+    FileHash, combine_file_hashes, compute_file_hashes, read_manifest, sha256_hex,
+};
+use crate::error::Result;
+use crate::verbose;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Interval between source polls.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Quiet period a change must survive before it triggers a rebuild.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Polls a skill source directory for settled changes.
+pub struct SourceWatcher {
+    /// Directory the user edits: the source, or the original of an imported skill
+    watch_dir: PathBuf,
+    /// Source the runtime is built from; manifest include paths are relative to it
+    source_dir: PathBuf,
+    runtime_dir: PathBuf,
+    debounce: Duration,
+    /// Source hash of the last build
+    built_hash: Option<String>,
+    /// Observed hash that differs from the last build, and when it was first seen
+    pending: Option<(String, Instant)>,
+}
+
+impl SourceWatcher {
+    /// Create a watcher whose baseline is the current source state.
+    ///
+    /// `watch_dir` is hashed together with the include dependencies in the
+    /// manifest of `runtime_dir`, resolved against `source_dir`. For a skill
+    /// built in place the two directories are the same.
+    pub fn new(
+        watch_dir: &Path,
+        source_dir: &Path,
+        runtime_dir: &Path,
+        debounce: Duration,
+    ) -> Self {
+        let mut watcher = Self {
+            watch_dir: watch_dir.to_path_buf(),
+            source_dir: source_dir.to_path_buf(),
+            runtime_dir: runtime_dir.to_path_buf(),
+            debounce,
+            built_hash: None,
+            pending: None,
+        };
+        watcher.rebuilt();
+        watcher
+    }
+
+    /// Take the current state as the baseline, e.g. after a rebuild that
+    /// changed the recorded include dependencies.
+    pub fn rebuilt(&mut self) {
+        self.built_hash = self.current_hash().ok();
+        self.pending = None;
+    }
+
+    /// Hash of the watched files plus the current include dependencies.
+    fn current_hash(&self) -> Result<String> {
+        let mut hashed = compute_file_hashes(&self.watch_dir)?;
+        let includes = read_manifest(&self.runtime_dir)
+            .map(|manifest| manifest.includes)
+            .unwrap_or_default();
+        for dependency in includes {
+            hashed.push(FileHash {
+                sha256: fs::read(self.source_dir.join(&dependency.path))
+                    .map(|content| sha256_hex(&content))
+                    .unwrap_or_default(),
+                path: dependency.path,
+            });
+        }
+        Ok(combine_file_hashes(&hashed))
+    }
+
+    /// Poll the source once.
+    ///
+    /// Returns true when the source hash differs from the last build and has
+    /// been stable for at least the debounce window. The new hash becomes the
+    /// baseline, so each change is reported once.
+    pub fn poll(&mut self) -> bool {
+        // Files may vanish mid-walk while an editor saves; treat as unsettled
+        let Ok(current) = self.current_hash() else {
+            self.pending = None;
+            return false;
+        };
+
+        if self.built_hash.as_deref() == Some(current.as_str()) {
+            self.pending = None;
+            return false;
+        }
+
+        match &self.pending {
+            Some((hash, since)) if *hash == current => {
+                if since.elapsed() < self.debounce {
+                    return false;
+                }
+                verbose!("watch: source changed (hash={})", &current[..16]);
+                self.built_hash = Some(current);
+                self.pending = None;
+                true
+            }
+            _ => {
+                self.pending = Some((current, Instant::now()));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a skill dir below the temp root (which is itself a hidden dir).
+    fn skill_dir(temp: &TempDir) -> PathBuf {
+        let dir = temp.path().join("skill");
+        fs::create_dir_all(&dir).expect("create skill dir");
+        fs::write(dir.join("SKILL.md"), "# Skill").expect("write file");
+        dir
+    }
+
+    #[test]
+    fn test_poll_ignores_unchanged_source() {
+        let temp = TempDir::new().expect("create temp dir");
+        let dir = skill_dir(&temp);
+
+        let mut watcher =
+            SourceWatcher::new(&dir, &dir, &temp.path().join("runtime"), Duration::ZERO);
+        assert!(!watcher.poll());
+        assert!(!watcher.poll());
+    }
+
+    #[test]
+    fn test_poll_reports_settled_change_once() {
+        let temp = TempDir::new().expect("create temp dir");
+        let dir = skill_dir(&temp);
+
+        let mut watcher =
+            SourceWatcher::new(&dir, &dir, &temp.path().join("runtime"), Duration::ZERO);
+        fs::write(dir.join("SKILL.md"), "# Edited").expect("write file");
+
+        // First sighting starts the debounce window
+        assert!(!watcher.poll());
+        // Stable across the window: report once
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+    }
+
+    #[test]
+    fn test_poll_restarts_debounce_on_further_edits() {
+        let temp = TempDir::new().expect("create temp dir");
+        let dir = skill_dir(&temp);
+
+        let mut watcher = SourceWatcher::new(
+            &dir,
+            &dir,
+            &temp.path().join("runtime"),
+            Duration::from_secs(3600),
+        );
+        fs::write(dir.join("SKILL.md"), "# Edit 1").expect("write file");
+        assert!(!watcher.poll());
+        fs::write(dir.join("SKILL.md"), "# Edit 2").expect("write file");
+        assert!(!watcher.poll());
+        // Window has not elapsed yet
+        assert!(!watcher.poll());
+    }
+
+    #[test]
+    fn test_poll_reports_include_dependency_change() {
+        let temp = TempDir::new().expect("create temp dir");
+        let dir = skill_dir(&temp);
+        let shared = temp.path().join("shared.md");
+        fs::write(&shared, "# Shared").expect("write include");
+        let runtime = temp.path().join("runtime");
+        fs::create_dir_all(runtime.join(".skillc-meta")).expect("create runtime");
+        fs::write(
+            runtime.join(".skillc-meta/manifest.json"),
+            r#"{"skill":"skill","version":5,"built_at":"","source_hash":"","includes":[{"path":"../shared.md","sha256":""}]}"#,
+        )
+        .expect("write manifest");
+
+        let mut watcher = SourceWatcher::new(&dir, &dir, &runtime, Duration::ZERO);
+        assert!(!watcher.poll());
+        fs::write(&shared, "# Shared, edited").expect("edit include");
+        assert!(!watcher.poll());
+        assert!(watcher.poll());
+    }
+}
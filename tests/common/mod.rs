@@ -260,6 +260,22 @@ Performance tips here.
         }
     }
 
+    /// Start a long-running skc command (e.g. `build --watch`) in the project
+    /// or temp directory, with the same isolation as [`Self::run_skc`].
+    pub fn spawn_skc(&self, args: &[&str]) -> std::process::Child {
+        let cwd = self.project_dir.as_deref().unwrap_or(self.temp.path());
+        std::process::Command::new(assert_cmd::cargo::cargo_bin!("skc"))
+            .args(args)
+            .current_dir(cwd)
+            .env("NO_COLOR", "1")
+            .env("SKC_RUN_ID", "TEST-RUN-ID")
+            .env("SKILLC_HOME", &self.mock_home)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .expect("failed to spawn skc")
+    }
+
     /// Run skc command with additional environment variables.
     ///
     /// Per [[RFC-0009:C-ENV-OVERRIDE]], SKILLC_HOME is always set for isolation.
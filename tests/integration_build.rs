@@ -83,6 +83,48 @@ fn test_build_import_from_path() {
     );
 }
 
+/// Test: --watch on an imported skill follows edits to the original directory
+#[test]
+fn test_build_import_watch_follows_original() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let external_skill = ctx.create_external_skill("external-skill");
+    let path = external_skill.to_str().expect("path to str");
+
+    let mut watch = ctx.spawn_skc(&["build", path, "--target", ctx.mock_agent_str(), "--watch"]);
+    let stub = ctx
+        .project_dir()
+        .join(".skillc/runtime/external-skill/SKILL.md");
+    let wait_for = |expected: &str| {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(20);
+        while std::time::Instant::now() < deadline {
+            if fs::read_to_string(&stub).is_ok_and(|s| s.contains(expected)) {
+                return true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        false
+    };
+    assert!(wait_for("external-skill"), "initial build");
+
+    let skill_md = external_skill.join("SKILL.md");
+    let content = fs::read_to_string(&skill_md).expect("read SKILL.md");
+    fs::write(&skill_md, format!("{}\n## Watched Edit\n", content)).expect("edit SKILL.md");
+    let rebuilt = wait_for("Watched Edit");
+
+    let _ = watch.kill();
+    let _ = watch.wait();
+    assert!(rebuilt, "edit to the original directory triggers a rebuild");
+    assert!(
+        fs::read_to_string(
+            ctx.project_dir()
+                .join(".skillc/skills/external-skill/SKILL.md")
+        )
+        .expect("read imported SKILL.md")
+        .contains("Watched Edit"),
+        "store copy re-imported"
+    );
+}
+
 /// Test: Import with --force overwrites existing skill
 #[test]
 fn test_build_import_force_overwrite() {
@@ -394,6 +436,68 @@ fn test_build_copy_overwrites_existing_directory() {
     );
 }
 
+/// Test: Rebuilding refreshes copy deployments outside the requested targets,
+/// and `skc list` flags copies left behind the runtime
+#[test]
+fn test_build_refreshes_other_copy_deployments() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "copy-skill",
+        "---\nname: copy-skill\ndescription: First version\n---\n# Test\n",
+    );
+    let other_agent = ctx.temp_path().join("other-agent");
+    fs::create_dir_all(&other_agent).expect("test operation");
+    let other_str = other_agent.to_str().expect("path to str");
+
+    for target in [ctx.mock_agent_str(), other_str] {
+        ctx.run_skc(&["build", "copy-skill", "--copy", "--target", target])
+            .assert_success("Copy deploy");
+    }
+    let other_copy = other_agent.join("copy-skill");
+    let manifest = other_copy.join(".skillc-meta/manifest.json");
+    let old_manifest = fs::read_to_string(&manifest).expect("read manifest");
+    let registry = ctx.mock_home().join(".skillc/registry.json");
+    let old_registry = fs::read_to_string(&registry).expect("read registry");
+
+    ctx.create_skill_with_content(
+        "copy-skill",
+        "---\nname: copy-skill\ndescription: Second version\n---\n# Test\n",
+    );
+    let result = ctx.run_skc(&[
+        "build",
+        "copy-skill",
+        "--copy",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_success("Rebuild one target");
+    assert!(
+        result.stdout.contains("Refresh:"),
+        "Should report the refreshed copy, got: {}",
+        result.stdout
+    );
+    let stub = fs::read_to_string(other_copy.join("SKILL.md")).expect("read stub");
+    assert!(stub.contains("Second version"), "other copy refreshed");
+
+    // A copy whose manifest predates the runtime is flagged as stale
+    fs::write(&manifest, old_manifest).expect("test operation");
+    fs::write(&registry, old_registry).expect("test operation");
+    let list = ctx.run_skc(&["list", "-o", "json"]);
+    list.assert_success("List");
+    let parsed: serde_json::Value = serde_json::from_str(&list.stdout).expect("JSON list");
+    let stale = parsed["skills"][0]["stale_copies"]
+        .as_array()
+        .expect("stale copies");
+    assert_eq!(stale.len(), 1, "got: {}", list.stdout);
+    assert!(
+        stale[0]
+            .as_str()
+            .is_some_and(|p| p.ends_with("other-agent/copy-skill")),
+        "got: {}",
+        list.stdout
+    );
+}
+
 /// Test: Project-local skills deploy to project agent directory
 #[test]
 fn test_build_project_local_deploys_to_project_agent_dir() {
@@ -437,3 +541,868 @@ fn test_build_project_local_deploys_to_project_agent_dir() {
         "Output should show project agent path"
     );
 }
+
+/// Test: Rebuilding an unchanged skill is a no-op
+#[test]
+fn test_build_up_to_date_is_noop() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("noop-skill");
+
+    let result = ctx.run_skc(&["build", "noop-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("First build");
+
+    let manifest_path = ctx
+        .project_dir()
+        .join(".skillc")
+        .join("runtime")
+        .join("noop-skill")
+        .join(".skillc-meta")
+        .join("manifest.json");
+    let manifest = fs::read_to_string(&manifest_path).expect("read manifest");
+
+    let result = ctx.run_skc(&["build", "noop-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Second build");
+    assert!(
+        result.stdout.contains("noop-skill is up to date"),
+        "Should report up to date, got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("unchanged"),
+        "Should leave deployment untouched, got: {}",
+        result.stdout
+    );
+    assert_eq!(
+        fs::read_to_string(&manifest_path).expect("read manifest"),
+        manifest,
+        "Manifest should not be rewritten"
+    );
+
+    // --force rebuilds anyway
+    let result = ctx.run_skc(&[
+        "build",
+        "noop-skill",
+        "--force",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_success("Forced build");
+    assert!(
+        result.stdout.contains("Built noop-skill"),
+        "Should rebuild with --force, got: {}",
+        result.stdout
+    );
+}
+
+/// Test: --check fails when a rebuild is needed and passes otherwise
+#[test]
+fn test_build_check_mode() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill("check-skill");
+
+    // Never built
+    let result = ctx.run_skc(&[
+        "build",
+        "check-skill",
+        "--check",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_failure("Check before build");
+    assert!(
+        result.stderr.contains("error[E013]"),
+        "Should report E013, got: {}",
+        result.stderr
+    );
+    assert!(
+        !ctx.mock_agent().join("check-skill").exists(),
+        "Check should not deploy"
+    );
+
+    ctx.run_skc(&["build", "check-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&[
+        "build",
+        "check-skill",
+        "--check",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_success("Check after build");
+
+    // Source change makes the runtime stale
+    fs::write(skill_dir.join("extra.md"), "# Extra\n").expect("test operation");
+    let result = ctx.run_skc(&[
+        "build",
+        "check-skill",
+        "--check",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_failure("Check after source change");
+}
+
+/// Test: --all builds every skill and reports failures per skill
+#[test]
+fn test_build_all() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.ensure_global_skills_dir();
+    ctx.create_skill("alpha");
+    ctx.create_skill("beta");
+    ctx.create_skill_with_content("broken", "---\nname: broken\n---\n# Broken\n");
+
+    let result = ctx.run_skc(&["build", "--all", "--target", ctx.mock_agent_str()]);
+    result.assert_failure("Build --all with a broken skill");
+    assert!(
+        result.stdout.contains("failed") && result.stdout.contains("3 skill(s), 1 failed"),
+        "Should report per-skill results, got: {}",
+        result.stdout
+    );
+    assert!(ctx.mock_agent().join("alpha").exists());
+    assert!(ctx.mock_agent().join("beta").exists());
+    assert!(!ctx.mock_agent().join("broken").exists());
+
+    // --pattern narrows the batch
+    let result = ctx.run_skc(&[
+        "build",
+        "--all",
+        "--pattern",
+        "a*",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_success("Build --all --pattern");
+    assert!(
+        result.stdout.contains("alpha") && !result.stdout.contains("beta"),
+        "Pattern should select only alpha, got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("up-to-date"),
+        "Unchanged skill should be up to date, got: {}",
+        result.stdout
+    );
+}
+
+/// Test: --all with `-o json` prints one report per skill, failures included
+#[test]
+fn test_build_all_json_output() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("alpha");
+    ctx.create_skill_with_content("broken", "---\nname: broken\n---\n# Broken\n");
+
+    let result = ctx.run_skc(&[
+        "build",
+        "--all",
+        "--target",
+        ctx.mock_agent_str(),
+        "-o",
+        "json",
+    ]);
+    result.assert_failure("Build --all with a broken skill");
+    let reports: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    let reports = reports.as_array().expect("array of reports");
+    assert_eq!(reports.len(), 2, "got: {:?}", reports);
+
+    let alpha = reports
+        .iter()
+        .find(|r| r["skill"] == "alpha")
+        .expect("alpha report");
+    assert_eq!(alpha["status"], "built");
+    assert_eq!(alpha["source_hash"].as_str().map(str::len), Some(64));
+    assert_eq!(alpha["deployments"].as_array().map(Vec::len), Some(1));
+
+    let broken = reports
+        .iter()
+        .find(|r| r["skill"] == "broken")
+        .expect("broken report");
+    assert_eq!(broken["status"], "failed");
+    assert!(broken["error"].is_string(), "got: {}", broken);
+}
+
+/// Test: A project-level stub template replaces the built-in stub body
+#[test]
+fn test_build_with_project_stub_template() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("templated");
+    fs::write(
+        ctx.project_dir().join(".skillc").join("stub.tmpl"),
+        "# {{name}} for our agents\n\n{{sections}}",
+    )
+    .expect("test operation");
+
+    ctx.run_skc(&["build", "templated", "--target", ctx.mock_agent_str()])
+        .assert_success("Build with template");
+
+    let stub = fs::read_to_string(
+        ctx.project_dir()
+            .join(".skillc")
+            .join("runtime")
+            .join("templated")
+            .join("SKILL.md"),
+    )
+    .expect("read stub");
+    assert!(stub.starts_with("---\nname: templated\n"), "got: {}", stub);
+    assert!(stub.contains("# templated for our agents\n\n- templated\n"));
+    assert!(!stub.contains("## Top Sections"));
+}
+
+/// Test: A stub over budget warns once per build, even with target variants;
+/// in error mode it fails the build
+#[test]
+fn test_build_stub_token_budget() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "budgeted",
+        "---\nname: budgeted\ndescription: Budget test\n---\n\n# Budgeted\n\n<!-- skc:if target=claude -->\nClaude only.\n<!-- skc:endif -->\n<!-- skc:if target=codex -->\nCodex only.\n<!-- skc:endif -->\n",
+    );
+    let config = ctx.project_dir().join(".skillc").join("config.toml");
+
+    fs::write(&config, "[build]\nstub_token_budget = 10\n").expect("test operation");
+    let result = ctx.run_skc(&["build", "budgeted", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Build over budget in warn mode");
+    assert_eq!(
+        result.stderr.matches("warning[W020]").count(),
+        1,
+        "Should warn once, got: {}",
+        result.stderr
+    );
+
+    fs::write(
+        &config,
+        "[build]\nstub_token_budget = 10\nstub_budget_mode = \"error\"\n",
+    )
+    .expect("test operation");
+    let result = ctx.run_skc(&["build", "budgeted", "--target", ctx.mock_agent_str()]);
+    result.assert_failure("Build over budget in error mode");
+    assert!(
+        result.stderr.contains("error[E016]"),
+        "Should fail with E016, got: {}",
+        result.stderr
+    );
+}
+
+/// Test: Include directives are expanded into the index and gateway views
+#[test]
+fn test_build_expands_includes() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n<!-- skc:include ../shared/conventions.md#Safety -->\n",
+    );
+    let shared = skill_dir.parent().expect("store dir").join("shared");
+    fs::create_dir_all(&shared).expect("create shared dir");
+    fs::write(
+        shared.join("conventions.md"),
+        "# Conventions\n\n## Safety\n\nNever force-push to main.\n\n## Style\n\nUse tabs.\n",
+    )
+    .expect("write shared file");
+
+    let result = ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Build with include");
+
+    // The source itself is untouched
+    let source = fs::read_to_string(skill_dir.join("SKILL.md")).expect("read source");
+    assert!(source.contains("skc:include"));
+
+    let show = ctx.run_skc(&["show", "test-skill", "--section", "Safety"]);
+    show.assert_success("Show included section");
+    assert!(
+        show.stdout.contains("Never force-push to main."),
+        "got: {}",
+        show.stdout
+    );
+
+    let search = ctx.run_skc(&["search", "test-skill", "force-push"]);
+    search.assert_success("Search included content");
+    assert!(search.stdout.contains("Safety"), "got: {}", search.stdout);
+
+    // Only the selected section is included
+    let open = ctx.run_skc(&["open", "test-skill", "SKILL.md"]);
+    open.assert_success("Open expanded file");
+    assert!(!open.stdout.contains("Use tabs."), "got: {}", open.stdout);
+
+    // Editing the shared file makes the build stale
+    fs::write(
+        shared.join("conventions.md"),
+        "# Conventions\n\n## Safety\n\nAlways review.\n",
+    )
+    .expect("edit shared file");
+    let check = ctx.run_skc(&["build", "test-skill", "--check"]);
+    check.assert_failure("Check after shared edit");
+}
+
+/// Test: Include cycles fail the build
+#[test]
+fn test_build_rejects_include_cycle() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n<!-- skc:include a.md -->\n",
+    );
+    fs::write(skill_dir.join("a.md"), "<!-- skc:include SKILL.md -->\n").expect("write a.md");
+
+    let result = ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_failure("Build with cycle");
+    assert!(
+        result.stderr.contains("error[E018]"),
+        "got: {}",
+        result.stderr
+    );
+}
+
+/// Test: Target conditionals produce a per-target runtime variant
+#[test]
+fn test_build_target_conditionals() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n## Tools\n\nShared tips.\n\n<!-- skc:if target=claude -->\nUse the Task tool.\n<!-- skc:endif -->\n",
+    );
+
+    let target = format!("claude,{}", ctx.mock_agent_str());
+    let result = ctx.run_skc(&["build", "test-skill", "--target", &target]);
+    result.assert_success("Build with conditionals");
+
+    // Claude gets its own variant, addressed as skill@claude
+    let claude = ctx
+        .project_dir()
+        .join(".claude")
+        .join("skills")
+        .join("test-skill");
+    let stub = fs::read_to_string(claude.join("SKILL.md")).expect("read claude stub");
+    assert!(stub.contains("skc show test-skill@claude"), "got: {}", stub);
+
+    // Other targets get the shared runtime, without the block
+    let stub = fs::read_to_string(ctx.mock_agent().join("test-skill").join("SKILL.md"))
+        .expect("read shared stub");
+    assert!(!stub.contains("@claude"), "got: {}", stub);
+
+    let show = ctx.run_skc(&["show", "test-skill@claude", "--section", "Tools"]);
+    show.assert_success("Show claude variant");
+    assert!(
+        show.stdout.contains("Use the Task tool."),
+        "got: {}",
+        show.stdout
+    );
+
+    let show = ctx.run_skc(&["show", "test-skill", "--section", "Tools"]);
+    show.assert_success("Show shared runtime");
+    assert!(show.stdout.contains("Shared tips."), "got: {}", show.stdout);
+    assert!(!show.stdout.contains("Task tool"), "got: {}", show.stdout);
+    assert!(!show.stdout.contains("skc:if"), "got: {}", show.stdout);
+
+    let search = ctx.run_skc(&["search", "test-skill@claude", "Task"]);
+    search.assert_success("Search claude variant");
+    assert!(search.stdout.contains("Tools"), "got: {}", search.stdout);
+
+    // A second build is a no-op
+    let result = ctx.run_skc(&["build", "test-skill", "--check"]);
+    result.assert_success("Check after build");
+}
+
+/// Test: Unbalanced conditionals fail the build with the offending line
+#[test]
+fn test_build_rejects_unbalanced_conditional() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n<!-- skc:if target=claude -->\nClaude only.\n",
+    );
+
+    let result = ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_failure("Build with unclosed conditional");
+    assert!(
+        result.stderr.contains("error[E018]"),
+        "got: {}",
+        result.stderr
+    );
+    assert!(
+        result
+            .stderr
+            .contains("SKILL.md:8: 'skc:if' is never closed"),
+        "got: {}",
+        result.stderr
+    );
+
+    let lint = ctx.run_skc(&["lint", "test-skill"]);
+    assert!(lint.stdout.contains("SKL206") || lint.stderr.contains("SKL206"));
+}
+
+/// Test: .skillcignore excludes files from import, hashing, indexing, and sources
+#[test]
+fn test_build_respects_skillcignore() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let external_skill = ctx.create_external_skill("ignore-skill");
+    fs::write(external_skill.join(".skillcignore"), "drafts/\n*.log\n").expect("write ignore");
+    fs::create_dir_all(external_skill.join("drafts")).expect("create drafts");
+    fs::write(
+        external_skill.join("drafts").join("idea.md"),
+        "# Idea\n\nUnfinished zeppelin notes.\n",
+    )
+    .expect("write draft");
+    fs::write(external_skill.join("build.log"), "noise").expect("write log");
+
+    ctx.run_skc(&[
+        "build",
+        external_skill.to_str().expect("path to str"),
+        "--target",
+        ctx.mock_agent_str(),
+    ])
+    .assert_success("Build import");
+
+    // Ignored files are not imported
+    let imported = ctx
+        .project_dir()
+        .join(".skillc")
+        .join("skills")
+        .join("ignore-skill");
+    assert!(imported.join(".skillcignore").exists());
+    assert!(!imported.join("drafts").exists());
+    assert!(!imported.join("build.log").exists());
+
+    // Ignored files in the store are not hashed, indexed, or listed
+    fs::create_dir_all(imported.join("drafts")).expect("create drafts");
+    fs::write(
+        imported.join("drafts").join("idea.md"),
+        "# Idea\n\nUnfinished zeppelin notes.\n",
+    )
+    .expect("write draft");
+    ctx.run_skc(&[
+        "build",
+        "ignore-skill",
+        "--check",
+        "--target",
+        ctx.mock_agent_str(),
+    ])
+    .assert_success("Check ignores drafts");
+
+    let search = ctx.run_skc(&["search", "ignore-skill", "zeppelin"]);
+    search.assert_success("Search");
+    assert!(!search.stdout.contains("drafts"), "got: {}", search.stdout);
+
+    let sources = ctx.run_skc(&["sources", "ignore-skill"]);
+    sources.assert_success("Sources");
+    assert!(
+        !sources.stdout.contains("drafts"),
+        "got: {}",
+        sources.stdout
+    );
+}
+
+/// Test: Two builds of the same source are byte-identical
+#[test]
+fn test_build_reproducible_check() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill("repro-skill");
+    fs::write(
+        skill_dir.join("guide.md"),
+        "# Guide\n\n## Usage\n\nRun it.\n",
+    )
+    .expect("write guide");
+
+    let result = ctx.run_skc(&["build", "repro-skill", "--reproducible-check"]);
+    result.assert_success("Reproducible check");
+    assert!(
+        result.stdout.contains("repro-skill builds reproducibly"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(
+        !ctx.project_dir()
+            .join(".skillc/runtime/repro-skill")
+            .exists(),
+        "Check should not write the runtime"
+    );
+}
+
+/// Test: --native renders target-native instruction files in the build and
+/// deploys them where each agent reads them
+#[test]
+fn test_build_native_formats() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\nglobs: \"**/*.rs\"\n---\n\n# Test Skill\n\n<!-- skc:if target=codex -->\n## Codex Notes\n<!-- skc:endif -->\n",
+    );
+
+    let result = ctx.run_skc(&[
+        "build",
+        "test-skill",
+        "--target",
+        "cursor,codex,claude",
+        "--native",
+    ]);
+    result.assert_success("Build with --native");
+
+    let native = ctx
+        .project_dir()
+        .join(".skillc/runtime/test-skill/.skillc-meta/native");
+    let rule_path = ctx.project_dir().join(".cursor/rules/test-skill.mdc");
+    let rule = fs::read_to_string(&rule_path).expect("read deployed rule");
+    assert!(
+        rule.starts_with(
+            "---\ndescription: \"A test skill\"\nglobs: **/*.rs\nalwaysApply: false\n---\n"
+        ),
+        "got: {}",
+        rule
+    );
+    assert_eq!(
+        fs::read_to_string(native.join("cursor/test-skill.mdc")).expect("read rendered rule"),
+        rule
+    );
+
+    let agents_path = ctx.project_dir().join("AGENTS.md");
+    let agents = fs::read_to_string(&agents_path).expect("read AGENTS.md");
+    assert!(
+        agents.contains("skc show test-skill@codex"),
+        "got: {}",
+        agents
+    );
+    assert!(agents.contains("Codex Notes"), "got: {}", agents);
+    assert!(!native.join("claude").exists());
+    assert!(
+        result.stdout.contains("AGENTS.md (native)"),
+        "got: {}",
+        result.stdout
+    );
+
+    // A rebuild without --native keeps the deployed files current
+    ctx.run_skc(&["build", "test-skill", "--target", "claude"])
+        .assert_success("Rebuild");
+    assert!(native.join("cursor/test-skill.mdc").exists());
+    ctx.run_skc(&["verify", "test-skill"])
+        .assert_success("Verify native deployments");
+
+    // Native files go with the skill's other deployments
+    let result = ctx.run_skc(&["undeploy", "test-skill"]);
+    result.assert_success("Undeploy");
+    assert!(!rule_path.exists(), "rule removed");
+    assert!(!agents_path.exists(), "AGENTS.md removed");
+
+    // globs is a known frontmatter field
+    let lint = ctx.run_skc(&["lint", "test-skill"]);
+    assert!(!lint.stderr.contains("SKL109"), "got: {}", lint.stderr);
+}
+
+/// Test: --native never overwrites an instruction file skillc did not deploy
+#[test]
+fn test_build_native_keeps_foreign_files() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill("test-skill");
+    let agents = ctx.project_dir().join("AGENTS.md");
+    fs::write(&agents, "# Our conventions\n").expect("write AGENTS.md");
+
+    let result = ctx.run_skc(&["build", "test-skill", "--target", "codex", "--native"]);
+    result.assert_success("Build with --native");
+    assert!(
+        result.stderr.contains("not deployed by skillc"),
+        "got: {}",
+        result.stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&agents).expect("read AGENTS.md"),
+        "# Our conventions\n"
+    );
+}
+
+/// Test: --all --native deploys every skill's native files
+#[test]
+fn test_build_all_native() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill("alpha");
+    ctx.create_skill("beta");
+
+    ctx.run_skc(&["build", "--all", "--target", "cursor", "--native"])
+        .assert_success("Build --all --native");
+    let rules = ctx.project_dir().join(".cursor/rules");
+    assert!(rules.join("alpha.mdc").exists());
+    assert!(rules.join("beta.mdc").exists());
+}
+
+/// Test: [targets.<name>] in config defines a target that resolves project-locally
+#[test]
+fn test_build_configured_target() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill("test-skill");
+    fs::write(
+        ctx.project_dir().join(".skillc/config.toml"),
+        "[targets.foo]\ndir = \".foo\"\nskills_subdir = \"agent-skills\"\nlayout = \"agents-md\"\n",
+    )
+    .expect("write config");
+
+    let result = ctx.run_skc(&["build", "test-skill", "--target", "foo", "--native"]);
+    result.assert_success("Build to configured target");
+    let deployed = ctx.project_dir().join(".foo/agent-skills/test-skill");
+    assert!(
+        deployed.join("SKILL.md").exists(),
+        "deployed to project-local target dir"
+    );
+    assert!(
+        ctx.project_dir().join("AGENTS.md").exists(),
+        "layout selects the native format"
+    );
+
+    let result = ctx.run_skc(&["list", "--targets", "-o", "json"]);
+    result.assert_success("List targets");
+    let targets: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    let foo = targets
+        .as_array()
+        .expect("targets array")
+        .iter()
+        .find(|t| t["name"] == "foo")
+        .expect("configured target listed");
+    assert_eq!(foo["source"], "config");
+    assert_eq!(foo["layout"], "agents-md");
+    assert!(
+        foo["project_path"]
+            .as_str()
+            .is_some_and(|p| p.ends_with(".foo/agent-skills")),
+        "got: {}",
+        foo
+    );
+
+    let result = ctx.run_skc(&["list", "--targets"]);
+    result.assert_success("List targets");
+    assert!(
+        result.stdout.contains("claude") && result.stdout.contains("foo"),
+        "got: {}",
+        result.stdout
+    );
+
+    ctx.run_skc(&["verify", "test-skill"])
+        .assert_success("Verify finds the configured target deployment");
+}
+
+/// Test: Pre-hooks generate sources; a failing pre-hook aborts with E019
+#[cfg(unix)]
+#[test]
+fn test_build_hooks() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("hook-skill");
+    let config = ctx.project_dir().join(".skillc/config.toml");
+    fs::write(
+        &config,
+        "[build]\npre = [\"printf '# API\\\\n\\\\n## Endpoints\\\\n' > api.md\"]\npost = [\"test -f \\\"$SKILLC_RUNTIME_DIR/SKILL.md\\\" && touch post-ran\"]\n",
+    )
+    .expect("write config");
+
+    ctx.run_skc(&["build", "hook-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build with hooks");
+    let source = ctx.project_dir().join(".skillc/skills/hook-skill");
+    assert!(source.join("post-ran").exists(), "post-hook should run");
+    let result = ctx.run_skc(&["show", "hook-skill", "--section", "Endpoints"]);
+    result.assert_success("Show generated section");
+
+    fs::write(
+        &config,
+        "[build]\npre = [\"echo 'spec invalid' >&2; exit 2\"]\n",
+    )
+    .expect("write config");
+    let result = ctx.run_skc(&[
+        "build",
+        "hook-skill",
+        "--force",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_failure("Build with failing pre-hook");
+    assert!(
+        result
+            .stderr
+            .contains("error[E019]: build hook failed: pre hook"),
+        "got: {}",
+        result.stderr
+    );
+    assert!(
+        result.stderr.contains("spec invalid"),
+        "got: {}",
+        result.stderr
+    );
+}
+
+/// Test: Hooks see the frontmatter name; an up-to-date build and the
+/// reproducible check run no hooks
+#[cfg(unix)]
+#[test]
+fn test_build_hooks_skip_no_op_and_scratch_builds() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "hook-dir",
+        "---\nname: hook-name\ndescription: Hook test\n---\n# Hooks\n",
+    );
+    let pre_log = ctx.temp_path().join("pre.log");
+    let post_log = ctx.temp_path().join("post.log");
+    fs::write(
+        ctx.project_dir().join(".skillc/config.toml"),
+        format!(
+            "[build]\npre = [\"echo \\\"$SKILLC_SKILL\\\" >> {}\"]\npost = [\"echo post >> {}\"]\n",
+            pre_log.display(),
+            post_log.display()
+        ),
+    )
+    .expect("write config");
+    let read_lines = |path: &std::path::Path| -> Vec<String> {
+        fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+
+    let build = ["build", "hook-dir", "--target", ctx.mock_agent_str()];
+    ctx.run_skc(&build).assert_success("First build");
+    assert_eq!(read_lines(&pre_log), ["hook-name"]);
+    assert_eq!(read_lines(&post_log).len(), 1);
+
+    ctx.run_skc(&build).assert_success("Up-to-date build");
+    assert_eq!(read_lines(&pre_log).len(), 1, "up to date, no pre");
+    assert_eq!(read_lines(&post_log).len(), 1, "nothing written, no post");
+
+    ctx.run_skc(&["build", "hook-dir", "--reproducible-check"])
+        .assert_success("Reproducible check");
+    assert_eq!(read_lines(&pre_log).len(), 1, "scratch builds run no hooks");
+    assert_eq!(
+        read_lines(&post_log).len(),
+        1,
+        "scratch builds run no hooks"
+    );
+}
+
+/// Test: Post-hooks vet the staged build; a failing one leaves the previous
+/// build live
+#[cfg(unix)]
+#[test]
+fn test_build_failing_post_hook_keeps_previous_build() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "vetted",
+        "---\nname: vetted\ndescription: Post-hook test\n---\n# Version One\n",
+    );
+    let config = ctx.project_dir().join(".skillc/config.toml");
+    let build = ["build", "vetted", "--target", ctx.mock_agent_str()];
+    ctx.run_skc(&build).assert_success("First build");
+    let runtime_skill = ctx.mock_agent().join("vetted/SKILL.md");
+    let before = fs::read_to_string(&runtime_skill).expect("read runtime SKILL.md");
+
+    fs::write(
+        &config,
+        "[build]\npost = [\"! grep -q Rejected \\\"$SKILLC_RUNTIME_DIR/SKILL.md\\\"\"]\n",
+    )
+    .expect("write config");
+    fs::write(
+        ctx.project_dir().join(".skillc/skills/vetted/SKILL.md"),
+        "---\nname: vetted\ndescription: Rejected\n---\n# Version Two\n",
+    )
+    .expect("edit source");
+    let result = ctx.run_skc(&build);
+    result.assert_failure("Build with failing post-hook");
+    assert!(
+        result
+            .stderr
+            .contains("error[E019]: build hook failed: post hook"),
+        "got: {}",
+        result.stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&runtime_skill).expect("read runtime SKILL.md"),
+        before,
+        "rejected build must not go live"
+    );
+}
+
+/// Test that `section_order = "usage"` keeps accessed sections in a truncated stub
+#[test]
+fn test_build_section_order_usage() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let mut content =
+        String::from("---\nname: busy-skill\ndescription: Many sections\n---\n\n# Busy Skill\n");
+    for i in 1..=20 {
+        content.push_str(&format!("\n## Topic {}\n\nBody {}.\n", i, i));
+    }
+    ctx.create_skill_with_content("busy-skill", &content);
+    fs::write(
+        ctx.project_dir().join(".skillc/config.toml"),
+        "[build]\nsection_order = \"usage\"\n",
+    )
+    .expect("write config");
+    let stub_path = ctx
+        .project_dir()
+        .join(".skillc")
+        .join("runtime")
+        .join("busy-skill")
+        .join("SKILL.md");
+
+    ctx.run_skc(&["build", "busy-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Initial build");
+    let stub = fs::read_to_string(&stub_path).expect("read stub");
+    assert!(!stub.contains("Topic 18"), "stub:\n{}", stub);
+
+    ctx.run_skc(&["show", "busy-skill", "--section", "Topic 18"])
+        .assert_success("Show late section");
+    ctx.run_skc(&["build", "busy-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Rebuild");
+    let stub = fs::read_to_string(&stub_path).expect("read stub");
+    assert!(stub.contains("  - Topic 18\n"), "stub:\n{}", stub);
+    assert!(!stub.contains("Topic 14"), "stub:\n{}", stub);
+    assert!(stub.contains("... (6 more)"), "stub:\n{}", stub);
+}
+
+/// Test that `-o json` reports the build as structured data
+#[test]
+fn test_build_json_output() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("json-skill");
+
+    let result = ctx.run_skc(&[
+        "build",
+        "json-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "-o",
+        "json",
+    ]);
+    result.assert_success("Build with JSON output");
+
+    let report: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    assert_eq!(report["skill"], "json-skill");
+    assert_eq!(report["status"], "built");
+    assert_eq!(report["source_hash"].as_str().map(str::len), Some(64));
+    assert!(
+        report["runtime"]
+            .as_str()
+            .is_some_and(|p| p.contains("runtime"))
+    );
+    assert!(report["headings"].as_u64().unwrap_or(0) > 0);
+    assert!(report["files"].as_u64().unwrap_or(0) > 0);
+    assert!(report["stub_lines"].as_u64().unwrap_or(0) > 0);
+    assert_eq!(report["sections_omitted"], 0);
+    assert!(report["index"]["tokenizer"].is_string());
+
+    let deployments = report["deployments"].as_array().expect("deployments");
+    assert_eq!(deployments.len(), 1);
+    assert_eq!(deployments[0]["unchanged"], false);
+    assert!(
+        deployments[0]["method"] == "symlink" || deployments[0]["method"] == "junction",
+        "got: {}",
+        deployments[0]
+    );
+
+    // A second build is up to date and leaves the deployment alone
+    let result = ctx.run_skc(&[
+        "build",
+        "json-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "-o",
+        "json",
+    ]);
+    let report: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    assert_eq!(report["status"], "up_to_date");
+    assert_eq!(report["deployments"][0]["unchanged"], true);
+}
@@ -0,0 +1,129 @@
+//! Integration tests for `skc diff`
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+
+const SKILL: &str =
+    "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n## Setup\n\nSteps.\n";
+
+/// Test: No changes right after a build
+#[test]
+fn test_diff_no_changes() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content("test-skill", SKILL);
+
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&["diff", "test-skill"]);
+    result.assert_success("Diff");
+    assert!(
+        result
+            .stdout
+            .contains("No changes to test-skill since last build"),
+        "got: {}",
+        result.stdout
+    );
+}
+
+/// Test: Includes and conditional blocks are compared as built, not as on disk
+#[test]
+fn test_diff_no_changes_with_includes_and_conditionals() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n<!-- skc:include ../shared/conventions.md#Safety -->\n\n<!-- skc:if target=claude -->\n## Claude Only\n\nUse the Task tool.\n<!-- skc:endif -->\n",
+    );
+    let shared = skill_dir.parent().expect("store dir").join("shared");
+    fs::create_dir_all(&shared).expect("create shared dir");
+    fs::write(
+        shared.join("conventions.md"),
+        "# Conventions\n\n## Safety\n\nNever force-push to main.\n",
+    )
+    .expect("write shared file");
+
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&["diff", "test-skill"]);
+    result.assert_success("Diff");
+    assert!(
+        result
+            .stdout
+            .contains("No changes to test-skill since last build"),
+        "got: {}",
+        result.stdout
+    );
+}
+
+/// Test: File, heading, and stub changes are all reported
+#[test]
+fn test_diff_reports_changes() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill_with_content("test-skill", SKILL);
+
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        SKILL.replace("## Setup", "## Installation"),
+    )
+    .expect("edit skill");
+    fs::write(skill_dir.join("guide.md"), "# Guide\n").expect("write guide");
+
+    let result = ctx.run_skc(&["diff", "test-skill"]);
+    result.assert_success("Diff");
+    assert!(
+        result.stdout.contains("modified  SKILL.md"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("added     guide.md"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(
+        result
+            .stdout
+            .contains("renamed   SKILL.md: ## Setup -> Installation"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("+++ regenerated/SKILL.md"),
+        "got: {}",
+        result.stdout
+    );
+
+    let json = ctx.run_skc(&["diff", "test-skill", "-o", "json"]);
+    json.assert_success("Diff JSON");
+    let value: serde_json::Value = serde_json::from_str(&json.stdout).expect("valid JSON");
+    assert_eq!(value["skill"], "test-skill");
+    assert_eq!(value["headings"][0]["change"], "renamed");
+    assert_eq!(value["headings"][0]["old_text"], "Setup");
+    assert!(
+        value["stub_diff"]
+            .as_str()
+            .expect("stub diff")
+            .contains("Installation")
+    );
+}
+
+/// Test: Diffing an unbuilt skill asks for a build
+#[test]
+fn test_diff_requires_build() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill("test-skill");
+
+    let result = ctx.run_skc(&["diff", "test-skill"]);
+    result.assert_failure("Diff unbuilt");
+    assert!(
+        result.stderr.contains("error[E013]"),
+        "got: {}",
+        result.stderr
+    );
+}
@@ -0,0 +1,174 @@
+//! Integration tests for `skc doctor`
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+use std::path::Path;
+
+/// Create a fallback log database with one entry.
+fn create_fallback_log(project_dir: &Path, skill_name: &str) {
+    let meta = project_dir
+        .join(".skillc/logs")
+        .join(skill_name)
+        .join(".skillc-meta");
+    fs::create_dir_all(&meta).expect("create fallback dir");
+    let conn = rusqlite::Connection::open(meta.join("logs.db")).expect("create db");
+    conn.execute_batch(
+        "CREATE TABLE access_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp TEXT NOT NULL,
+            run_id TEXT NOT NULL,
+            command TEXT NOT NULL,
+            skill TEXT NOT NULL,
+            skill_path TEXT NOT NULL,
+            cwd TEXT NOT NULL,
+            args TEXT NOT NULL,
+            error TEXT
+        );
+        INSERT INTO access_log (timestamp, run_id, command, skill, skill_path, cwd, args)
+        VALUES ('2026-01-30T10:00:00Z', 'run', 'outline', 'test-skill', '/p', '/c', '{}');",
+    )
+    .expect("write fallback log");
+}
+
+/// Test: a clean environment has no problems
+#[test]
+fn test_doctor_clean() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&["doctor"]);
+    result.assert_success("Doctor");
+    assert!(
+        result.stdout.contains("no problems found"),
+        "got: {}",
+        result.stdout
+    );
+}
+
+/// Test: doctor reports problems with hints and --fix repairs the safe ones
+#[cfg(unix)]
+#[test]
+fn test_doctor_reports_and_fixes() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    ctx.create_skill("copied-skill");
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+    ctx.run_skc(&[
+        "build",
+        "copied-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "--copy",
+    ])
+    .assert_success("Build copy");
+
+    // Copy edited after deploy
+    let copy = ctx.mock_agent().join("copied-skill");
+    fs::write(copy.join("SKILL.md"), "# Edited\n").expect("edit copy");
+    // Link into a runtime that was deleted
+    let claude_dir = ctx.project_dir().join(".claude/skills");
+    fs::create_dir_all(&claude_dir).expect("create agent dir");
+    let broken = claude_dir.join("gone-skill");
+    std::os::unix::fs::symlink(
+        ctx.project_dir().join(".skillc/runtime/gone-skill"),
+        &broken,
+    )
+    .expect("create link");
+    // Index left behind by a moved source
+    let orphan = ctx
+        .project_dir()
+        .join(".skillc/runtime/test-skill/.skillc-meta/search-0000000000000000.db");
+    fs::write(&orphan, "").expect("write orphan index");
+    create_fallback_log(ctx.project_dir(), "test-skill");
+    let config = ctx.project_dir().join(".skillc/config.toml");
+    fs::write(&config, "[build\n").expect("write config");
+
+    let result = ctx.run_skc(&["doctor"]);
+    result.assert_failure("Doctor with problems");
+    assert!(
+        result.stderr.contains("error[E070]"),
+        "got: {}",
+        result.stderr
+    );
+    for kind in [
+        "stale-copy",
+        "broken-link",
+        "orphan-index",
+        "unsynced-logs",
+        "config",
+    ] {
+        assert!(
+            result.stdout.contains(kind),
+            "should report {}: {}",
+            kind,
+            result.stdout
+        );
+    }
+    assert!(result.stdout.contains("hint:"), "got: {}", result.stdout);
+    assert!(
+        broken.symlink_metadata().is_ok(),
+        "no repairs without --fix"
+    );
+
+    // The config problem cannot be fixed automatically
+    let result = ctx.run_skc(&["doctor", "--fix", "-o", "json"]);
+    result.assert_failure("Doctor --fix");
+    let report: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    let problems = report["problems"].as_array().expect("problems");
+    let unfixed: Vec<&str> = problems
+        .iter()
+        .filter(|p| p["fixed"] == false)
+        .filter_map(|p| p["kind"].as_str())
+        .collect();
+    assert_eq!(unfixed, vec!["config"]);
+    assert!(broken.symlink_metadata().is_err(), "broken link removed");
+    assert!(!orphan.exists(), "orphan index deleted");
+    assert!(
+        !ctx.project_dir().join(".skillc/logs/test-skill").exists(),
+        "fallback logs synced"
+    );
+    assert_ne!(
+        fs::read_to_string(copy.join("SKILL.md")).expect("read copy"),
+        "# Edited\n",
+        "copy refreshed"
+    );
+
+    fs::remove_file(&config).expect("remove config");
+    ctx.run_skc(&["doctor"]).assert_success("Doctor after fix");
+}
+
+/// Test: doctor checks the search indexes of target variants too
+#[test]
+fn test_doctor_checks_variant_indexes() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: A test skill\n---\n\n# Test Skill\n\n<!-- skc:if target=codex -->\n## Codex Notes\n<!-- skc:endif -->\n",
+    );
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+    let variant_meta = ctx
+        .project_dir()
+        .join(".skillc/runtime/test-skill/.skillc-meta/targets/codex/.skillc-meta");
+    assert!(variant_meta.is_dir(), "codex variant built");
+    let orphan = variant_meta.join("search-0000000000000000.db");
+    fs::write(&orphan, "").expect("write orphan index");
+
+    let result = ctx.run_skc(&["doctor", "-o", "json"]);
+    result.assert_failure("Doctor with an orphaned variant index");
+    assert!(
+        result.stdout.contains("orphan-index") && result.stdout.contains("targets/codex"),
+        "got: {}",
+        result.stdout
+    );
+
+    ctx.run_skc(&["doctor", "--fix"])
+        .assert_success("Doctor --fix");
+    assert!(!orphan.exists(), "orphan variant index deleted");
+}
@@ -177,3 +177,42 @@ fn test_list_shows_scope() {
         result.stdout
     );
 }
+
+/// Test `skc list` shows the targets recorded in the deployment registry.
+#[test]
+fn test_list_shows_deployments() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("deployed-skill");
+    ctx.create_skill("local-skill");
+    ctx.run_skc(&["build", "deployed-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("build");
+    assert!(ctx.mock_home().join(".skillc/registry.json").exists());
+
+    let result = ctx.run_skc(&["list", "-o", "json"]);
+    result.assert_success("list");
+    let json: serde_json::Value = serde_json::from_str(&result.stdout).expect("parse JSON");
+    let skills = json["skills"].as_array().expect("skills array");
+    let deployed = skills
+        .iter()
+        .find(|s| s["name"] == "deployed-skill")
+        .expect("deployed-skill listed");
+    assert_eq!(deployed["deployments"][0]["target"], ctx.mock_agent_str());
+    assert!(
+        deployed["deployments"][0]["source_hash"]
+            .as_str()
+            .is_some_and(|h| !h.is_empty())
+    );
+    let local = skills
+        .iter()
+        .find(|s| s["name"] == "local-skill")
+        .expect("local-skill listed");
+    assert!(local.get("deployments").is_none());
+
+    let result = ctx.run_skc(&["list"]);
+    result.assert_success("list");
+    assert!(
+        result.stdout.contains("TARGETS") && result.stdout.contains(ctx.mock_agent_str()),
+        "should show deployment target: {}",
+        result.stdout
+    );
+}
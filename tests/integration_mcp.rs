@@ -202,6 +202,10 @@ fn test_mcp_list_tools() {
         "should have skc_sources"
     );
     assert!(tool_names.contains(&"skc_search"), "should have skc_search");
+    assert!(
+        tool_names.contains(&"skc_search_all"),
+        "should have skc_search_all"
+    );
     assert!(tool_names.contains(&"skc_stats"), "should have skc_stats");
     assert!(tool_names.contains(&"skc_build"), "should have skc_build");
     assert!(tool_names.contains(&"skc_init"), "should have skc_init");
@@ -355,6 +359,79 @@ fn test_mcp_lint_tool() {
     );
 }
 
+/// Test skc_build tool returns the `skc build -o json` report
+#[test]
+fn test_mcp_build_tool() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("build-mcp-skill");
+
+    let mut client = McpTestClient::spawn_with_context(&ctx);
+    client.initialize();
+
+    let response = client.call_tool(
+        "skc_build",
+        json!({
+            "skill": "build-mcp-skill",
+            "target": ctx.mock_agent_str()
+        }),
+    );
+
+    let text = response["result"]["content"][0]["text"]
+        .as_str()
+        .expect("should have text");
+    let report: Value = serde_json::from_str(text).expect("should parse as JSON");
+    assert_eq!(report["skill"], "build-mcp-skill");
+    assert_eq!(report["status"], "built");
+    assert!(report["index"]["sections"].as_u64().unwrap_or(0) > 0);
+    let deployments = report["deployments"].as_array().expect("deployments");
+    assert_eq!(deployments.len(), 1);
+    assert!(
+        ctx.mock_agent().join("build-mcp-skill").exists(),
+        "should deploy to the target"
+    );
+}
+
+/// Test skc_build runs project hooks only when the global config opts in
+#[cfg(unix)]
+#[test]
+fn test_mcp_build_tool_hooks_need_opt_in() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("hook-mcp-skill");
+    let marker = ctx.temp_path().join("hook-ran");
+    std::fs::write(
+        ctx.project_dir().join(".skillc/config.toml"),
+        format!(
+            "[build]\nmcp_hooks = true\npre = [\"touch {}\"]\n",
+            marker.display()
+        ),
+    )
+    .expect("write project config");
+
+    let mut client = McpTestClient::spawn_with_context(&ctx);
+    client.initialize();
+    let arguments = json!({
+        "skill": "hook-mcp-skill",
+        "target": ctx.mock_agent_str()
+    });
+    client.call_tool("skc_build", arguments.clone());
+    assert!(
+        !marker.exists(),
+        "a project config cannot opt itself in to MCP hooks"
+    );
+
+    let global = ctx.mock_home().join(".skillc");
+    std::fs::create_dir_all(&global).expect("create global dir");
+    std::fs::write(global.join("config.toml"), "[build]\nmcp_hooks = true\n")
+        .expect("write global config");
+    // Change the source, since an up-to-date build runs no hooks
+    ctx.create_skill_with_content(
+        "hook-mcp-skill",
+        "---\nname: hook-mcp-skill\ndescription: Edited\n---\n# Edited\n",
+    );
+    client.call_tool("skc_build", arguments);
+    assert!(marker.exists(), "global opt-in runs the hooks");
+}
+
 /// Test skc_init tool
 #[test]
 fn test_mcp_init_tool() {
@@ -667,6 +744,36 @@ fn test_mcp_stats_tool_group_by() {
     }
 }
 
+/// Test skc_search_all tool
+#[test]
+fn test_mcp_search_all_tool() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("search-all-skill");
+    ctx.run_skc(&[
+        "build",
+        "search-all-skill",
+        "--target",
+        ctx.mock_agent_str(),
+    ])
+    .assert_success("build");
+
+    let mut client = McpTestClient::spawn_with_context(&ctx);
+    client.initialize();
+
+    let response = client.call_tool("skc_search_all", json!({ "query": "test" }));
+    let result = response.get("result").expect("should have result");
+    let text = result["content"][0]["text"]
+        .as_str()
+        .expect("should have text");
+    let parsed: Value = serde_json::from_str(text).expect("should parse as JSON");
+    assert_eq!(
+        parsed["results"][0]["skill"].as_str(),
+        Some("search-all-skill"),
+        "got: {}",
+        text
+    );
+}
+
 /// Test skc_list tool
 #[test]
 fn test_mcp_list_tool() {
@@ -0,0 +1,81 @@
+//! Integration tests for `skc pack` and `skc install`
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+
+/// Test: A packed skill installs into another store and builds
+#[test]
+fn test_pack_and_install_round_trip() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill("test-skill");
+    fs::create_dir_all(skill_dir.join("refs")).expect("create refs");
+    fs::write(skill_dir.join("refs/guide.md"), "# Guide\n").expect("write guide");
+    fs::write(skill_dir.join(".skillcignore"), "drafts/\n").expect("write ignore");
+    fs::create_dir_all(skill_dir.join("drafts")).expect("create drafts");
+    fs::write(skill_dir.join("drafts/wip.md"), "# WIP\n").expect("write draft");
+
+    let archive = ctx.temp_path().join("out/test-skill.zip");
+    let result = ctx.run_skc(&[
+        "pack",
+        "test-skill",
+        "--output",
+        archive.to_str().expect("UTF-8 path"),
+    ]);
+    result.assert_success("Pack");
+    assert!(result.stdout.contains("Packed test-skill (3 files)"));
+    assert!(archive.exists());
+
+    let result = ctx.run_skc(&[
+        "install",
+        archive.to_str().expect("UTF-8 path"),
+        "--global",
+        "--build",
+        "--target",
+        ctx.mock_agent_str(),
+    ]);
+    result.assert_success("Install");
+    assert!(
+        result.stdout.contains("Installed test-skill (3 files)"),
+        "got: {}",
+        result.stdout
+    );
+
+    let installed = ctx.mock_home().join(".skillc/skills/test-skill");
+    assert_eq!(
+        fs::read_to_string(installed.join("refs/guide.md")).expect("read guide"),
+        "# Guide\n"
+    );
+    assert!(
+        !installed.join("drafts").exists(),
+        "ignored files are not packed"
+    );
+    assert!(ctx.mock_agent().join("test-skill/SKILL.md").exists());
+
+    // Installing again is a no-op
+    let result = ctx.run_skc(&["install", archive.to_str().expect("UTF-8 path"), "--global"]);
+    result.assert_success("Reinstall");
+    assert!(
+        result
+            .stdout
+            .contains("test-skill is already installed (global)")
+    );
+}
+
+/// Test: A corrupt archive is rejected before anything is imported
+#[test]
+fn test_install_rejects_invalid_archive() {
+    let ctx = TestContext::new().with_project();
+    let archive = ctx.temp_path().join("bogus.tar.gz");
+    fs::write(&archive, "not an archive").expect("write archive");
+
+    let result = ctx.run_skc(&["install", archive.to_str().expect("UTF-8 path")]);
+    result.assert_failure("Install bogus archive");
+    assert!(
+        result.stderr.contains("error[E060]"),
+        "got: {}",
+        result.stderr
+    );
+    assert!(!ctx.project_dir().join(".skillc/skills").exists());
+}
@@ -0,0 +1,194 @@
+//! Integration tests for `skc undeploy` and `skc remove`
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+
+/// Test: undeploy removes skillc deployments and leaves other entries alone
+#[test]
+fn test_undeploy() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    ctx.create_skill("other-skill");
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+    let deployed = ctx.mock_agent().join("test-skill");
+    assert!(deployed.exists());
+
+    // A hand-written skill with the same name as a skillc skill is not ours
+    let foreign = ctx.mock_agent().join("other-skill");
+    fs::create_dir_all(&foreign).expect("create foreign skill");
+    fs::write(foreign.join("SKILL.md"), "# Mine\n").expect("write foreign skill");
+
+    let result = ctx.run_skc(&[
+        "undeploy",
+        "test-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "--dry-run",
+    ]);
+    result.assert_success("Undeploy dry run");
+    assert!(
+        result.stdout.contains("Would remove"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(deployed.exists(), "dry run should not remove anything");
+
+    let result = ctx.run_skc(&["undeploy", "test-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Undeploy");
+    assert!(result.stdout.contains("Removed"), "got: {}", result.stdout);
+    assert!(deployed.symlink_metadata().is_err(), "deployment removed");
+
+    let result = ctx.run_skc(&["undeploy", "other-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Undeploy foreign");
+    assert!(
+        result.stdout.contains("not created by skillc"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(foreign.join("SKILL.md").exists(), "foreign skill kept");
+}
+
+/// Test: undeploy finds custom-path deployments through the registry
+#[test]
+fn test_undeploy_uses_registry() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+    let deployed = ctx.mock_agent().join("test-skill");
+    assert!(deployed.exists());
+
+    // No --target: the custom path is only known from the registry
+    let result = ctx.run_skc(&["undeploy", "test-skill"]);
+    result.assert_success("Undeploy");
+    assert!(result.stdout.contains("Removed"), "got: {}", result.stdout);
+    assert!(deployed.symlink_metadata().is_err(), "deployment removed");
+
+    let registry =
+        fs::read_to_string(ctx.mock_home().join(".skillc/registry.json")).expect("read registry");
+    assert!(
+        !registry.contains("test-skill"),
+        "registry entry dropped: {}",
+        registry
+    );
+}
+
+/// Test: undeploy removes links into the same runtime from every target
+#[test]
+fn test_undeploy_custom_and_known_targets() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    let targets = format!("{},claude", ctx.mock_agent_str());
+    ctx.run_skc(&["build", "test-skill", "--target", &targets])
+        .assert_success("Build");
+    let custom = ctx.mock_agent().join("test-skill");
+    let known = ctx.project_dir().join(".claude/skills/test-skill");
+    assert!(custom.exists() && known.exists());
+
+    let result = ctx.run_skc(&["undeploy", "test-skill"]);
+    result.assert_success("Undeploy");
+    assert!(custom.symlink_metadata().is_err(), "custom link removed");
+    assert!(known.symlink_metadata().is_err(), "known link removed");
+}
+
+/// Test: a malformed config is reported once, however many targets are parsed
+#[test]
+fn test_undeploy_warns_once_about_malformed_config() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    fs::write(
+        ctx.project_dir().join(".skillc/config.toml"),
+        "not = [valid\n",
+    )
+    .expect("write config");
+
+    let result = ctx.run_skc(&["undeploy", "test-skill", "--target", "a,b,c", "--dry-run"]);
+    assert_eq!(
+        result.stderr.matches("Failed to parse config file").count(),
+        1,
+        "got: {}",
+        result.stderr
+    );
+}
+
+/// Test: a same-named copy replaced since the deploy is not undeployed
+#[test]
+fn test_undeploy_skips_replaced_copy() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+    ctx.run_skc(&[
+        "build",
+        "test-skill",
+        "--copy",
+        "--target",
+        ctx.mock_agent_str(),
+    ])
+    .assert_success("Copy deploy");
+
+    // Another runtime's build of the same skill now sits at the deployed path
+    let manifest = ctx
+        .mock_agent()
+        .join("test-skill/.skillc-meta/manifest.json");
+    let mut parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("read manifest"))
+            .expect("parse manifest");
+    parsed["source_hash"] = serde_json::Value::from("other-runtime");
+    fs::write(&manifest, parsed.to_string()).expect("write manifest");
+
+    let result = ctx.run_skc(&["undeploy", "test-skill"]);
+    result.assert_success("Undeploy");
+    assert!(
+        result.stdout.contains("not created by skillc"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(manifest.exists(), "replaced copy kept");
+}
+
+/// Test: remove deletes source and runtime, keeping logs unless asked
+#[test]
+fn test_remove() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let source = ctx.create_skill("test-skill");
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+    ctx.run_skc(&["outline", "test-skill"])
+        .assert_success("Outline (writes the access log)");
+    let runtime = ctx.project_dir().join(".skillc/runtime/test-skill");
+    let log_db = runtime.join(".skillc-meta/logs.db");
+    assert!(log_db.exists());
+
+    let result = ctx.run_skc(&["remove", "test-skill", "--dry-run"]);
+    result.assert_success("Remove dry run");
+    assert!(
+        result.stdout.contains("Would delete"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(source.exists() && runtime.join("SKILL.md").exists());
+
+    let result = ctx.run_skc(&["remove", "test-skill", "-o", "json"]);
+    result.assert_success("Remove");
+    let report: serde_json::Value =
+        serde_json::from_str(&result.stdout).expect("stdout should be JSON");
+    assert_eq!(report["skill"], "test-skill");
+    assert!(report["kept_logs"].is_string());
+    assert!(!source.exists(), "source deleted");
+    assert!(!runtime.join("SKILL.md").exists(), "runtime deleted");
+    assert!(log_db.exists(), "logs kept");
+
+    ctx.run_skc(&["remove", "test-skill", "--logs"])
+        .assert_success("Remove logs");
+    assert!(!runtime.exists(), "runtime and logs deleted");
+
+    let result = ctx.run_skc(&["remove", "test-skill"]);
+    result.assert_failure("Remove missing skill");
+    assert!(
+        result.stderr.contains("error[E001]"),
+        "got: {}",
+        result.stderr
+    );
+}
@@ -0,0 +1,98 @@
+//! Integration tests for retained builds and `skc rollback`
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+
+/// Test: rebuilds retain the previous runtime, and rollback restores it
+/// and refreshes copy deployments
+#[test]
+fn test_rollback_restores_previous_build() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: First version\n---\n# Test\n",
+    );
+    let build = [
+        "build",
+        "test-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "--copy",
+    ];
+    ctx.run_skc(&build).assert_success("First build");
+
+    ctx.create_skill_with_content(
+        "test-skill",
+        "---\nname: test-skill\ndescription: Second version\n---\n# Test\n",
+    );
+    ctx.run_skc(&build).assert_success("Second build");
+
+    let runtime = ctx.project_dir().join(".skillc/runtime/test-skill");
+    let deployed = ctx.mock_agent().join("test-skill");
+    let read_stub =
+        |dir: &std::path::Path| fs::read_to_string(dir.join("SKILL.md")).expect("read stub");
+    assert!(read_stub(&runtime).contains("Second version"));
+    assert!(read_stub(&deployed).contains("Second version"));
+
+    let retained = ctx
+        .project_dir()
+        .join(".skillc/runtime/.skillc-builds/test-skill/1");
+    assert!(
+        read_stub(&retained).contains("First version"),
+        "previous runtime retained"
+    );
+
+    let list = ctx.run_skc(&["rollback", "test-skill", "--list", "-o", "json"]);
+    list.assert_success("List builds");
+    let history: serde_json::Value = serde_json::from_str(&list.stdout).expect("JSON history");
+    assert_eq!(history["retained"][0]["id"], 1);
+
+    let result = ctx.run_skc(&["rollback", "test-skill"]);
+    result.assert_success("Rollback");
+    assert!(
+        result.stdout.contains("Rolled back test-skill to build 1"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(read_stub(&runtime).contains("First version"));
+    assert!(
+        read_stub(&deployed).contains("First version"),
+        "copy deployment refreshed"
+    );
+
+    // The replaced build is retained in turn, so the rollback can be undone
+    ctx.run_skc(&["rollback", "test-skill", "--to", "2"])
+        .assert_success("Roll forward");
+    assert!(read_stub(&runtime).contains("Second version"));
+    ctx.run_skc(&["verify", "test-skill"])
+        .assert_success("Verify after roll forward");
+}
+
+/// Test: rollback without a retained build fails with E080
+#[test]
+fn test_rollback_without_retained_build() {
+    let ctx = TestContext::new().with_project();
+    ctx.create_skill("test-skill");
+    ctx.run_skc(&["build", "test-skill"])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&["rollback", "test-skill"]);
+    result.assert_failure("Rollback");
+    assert!(
+        result.stderr.contains("error[E080]"),
+        "got: {}",
+        result.stderr
+    );
+
+    let result = ctx.run_skc(&["rollback", "test-skill", "--to", "7"]);
+    result.assert_failure("Rollback to missing build");
+    assert!(
+        result
+            .stderr
+            .contains("build 7 of 'test-skill' is not retained"),
+        "got: {}",
+        result.stderr
+    );
+}
@@ -74,3 +74,234 @@ fn test_search_limit() {
     ]);
     assert_snapshot!("search_limit", output);
 }
+
+/// Files matched by a JSON search.
+fn matched_files(ctx: &TestContext, args: &[&str]) -> Vec<String> {
+    let result = ctx.run_skc(args);
+    result.assert_success("search");
+    let parsed: serde_json::Value = serde_json::from_str(&result.stdout).expect("JSON output");
+    parsed["results"]
+        .as_array()
+        .expect("results array")
+        .iter()
+        .map(|r| r["file"].as_str().expect("file").to_string())
+        .collect()
+}
+
+#[test]
+fn test_search_raw_query() {
+    let ctx = TestContext::new()
+        .with_rich_skill("test-skill")
+        .with_mock_agent();
+    build_skill(&ctx);
+    let skill = ctx.skill_name();
+
+    for query in ["section:performance", "perf*", "nonexistent OR tips"] {
+        let files = matched_files(&ctx, &["search", skill, query, "--raw", "-o", "json"]);
+        assert!(
+            !files.is_empty() && files.iter().all(|f| f == "docs/advanced.md"),
+            "query: {}, got: {:?}",
+            query,
+            files
+        );
+    }
+    let files = matched_files(
+        &ctx,
+        &[
+            "search",
+            skill,
+            "tips NOT performance",
+            "--raw",
+            "-o",
+            "json",
+        ],
+    );
+    assert!(files.is_empty(), "got: {:?}", files);
+
+    let result = ctx.run_skc(&["search", skill, "tips OR", "--raw"]);
+    result.assert_failure("malformed raw query");
+    assert!(
+        result.stderr.contains("error[E005]"),
+        "got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_search_phrase_query() {
+    let ctx = TestContext::new()
+        .with_rich_skill("test-skill")
+        .with_mock_agent();
+    build_skill(&ctx);
+    let skill = ctx.skill_name();
+
+    let files = matched_files(
+        &ctx,
+        &[
+            "search",
+            skill,
+            "performance tips",
+            "--phrase",
+            "-o",
+            "json",
+        ],
+    );
+    assert!(
+        !files.is_empty() && files.iter().all(|f| f == "docs/advanced.md"),
+        "got: {:?}",
+        files
+    );
+    let files = matched_files(
+        &ctx,
+        &[
+            "search",
+            skill,
+            "tips performance",
+            "--phrase",
+            "-o",
+            "json",
+        ],
+    );
+    assert!(files.is_empty(), "words out of order: {:?}", files);
+
+    ctx.run_skc(&["search", skill, "tips", "--phrase", "--raw"])
+        .assert_failure("--phrase with --raw");
+}
+
+#[test]
+fn test_search_all_skills() {
+    let ctx = TestContext::new()
+        .with_rich_skill("test-skill")
+        .with_mock_agent();
+    build_skill(&ctx);
+    ctx.create_skill_with_content(
+        "other-skill",
+        "---\nname: other-skill\ndescription: Another skill\n---\n# Other\n\n## Tuning\n\nPerformance tuning guide.\n",
+    );
+    ctx.run_skc(&["build", "other-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("build other skill");
+
+    let result = ctx.run_skc(&["search", "--all", "performance", "-o", "json"]);
+    result.assert_success("search --all");
+    let parsed: serde_json::Value = serde_json::from_str(&result.stdout).expect("JSON output");
+    let results = parsed["results"].as_array().expect("results array");
+    let skills: Vec<&str> = results
+        .iter()
+        .map(|r| r["skill"].as_str().expect("skill name"))
+        .collect();
+    assert!(
+        skills.contains(&"test-skill") && skills.contains(&"other-skill"),
+        "got: {:?}",
+        skills
+    );
+    // Scores are normalized to the best match across skills
+    assert_eq!(results[0]["score"].as_f64(), Some(1.0));
+    assert!(results.iter().all(|r| r["score"].as_f64() <= Some(1.0)));
+
+    // One access is logged per skill with a hit
+    for skill in ["test-skill", "other-skill"] {
+        let stats = ctx.run_skc(&["stats", skill, "--group-by", "search", "-o", "json"]);
+        stats.assert_success("stats");
+        assert!(
+            stats.stdout.contains("performance"),
+            "got: {}",
+            stats.stdout
+        );
+    }
+
+    ctx.run_skc(&["search", "--all", "test-skill", "performance"])
+        .assert_failure("--all with a skill name");
+}
+
+/// Test: A skill's weak match ranks below another skill's strong one
+#[test]
+fn test_search_all_ranks_across_skills() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let filler =
+        "## Setup\n\nInstall the tool.\n\n## Usage\n\nRun the tool.\n\n## Notes\n\nNothing else.\n";
+    ctx.create_skill_with_content(
+        "basic-skill",
+        &format!(
+            "---\nname: basic-skill\ndescription: Weak match\n---\n# Weak\n\n## Overview\n\nA long section that covers many unrelated topics in passing, \
+             from configuration files and command line flags to release notes, and only \
+             once mentions caching before moving on to other things entirely.\n\n{}",
+            filler
+        ),
+    );
+    ctx.create_skill_with_content(
+        "expert-skill",
+        &format!(
+            "---\nname: expert-skill\ndescription: Strong match\n---\n# Strong\n\n## Caching\n\nCaching, caching, caching.\n\n{}",
+            filler
+        ),
+    );
+    for skill in ["basic-skill", "expert-skill"] {
+        ctx.run_skc(&["build", skill, "--target", ctx.mock_agent_str()])
+            .assert_success("build skill");
+    }
+
+    let result = ctx.run_skc(&["search", "--all", "caching", "-o", "json"]);
+    result.assert_success("search --all");
+    let parsed: serde_json::Value = serde_json::from_str(&result.stdout).expect("JSON output");
+    let results = parsed["results"].as_array().expect("results array");
+    let ranked: Vec<(&str, f64)> = results
+        .iter()
+        .map(|r| {
+            (
+                r["skill"].as_str().expect("skill name"),
+                r["score"].as_f64().expect("score"),
+            )
+        })
+        .collect();
+    // Ties would put basic-skill first, so only its weaker score ranks it last
+    assert_eq!(ranked[0], ("expert-skill", 1.0), "got: {:?}", ranked);
+    let basic = ranked
+        .iter()
+        .position(|(skill, _)| *skill == "basic-skill")
+        .expect("basic-skill matches");
+    assert!(ranked[basic].1 < 1.0, "got: {:?}", ranked);
+    assert!(
+        ranked[basic..]
+            .iter()
+            .all(|(skill, _)| *skill == "basic-skill"),
+        "got: {:?}",
+        ranked
+    );
+}
+
+#[test]
+fn test_search_csv_rows() {
+    let ctx = TestContext::new()
+        .with_rich_skill("test-skill")
+        .with_mock_agent();
+    let skill_dir = ctx.project_dir().join(".skillc/skills/test-skill");
+    std::fs::write(
+        skill_dir.join("docs/errors.csv"),
+        "code,message,fix\nE041,Connection refused,Start the server\nE042,\"Request timeout, retrying\",Raise the limit\n",
+    )
+    .expect("write csv");
+    build_skill(&ctx);
+    let skill = ctx.skill_name();
+
+    let result = ctx.run_skc(&["search", skill, "timeout", "-o", "json"]);
+    result.assert_success("search csv");
+    let parsed: serde_json::Value = serde_json::from_str(&result.stdout).expect("JSON output");
+    let results = parsed["results"].as_array().expect("results array");
+    assert_eq!(results.len(), 1, "got: {}", result.stdout);
+    assert_eq!(results[0]["file"], "docs/errors.csv");
+    assert_eq!(results[0]["section"], "E042");
+
+    let expected = "code: E042\nmessage: Request timeout, retrying\nfix: Raise the limit";
+    for locator in ["docs/errors.csv#E042", "docs/errors.csv#2"] {
+        let output = ctx.run_skc_text(&["open", skill, locator]);
+        assert_eq!(output, expected, "locator: {}", locator);
+    }
+
+    let result = ctx.run_skc(&["open", skill, "docs/errors.csv#E999"]);
+    result.assert_failure("missing row");
+    assert!(
+        result.stderr.contains("error[E020]"),
+        "got: {}",
+        result.stderr
+    );
+}
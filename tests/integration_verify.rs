@@ -0,0 +1,102 @@
+//! Integration tests for `skc verify` per [[RFC-0001:C-MANIFEST]]
+
+mod common;
+
+use common::TestContext;
+use std::fs;
+
+/// Test: A freshly built and deployed skill verifies clean
+#[test]
+fn test_verify_clean_build() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    let result = ctx.run_skc(&["verify", "test-skill", "--target", ctx.mock_agent_str()]);
+    result.assert_success("Verify");
+    assert!(
+        result.stdout.contains("No drift detected"),
+        "got: {}",
+        result.stdout
+    );
+}
+
+/// Test: Source edits after a build are reported per file
+#[test]
+fn test_verify_reports_source_drift() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    let skill_dir = ctx.create_skill("test-skill");
+    fs::write(skill_dir.join("ref.md"), "# Ref\n").expect("write ref");
+
+    ctx.run_skc(&["build", "test-skill", "--target", ctx.mock_agent_str()])
+        .assert_success("Build");
+
+    fs::write(skill_dir.join("ref.md"), "# Ref\n\nEdited.\n").expect("edit ref");
+    fs::write(skill_dir.join("new.md"), "# New\n").expect("write new");
+
+    let result = ctx.run_skc(&["verify", "test-skill"]);
+    result.assert_failure("Verify with drift");
+    assert!(
+        result.stderr.contains("error[E017]"),
+        "got: {}",
+        result.stderr
+    );
+    assert!(
+        result.stdout.contains("modified  ref.md"),
+        "got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("added     new.md"),
+        "got: {}",
+        result.stdout
+    );
+}
+
+/// Test: Edits to a deployed copy are reported against the runtime
+#[test]
+fn test_verify_reports_copy_drift() {
+    let ctx = TestContext::new().with_project().with_mock_agent();
+    ctx.create_skill("test-skill");
+
+    ctx.run_skc(&[
+        "build",
+        "test-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "--copy",
+    ])
+    .assert_success("Build");
+
+    let deployed = ctx.mock_agent().join("test-skill");
+    fs::write(deployed.join("SKILL.md"), "tampered").expect("tamper copy");
+
+    let result = ctx.run_skc(&[
+        "verify",
+        "test-skill",
+        "--target",
+        ctx.mock_agent_str(),
+        "-o",
+        "json",
+    ]);
+    result.assert_failure("Verify with copy drift");
+
+    let json: serde_json::Value = serde_json::from_str(&result.stdout).expect("valid JSON");
+    let locations = json["locations"].as_array().expect("locations array");
+    let copy = locations
+        .iter()
+        .find(|l| l["location"] == ctx.mock_agent_str())
+        .expect("deployment location");
+    assert_eq!(copy["changes"][0]["path"], "SKILL.md");
+    assert_eq!(copy["changes"][0]["change"], "modified");
+
+    // Source and runtime are unaffected
+    for location in locations
+        .iter()
+        .filter(|l| l["location"] != ctx.mock_agent_str())
+    {
+        assert!(location["changes"].as_array().expect("changes").is_empty());
+    }
+}